@@ -24,6 +24,14 @@ fn main() {
         Space::Rec2020,
         Space::XyzD50,
         Space::XyzD65,
+        Space::Hct,
+        Space::LabD65,
+        Space::LchD65,
+        Space::Jzazbz,
+        Space::Jzczhz,
+        Space::Ipt,
+        Space::ICtCp,
+        Space::Hsv,
     ]
     .map(|space| left.interpolate(&right, space));
 
@@ -38,24 +46,9 @@ fn main() {
 
             let interp_index = y / (height / interps.len() as u32);
 
-            let c = interps[interp_index as usize]
-                .at(t)
-                .to_space(Space::Srgb)
-                .map_into_gamut_limits();
+            let [r, g, b] = interps[interp_index as usize].at(t).to_srgb_u8_mapped();
 
-            assert!(
-                c.in_gamut(),
-                "Out of gamut limits: {:?} {}",
-                interps[interp_index as usize].space,
-                c.components
-            );
-
-            *pixel = Rgba([
-                (c.components.0.clamp(0.0, 1.0) * 255.0).round() as u8,
-                (c.components.1.clamp(0.0, 1.0) * 255.0).round() as u8,
-                (c.components.2.clamp(0.0, 1.0) * 255.0).round() as u8,
-                255,
-            ]);
+            *pixel = Rgba([r, g, b, 255]);
         }
     });
 
@@ -0,0 +1,73 @@
+//! Compare converting a 1000x1000 buffer of components with
+//! `Color::convert_many` against converting each color with
+//! `Color::to_space`.
+//!
+//! Run with `cargo bench -p camelion --bench convert_many`.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use camelion::{Color, Component, Components, Space};
+
+const WIDTH: usize = 1000;
+const HEIGHT: usize = 1000;
+const RUNS: u32 = 5;
+
+/// Run `f` a few times and return the fastest run.
+fn fastest(mut f: impl FnMut()) -> Duration {
+    (0..RUNS)
+        .map(|_| {
+            let start = Instant::now();
+            f();
+            start.elapsed()
+        })
+        .min()
+        .unwrap()
+}
+
+fn main() {
+    let pairs = [
+        (Space::Srgb, Space::Srgb),
+        (Space::XyzD65, Space::SrgbLinear),
+        (Space::Srgb, Space::DisplayP3),
+        (Space::Srgb, Space::Oklch),
+        (Space::Lab, Space::Oklab),
+        (Space::DisplayP3, Space::Hsl),
+    ];
+
+    for (from, to) in pairs {
+        // A gradient of valid colors in the source color space.
+        let src = (0..WIDTH * HEIGHT)
+            .map(|i| {
+                let x = (i % WIDTH) as Component / (WIDTH - 1) as Component;
+                let y = (i / WIDTH) as Component / (HEIGHT - 1) as Component;
+                Color::new(Space::Srgb, x, y, 1.0 - x, 1.0)
+                    .to_space(from)
+                    .components
+            })
+            .collect::<Vec<_>>();
+        let mut out = vec![Components(0.0, 0.0, 0.0); src.len()];
+
+        let many = fastest(|| {
+            Color::convert_many(black_box(&src), from, to, &mut out);
+            black_box(&out);
+        });
+
+        let each = fastest(|| {
+            for (src, out) in src.iter().zip(out.iter_mut()) {
+                let Components(c0, c1, c2) = *src;
+                *out = Color::new(from, c0, c1, c2, 1.0).to_space(to).components;
+            }
+            black_box(&out);
+        });
+
+        println!(
+            "{:>12} -> {:<12} convert_many: {:>8.2?}  to_space: {:>8.2?}  ({:.1}x)",
+            from.name(),
+            to.name(),
+            many,
+            each,
+            each.as_secs_f64() / many.as_secs_f64()
+        );
+    }
+}
@@ -0,0 +1,243 @@
+//! Implementations of the `approx` traits for colors, components and models,
+//! so they can be compared with `assert_abs_diff_eq!` and
+//! `assert_relative_eq!`.
+//!
+//! Hue components are compared along the shorter arc between them, e.g. 359
+//! degrees and 1 degree are 2 degrees apart. Missing (NaN) components are only
+//! equal to other missing components.
+
+use approx::{AbsDiffEq, RelativeEq};
+
+use crate::{
+    color_space::ColorSpace,
+    math::closest_hue,
+    models::{
+        encoding::GammaEncoding, Hct, Hsl, Hsv, Hwb, ICtCp, Ipt, Polar, Rectangular, Rgb,
+        WhitePoint, Xyz,
+    },
+    Color, Component, Components,
+};
+
+/// Compare two components with `eq`. If `is_hue` is true, `right` is first
+/// moved by full turns to be as close as possible to `left`.
+fn component_eq(
+    left: Component,
+    right: Component,
+    is_hue: bool,
+    eq: &impl Fn(Component, Component) -> bool,
+) -> bool {
+    if left.is_nan() || right.is_nan() {
+        return left.is_nan() && right.is_nan();
+    }
+
+    let right = if is_hue {
+        closest_hue(right, left)
+    } else {
+        right
+    };
+
+    eq(left, right)
+}
+
+/// Compare each of the components with `eq`, where the component at
+/// `hue_index` is a hue.
+fn components_eq(
+    left: &Components,
+    right: &Components,
+    hue_index: Option<usize>,
+    eq: impl Fn(Component, Component) -> bool,
+) -> bool {
+    (0..3).all(|i| component_eq(left[i], right[i], hue_index == Some(i), &eq))
+}
+
+impl AbsDiffEq for Components {
+    type Epsilon = Component;
+
+    fn default_epsilon() -> Self::Epsilon {
+        Component::default_epsilon()
+    }
+
+    fn abs_diff_eq(&self, other: &Self, epsilon: Self::Epsilon) -> bool {
+        components_eq(self, other, None, |l, r| l.abs_diff_eq(&r, epsilon))
+    }
+}
+
+impl RelativeEq for Components {
+    fn default_max_relative() -> Self::Epsilon {
+        Component::default_max_relative()
+    }
+
+    fn relative_eq(
+        &self,
+        other: &Self,
+        epsilon: Self::Epsilon,
+        max_relative: Self::Epsilon,
+    ) -> bool {
+        components_eq(self, other, None, |l, r| {
+            l.relative_eq(&r, epsilon, max_relative)
+        })
+    }
+}
+
+impl AbsDiffEq for Color {
+    type Epsilon = Component;
+
+    fn default_epsilon() -> Self::Epsilon {
+        Component::default_epsilon()
+    }
+
+    fn abs_diff_eq(&self, other: &Self, epsilon: Self::Epsilon) -> bool {
+        self.approx_eq_with(other, |l, r| l.abs_diff_eq(&r, epsilon))
+    }
+}
+
+impl RelativeEq for Color {
+    fn default_max_relative() -> Self::Epsilon {
+        Component::default_max_relative()
+    }
+
+    fn relative_eq(
+        &self,
+        other: &Self,
+        epsilon: Self::Epsilon,
+        max_relative: Self::Epsilon,
+    ) -> bool {
+        self.approx_eq_with(other, |l, r| l.relative_eq(&r, epsilon, max_relative))
+    }
+}
+
+/// Implement the `approx` traits for a model, where `hue_index` is the index
+/// of its hue component, if it has one.
+macro_rules! impl_model_approx_eq {
+    ($model:ty, $hue_index:expr $(, $g:ident : $b:path)*) => {
+        impl<$($g: $b),*> AbsDiffEq for $model {
+            type Epsilon = Component;
+
+            fn default_epsilon() -> Self::Epsilon {
+                Component::default_epsilon()
+            }
+
+            fn abs_diff_eq(&self, other: &Self, epsilon: Self::Epsilon) -> bool {
+                components_eq(
+                    &self.to_components(),
+                    &other.to_components(),
+                    $hue_index,
+                    |l, r| l.abs_diff_eq(&r, epsilon),
+                )
+            }
+        }
+
+        impl<$($g: $b),*> RelativeEq for $model {
+            fn default_max_relative() -> Self::Epsilon {
+                Component::default_max_relative()
+            }
+
+            fn relative_eq(
+                &self,
+                other: &Self,
+                epsilon: Self::Epsilon,
+                max_relative: Self::Epsilon,
+            ) -> bool {
+                components_eq(
+                    &self.to_components(),
+                    &other.to_components(),
+                    $hue_index,
+                    |l, r| l.relative_eq(&r, epsilon, max_relative),
+                )
+            }
+        }
+    };
+}
+
+impl_model_approx_eq!(Rgb<S, E>, None, S: ColorSpace, E: GammaEncoding);
+impl_model_approx_eq!(Rectangular<S>, None, S: ColorSpace);
+impl_model_approx_eq!(Polar<S>, Some(2), S: ColorSpace);
+impl_model_approx_eq!(Xyz<W>, None, W: WhitePoint);
+impl_model_approx_eq!(Hsl, Some(0));
+impl_model_approx_eq!(Hwb, Some(0));
+impl_model_approx_eq!(Hsv, Some(0));
+impl_model_approx_eq!(Hct, Some(0));
+impl_model_approx_eq!(Ipt, None);
+impl_model_approx_eq!(ICtCp, None);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        models::{Lab, Oklch, Srgb},
+        Space,
+    };
+    use approx::{assert_abs_diff_eq, assert_abs_diff_ne, assert_relative_eq};
+
+    #[test]
+    fn compare_colors() {
+        let left = Color::new(Space::Oklch, 0.5, 0.1, 359.9, 1.0);
+        let right = Color::new(Space::Oklch, 0.5, 0.1, 0.05, 1.0);
+        assert_abs_diff_eq!(left, right, epsilon = 0.2);
+        assert_abs_diff_ne!(left, right, epsilon = 0.1);
+
+        // Colors in different color spaces are never equal.
+        let srgb = Color::new(Space::Srgb, 0.5, 0.1, 0.0, 1.0);
+        let p3 = Color::new(Space::DisplayP3, 0.5, 0.1, 0.0, 1.0);
+        assert_abs_diff_ne!(srgb, p3, epsilon = 1.0);
+
+        // Missing components are only equal to other missing components.
+        let missing = Color::new(Space::Srgb, 0.5, None, 0.0, None);
+        assert_abs_diff_eq!(missing, missing.clone(), epsilon = 0.0);
+        assert_abs_diff_ne!(missing, srgb, epsilon = 1.0);
+
+        // The values stored for missing components are ignored, like with
+        // `PartialEq` and `Color::approx_eq`.
+        let mut stored = missing.clone();
+        stored.components.1 = 0.75;
+        assert_abs_diff_eq!(missing, stored, epsilon = 0.0);
+        assert!(missing.approx_eq(&stored, 0.0));
+        assert!(left.approx_eq(&right, 0.2) && !left.approx_eq(&right, 0.1));
+
+        let red = Color::new(Space::Srgb, 1.0, 0.0, 0.0, 1.0);
+        assert_relative_eq!(
+            red,
+            red.to_space(Space::Lab).to_space(Space::Srgb),
+            epsilon = 1.0e-4
+        );
+    }
+
+    #[test]
+    fn compare_models() {
+        assert_abs_diff_eq!(
+            Srgb::new(0.1, 0.2, 0.3),
+            Srgb::new(0.1, 0.2, 0.3001),
+            epsilon = 1.0e-3
+        );
+        assert_abs_diff_ne!(
+            Lab::new(50.0, 1.0, 359.0),
+            Lab::new(50.0, 1.0, -1.0),
+            epsilon = 1.0
+        );
+        assert_abs_diff_eq!(
+            Oklch::new(0.5, 0.1, 359.0),
+            Oklch::new(0.5, 0.1, -1.0),
+            epsilon = 1.0e-3
+        );
+        assert_abs_diff_eq!(
+            Hsl::new(1.0, 0.5, 0.5),
+            Hsl::new(361.0, 0.5, 0.5),
+            epsilon = 1.0e-3
+        );
+        assert_abs_diff_eq!(
+            Oklch::new(0.5, 0.0, Component::NAN),
+            Oklch::new(0.5, 0.0, Component::NAN),
+            epsilon = 1.0e-3
+        );
+        assert_abs_diff_ne!(
+            Oklch::new(0.5, 0.0, Component::NAN),
+            Oklch::new(0.5, 0.0, 0.0),
+            epsilon = 1.0e-3
+        );
+        assert_abs_diff_eq!(
+            Components(0.1, 0.2, 0.3),
+            Components(0.1, 0.2, 0.3),
+            epsilon = 0.0
+        );
+    }
+}
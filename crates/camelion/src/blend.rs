@@ -0,0 +1,196 @@
+//! Separable blend modes.
+//! <https://drafts.fxtf.org/compositing-1/#blending>
+
+use crate::{Color, Component, Components, Space};
+
+/// The separable blend modes, applied to each color component independently.
+/// <https://drafts.fxtf.org/compositing-1/#blendingseparable>
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum BlendMode {
+    /// The source color replaces the backdrop.
+    Normal,
+    /// Multiplies the backdrop and source colors.
+    Multiply,
+    /// Multiplies the complements of the backdrop and source colors, then
+    /// complements the result.
+    Screen,
+    /// Multiplies or screens the colors, depending on the backdrop color.
+    Overlay,
+    /// Selects the darker of the backdrop and source colors.
+    Darken,
+    /// Selects the lighter of the backdrop and source colors.
+    Lighten,
+    /// Brightens the backdrop color to reflect the source color.
+    ColorDodge,
+    /// Darkens the backdrop color to reflect the source color.
+    ColorBurn,
+    /// Multiplies or screens the colors, depending on the source color.
+    HardLight,
+    /// Darkens or lightens the colors, depending on the source color.
+    SoftLight,
+    /// Subtracts the darker of the two colors from the lighter color.
+    Difference,
+    /// Similar to [`BlendMode::Difference`], but with lower contrast.
+    Exclusion,
+}
+
+impl BlendMode {
+    /// Blend a single backdrop component (`cb`) with a source component
+    /// (`cs`).
+    fn blend(&self, cb: Component, cs: Component) -> Component {
+        let multiply = |cb: Component, cs: Component| cb * cs;
+        let screen = |cb: Component, cs: Component| cb + cs - cb * cs;
+        let hard_light = |cb: Component, cs: Component| {
+            if cs <= 0.5 {
+                multiply(cb, 2.0 * cs)
+            } else {
+                screen(cb, 2.0 * cs - 1.0)
+            }
+        };
+
+        match self {
+            BlendMode::Normal => cs,
+            BlendMode::Multiply => multiply(cb, cs),
+            BlendMode::Screen => screen(cb, cs),
+            BlendMode::Overlay => hard_light(cs, cb),
+            BlendMode::Darken => cb.min(cs),
+            BlendMode::Lighten => cb.max(cs),
+            BlendMode::ColorDodge => {
+                if cb == 0.0 {
+                    0.0
+                } else if cs >= 1.0 {
+                    1.0
+                } else {
+                    (cb / (1.0 - cs)).min(1.0)
+                }
+            }
+            BlendMode::ColorBurn => {
+                if cb >= 1.0 {
+                    1.0
+                } else if cs == 0.0 {
+                    0.0
+                } else {
+                    1.0 - ((1.0 - cb) / cs).min(1.0)
+                }
+            }
+            BlendMode::HardLight => hard_light(cb, cs),
+            BlendMode::SoftLight => {
+                if cs <= 0.5 {
+                    cb - (1.0 - 2.0 * cs) * cb * (1.0 - cb)
+                } else {
+                    let d = if cb <= 0.25 {
+                        ((16.0 * cb - 12.0) * cb + 4.0) * cb
+                    } else {
+                        cb.sqrt()
+                    };
+                    cb + (2.0 * cs - 1.0) * (d - cb)
+                }
+            }
+            BlendMode::Difference => (cb - cs).abs(),
+            BlendMode::Exclusion => cb + cs - 2.0 * cb * cs,
+        }
+    }
+}
+
+impl Color {
+    /// Blend this (source) color with the `backdrop` color using the given
+    /// blend mode, then composite the result over the backdrop (source-over).
+    /// Blending is done in the [`Space::SrgbLinear`] color space and the
+    /// result is returned in the color space of this color.
+    /// <https://drafts.fxtf.org/compositing-1/#generalformula>
+    pub fn blend(&self, backdrop: &Color, mode: BlendMode) -> Color {
+        let source = self.to_space(Space::SrgbLinear);
+        let backdrop = backdrop.to_space(Space::SrgbLinear);
+
+        let (alpha_s, alpha_b) = (source.alpha, backdrop.alpha);
+        let alpha = alpha_s + alpha_b * (1.0 - alpha_s);
+        if alpha == 0.0 {
+            return Color::new(Space::SrgbLinear, 0.0, 0.0, 0.0, 0.0).to_space(self.space);
+        }
+
+        let component = |cb: Component, cs: Component| {
+            // Cs = (1 - αb) x Cs + αb x B(Cb, Cs)
+            let cs = (1.0 - alpha_b) * cs + alpha_b * mode.blend(cb, cs);
+            // co = cs x αs + cb x αb x (1 - αs)
+            let co = cs * alpha_s + cb * alpha_b * (1.0 - alpha_s);
+            co / alpha
+        };
+
+        let Components(red, green, blue) = Components(
+            component(backdrop.components.0, source.components.0),
+            component(backdrop.components.1, source.components.1),
+            component(backdrop.components.2, source.components.2),
+        );
+
+        Color::new(Space::SrgbLinear, red, green, blue, alpha).to_space(self.space)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_component_eq;
+
+    fn linear(red: Component, green: Component, blue: Component, alpha: Component) -> Color {
+        Color::new(Space::SrgbLinear, red, green, blue, alpha)
+    }
+
+    #[test]
+    fn blend_opaque_colors() {
+        let backdrop = linear(0.2, 0.5, 0.8, 1.0);
+        let source = linear(0.5, 0.25, 1.0, 1.0);
+
+        let check = |mode: BlendMode, expected: [Component; 3]| {
+            let result = source.blend(&backdrop, mode);
+            assert_component_eq!(result.components.0, expected[0]);
+            assert_component_eq!(result.components.1, expected[1]);
+            assert_component_eq!(result.components.2, expected[2]);
+            assert_eq!(result.alpha, 1.0);
+        };
+
+        check(BlendMode::Normal, [0.5, 0.25, 1.0]);
+        check(BlendMode::Multiply, [0.1, 0.125, 0.8]);
+        check(BlendMode::Screen, [0.6, 0.625, 1.0]);
+        check(BlendMode::Overlay, [0.2, 0.25, 1.0]);
+        check(BlendMode::Darken, [0.2, 0.25, 0.8]);
+        check(BlendMode::Lighten, [0.5, 0.5, 1.0]);
+        check(BlendMode::ColorDodge, [0.4, 0.666667, 1.0]);
+        check(BlendMode::ColorBurn, [0.0, 0.0, 0.8]);
+        check(BlendMode::HardLight, [0.2, 0.25, 1.0]);
+        check(BlendMode::SoftLight, [0.2, 0.375, 0.894427]);
+        check(BlendMode::Difference, [0.3, 0.25, 0.2]);
+        check(BlendMode::Exclusion, [0.5, 0.5, 0.2]);
+    }
+
+    #[test]
+    fn blend_with_transparency() {
+        let backdrop = linear(0.2, 0.5, 0.8, 1.0);
+
+        // A fully transparent source leaves the backdrop unchanged.
+        let result = linear(1.0, 1.0, 1.0, 0.0).blend(&backdrop, BlendMode::Multiply);
+        assert_component_eq!(result.components.0, 0.2);
+        assert_component_eq!(result.components.1, 0.5);
+        assert_component_eq!(result.components.2, 0.8);
+        assert_eq!(result.alpha, 1.0);
+
+        // With a transparent backdrop the source is used as is.
+        let result =
+            linear(0.4, 0.6, 0.8, 0.5).blend(&linear(0.0, 0.0, 0.0, 0.0), BlendMode::Screen);
+        assert_component_eq!(result.components.0, 0.4);
+        assert_component_eq!(result.components.1, 0.6);
+        assert_component_eq!(result.components.2, 0.8);
+        assert_eq!(result.alpha, 0.5);
+    }
+
+    #[test]
+    fn blend_returns_source_space() {
+        let white = Color::new(Space::Srgb, 1.0, 1.0, 1.0, 1.0);
+        let red = Color::new(Space::Srgb, 1.0, 0.0, 0.0, 1.0);
+
+        let result = white.blend(&red, BlendMode::Multiply);
+        assert_eq!(result.space, Space::Srgb);
+        assert_component_eq!(result.components.0, 1.0);
+        assert_component_eq!(result.components.1, 0.0);
+        assert_component_eq!(result.components.2, 0.0);
+    }
+}
@@ -3,7 +3,11 @@
 
 use bitflags::bitflags;
 
-use crate::{models::Model, Component};
+use crate::{
+    math::{closest_hue, normalize_hue},
+    models::Model,
+    Component,
+};
 
 /// Represent the three components that describe any color.
 #[derive(Clone, Copy, Debug, PartialEq)]
@@ -15,6 +19,33 @@ impl Components {
     pub fn map(&self, f: impl Fn(Component) -> Component) -> Self {
         Self(f(self.0), f(self.1), f(self.2))
     }
+
+    /// Return the components as a fixed size array.
+    pub fn to_array(&self) -> [Component; 3] {
+        [self.0, self.1, self.2]
+    }
+
+    /// Create components from a fixed size array.
+    pub fn from_array(array: [Component; 3]) -> Self {
+        Self(array[0], array[1], array[2])
+    }
+
+    /// Return the dot product of these components and `other`, treating both
+    /// as 3D vectors.
+    #[inline]
+    pub fn dot(&self, other: &Components) -> Component {
+        self.0 * other.0 + self.1 * other.1 + self.2 * other.2
+    }
+
+    /// Return the euclidean length of the components, treating them as a 3D
+    /// vector.
+    ///
+    /// NOTE: For polar components (with a hue) this is not a meaningful
+    ///       distance, convert to a rectangular form first.
+    #[inline]
+    pub fn length(&self) -> Component {
+        self.dot(self).sqrt()
+    }
 }
 
 impl std::fmt::Display for Components {
@@ -23,14 +54,90 @@ impl std::fmt::Display for Components {
     }
 }
 
+impl std::ops::Add for Components {
+    type Output = Self;
+
+    #[inline]
+    fn add(self, rhs: Self) -> Self::Output {
+        Self(self.0 + rhs.0, self.1 + rhs.1, self.2 + rhs.2)
+    }
+}
+
 impl std::ops::Sub for Components {
     type Output = Self;
 
+    #[inline]
     fn sub(self, rhs: Self) -> Self::Output {
         Self(self.0 - rhs.0, self.1 - rhs.1, self.2 - rhs.2)
     }
 }
 
+impl std::ops::Mul<Component> for Components {
+    type Output = Self;
+
+    #[inline]
+    fn mul(self, rhs: Component) -> Self::Output {
+        Self(self.0 * rhs, self.1 * rhs, self.2 * rhs)
+    }
+}
+
+impl std::ops::Div<Component> for Components {
+    type Output = Self;
+
+    #[inline]
+    fn div(self, rhs: Component) -> Self::Output {
+        Self(self.0 / rhs, self.1 / rhs, self.2 / rhs)
+    }
+}
+
+impl std::ops::Index<usize> for Components {
+    type Output = Component;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        match index {
+            0 => &self.0,
+            1 => &self.1,
+            2 => &self.2,
+            _ => panic!("component index out of range: {}", index),
+        }
+    }
+}
+
+impl std::ops::IndexMut<usize> for Components {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        match index {
+            0 => &mut self.0,
+            1 => &mut self.1,
+            2 => &mut self.2,
+            _ => panic!("component index out of range: {}", index),
+        }
+    }
+}
+
+impl From<[Component; 3]> for Components {
+    fn from(value: [Component; 3]) -> Self {
+        Self::from_array(value)
+    }
+}
+
+impl From<Components> for [Component; 3] {
+    fn from(value: Components) -> Self {
+        value.to_array()
+    }
+}
+
+impl From<(Component, Component, Component)> for Components {
+    fn from(value: (Component, Component, Component)) -> Self {
+        Self(value.0, value.1, value.2)
+    }
+}
+
+impl From<Components> for (Component, Component, Component) {
+    fn from(value: Components) -> Self {
+        (value.0, value.1, value.2)
+    }
+}
+
 bitflags! {
     /// Flags to mark any missing components on a [`Color`]
     #[derive(Clone, Copy, Debug, PartialEq)]
@@ -48,7 +155,7 @@ bitflags! {
 
 /// Various color spaces and forms supported by the CSS specification.
 ///<https://drafts.csswg.org/css-color-4/#color-type>
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
 #[repr(u8)]
 pub enum Space {
     /// The sRGB color space.
@@ -85,6 +192,225 @@ pub enum Space {
     XyzD50 = 12,
     /// xyz-d65
     XyzD65 = 13,
+    /// The HCT (hue, chroma, tone) color space used by Material Design.
+    /// <https://material.io/blog/science-of-color-design>
+    Hct = 14,
+    /// The CIE-Lab color space with a D65 white point, using the rectangular
+    /// orthogonal form. This is not the CSS `lab()` color space, see
+    /// [`Space::Lab`].
+    LabD65 = 15,
+    /// The CIE-Lab color space with a D65 white point, using the cylindrical
+    /// polar form. This is not the CSS `lch()` color space, see
+    /// [`Space::Lch`].
+    LchD65 = 16,
+    /// The Jzazbz color space, designed to be perceptually uniform over a
+    /// wide range of luminance (HDR).
+    /// <https://doi.org/10.1364/OE.25.015131>
+    Jzazbz = 17,
+    /// The Jzazbz color space, using the cylindrical polar form (JzCzhz).
+    Jzczhz = 18,
+    /// The IPT color space, designed for uniform hue.
+    /// <https://doi.org/10.2352/CIC.1998.6.1.art00003>
+    Ipt = 19,
+    /// The ICtCp color space, designed for HDR and wide color gamut signals.
+    /// <https://www.itu.int/rec/R-REC-BT.2100>
+    ICtCp = 20,
+    /// The HSV (hue, saturation, value) notation, also known as HSB, is a
+    /// common way of specifying colors in the sRGB color space in color
+    /// pickers.
+    Hsv = 21,
+}
+
+/// Describes how the value of a component should be interpreted.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ComponentKind {
+    /// A plain number.
+    Number,
+    /// A percentage, stored as a fraction where 1.0 is 100%.
+    Percentage,
+    /// An angle in degrees.
+    Angle,
+}
+
+impl Space {
+    /// Return the stable numeric id of this color space. The id of a color
+    /// space never changes, so it is safe to use in serialized data.
+    pub fn as_u8(&self) -> u8 {
+        *self as u8
+    }
+
+    /// Return the color space with the given numeric id, or None if the id
+    /// does not belong to any color space. See [`Space::as_u8`].
+    pub fn from_u8(id: u8) -> Option<Space> {
+        Some(match id {
+            0 => Space::Srgb,
+            1 => Space::Hsl,
+            2 => Space::Hwb,
+            3 => Space::Lab,
+            4 => Space::Lch,
+            5 => Space::Oklab,
+            6 => Space::Oklch,
+            7 => Space::SrgbLinear,
+            8 => Space::DisplayP3,
+            9 => Space::A98Rgb,
+            10 => Space::ProPhotoRgb,
+            11 => Space::Rec2020,
+            12 => Space::XyzD50,
+            13 => Space::XyzD65,
+            14 => Space::Hct,
+            15 => Space::LabD65,
+            16 => Space::LchD65,
+            17 => Space::Jzazbz,
+            18 => Space::Jzczhz,
+            19 => Space::Ipt,
+            20 => Space::ICtCp,
+            21 => Space::Hsv,
+            _ => return None,
+        })
+    }
+
+    /// Return the identifier of this color space, as used by the CSS
+    /// `color()` function and color interpolation, e.g. `"srgb-linear"` or
+    /// `"oklch"`.
+    ///
+    /// NOTE: Color spaces that are not part of CSS use a similar lowercase
+    ///       identifier, e.g. `"hct"` or `"lab-d65"`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Space::Srgb => "srgb",
+            Space::Hsl => "hsl",
+            Space::Hwb => "hwb",
+            Space::Lab => "lab",
+            Space::Lch => "lch",
+            Space::Oklab => "oklab",
+            Space::Oklch => "oklch",
+            Space::SrgbLinear => "srgb-linear",
+            Space::DisplayP3 => "display-p3",
+            Space::A98Rgb => "a98-rgb",
+            Space::ProPhotoRgb => "prophoto-rgb",
+            Space::Rec2020 => "rec2020",
+            Space::XyzD50 => "xyz-d50",
+            Space::XyzD65 => "xyz-d65",
+            Space::Hct => "hct",
+            Space::LabD65 => "lab-d65",
+            Space::LchD65 => "lch-d65",
+            Space::Jzazbz => "jzazbz",
+            Space::Jzczhz => "jzczhz",
+            Space::Ipt => "ipt",
+            Space::ICtCp => "ictcp",
+            Space::Hsv => "hsv",
+        }
+    }
+
+    /// Return the color space with the given identifier, or None if the
+    /// identifier does not belong to any color space. Identifiers are ASCII
+    /// case-insensitive and `"xyz"` is an alias for `"xyz-d65"`, like in CSS.
+    /// See [`Space::name`].
+    pub fn from_name(name: &str) -> Option<Space> {
+        if name.eq_ignore_ascii_case("xyz") {
+            return Some(Space::XyzD65);
+        }
+
+        (0..=u8::MAX)
+            .filter_map(Space::from_u8)
+            .find(|space| space.name().eq_ignore_ascii_case(name))
+    }
+
+    /// Return a typical range for each of the components in this color
+    /// space, meant as a hint for displaying components, e.g. as the limits
+    /// of a slider in a color picker. These are not bounds: valid colors can
+    /// fall outside them, e.g. wide gamut colors in Lab/Oklab, and hues wrap
+    /// around.
+    ///
+    /// The ranges of RGB components are the gamut, those of Lab/Lch and
+    /// Oklab/Oklch are the reference ranges that CSS uses for percentages,
+    /// and the others roughly cover the colors inside the Rec2020 gamut.
+    /// CIE-XYZ components have no typical range (None), because it depends on
+    /// how the white point is normalized.
+    ///
+    /// NOTE: The ranges that CSS clamps components to are used by
+    ///       [`Color::clamp_to_space_range`] and [`Color::validate`].
+    /// <https://drafts.csswg.org/css-color-4/#color-syntax>
+    pub fn component_ranges(&self) -> [Option<(Component, Component)>; 3] {
+        const UNIT: Option<(Component, Component)> = Some((0.0, 1.0));
+        const HUE: Option<(Component, Component)> = Some((0.0, 360.0));
+
+        match self {
+            Space::Srgb
+            | Space::SrgbLinear
+            | Space::DisplayP3
+            | Space::A98Rgb
+            | Space::ProPhotoRgb
+            | Space::Rec2020 => [UNIT, UNIT, UNIT],
+            Space::XyzD50 | Space::XyzD65 => [None, None, None],
+            Space::Hsl | Space::Hwb | Space::Hsv => [HUE, UNIT, UNIT],
+            Space::Lab | Space::LabD65 => [
+                Some((0.0, 100.0)),
+                Some((-125.0, 125.0)),
+                Some((-125.0, 125.0)),
+            ],
+            Space::Lch | Space::LchD65 => [Some((0.0, 100.0)), Some((0.0, 150.0)), HUE],
+            Space::Oklab => [UNIT, Some((-0.4, 0.4)), Some((-0.4, 0.4))],
+            Space::Oklch => [UNIT, Some((0.0, 0.4)), HUE],
+            Space::Hct => [HUE, Some((0.0, 150.0)), Some((0.0, 100.0))],
+            Space::Jzazbz => [UNIT, Some((-0.21, 0.21)), Some((-0.21, 0.21))],
+            Space::Jzczhz => [UNIT, Some((0.0, 0.26)), HUE],
+            Space::Ipt => [UNIT, Some((-1.0, 1.0)), Some((-1.0, 1.0))],
+            Space::ICtCp => [UNIT, Some((-0.5, 0.5)), Some((-0.5, 0.5))],
+        }
+    }
+
+    /// Return the short names of the components in this color space, e.g.
+    /// `["L", "a", "b"]` for [`Space::Lab`].
+    pub fn component_names(&self) -> [&'static str; 3] {
+        match self {
+            Space::Srgb
+            | Space::SrgbLinear
+            | Space::DisplayP3
+            | Space::A98Rgb
+            | Space::ProPhotoRgb
+            | Space::Rec2020 => ["R", "G", "B"],
+            Space::Hsl => ["H", "S", "L"],
+            Space::Hwb => ["H", "W", "B"],
+            Space::Hsv => ["H", "S", "V"],
+            Space::Lab | Space::LabD65 | Space::Oklab => ["L", "a", "b"],
+            Space::Lch | Space::LchD65 | Space::Oklch => ["L", "C", "H"],
+            Space::XyzD50 | Space::XyzD65 => ["X", "Y", "Z"],
+            Space::Hct => ["H", "C", "T"],
+            Space::Jzazbz => ["Jz", "az", "bz"],
+            Space::Jzczhz => ["Jz", "Cz", "hz"],
+            Space::Ipt => ["I", "P", "T"],
+            Space::ICtCp => ["I", "Ct", "Cp"],
+        }
+    }
+
+    /// Return how each of the components in this color space should be
+    /// interpreted.
+    pub fn component_kinds(&self) -> [ComponentKind; 3] {
+        use ComponentKind as K;
+
+        match self {
+            Space::Hsl | Space::Hwb | Space::Hsv => [K::Angle, K::Percentage, K::Percentage],
+            Space::Lch | Space::LchD65 | Space::Oklch | Space::Jzczhz => {
+                [K::Number, K::Number, K::Angle]
+            }
+            Space::Hct => [K::Angle, K::Number, K::Number],
+            Space::Srgb
+            | Space::SrgbLinear
+            | Space::DisplayP3
+            | Space::A98Rgb
+            | Space::ProPhotoRgb
+            | Space::Rec2020
+            | Space::Lab
+            | Space::LabD65
+            | Space::Oklab
+            | Space::Jzazbz
+            | Space::Ipt
+            | Space::ICtCp
+            | Space::XyzD50
+            | Space::XyzD65 => [K::Number, K::Number, K::Number],
+        }
+    }
 }
 
 pub trait CssColorSpaceId {
@@ -139,6 +465,44 @@ impl Color {
         }
     }
 
+    /// Returns true if this color and `other` are in the same color space,
+    /// have the same missing components and all the components that are
+    /// present (including alpha) are within `epsilon` of each other. Hue
+    /// components are compared along the shorter arc between them, e.g. 359
+    /// degrees and 1 degree are 2 degrees apart.
+    pub fn approx_eq(&self, other: &Color, epsilon: Component) -> bool {
+        self.approx_eq_with(other, |left, right| (left - right).abs() <= epsilon)
+    }
+
+    /// Same as [`Color::approx_eq`], but the components that are present are
+    /// compared with `eq`. The hue of `other` is moved by full turns to be as
+    /// close as possible to the hue of this color before it is compared.
+    pub(crate) fn approx_eq_with(
+        &self,
+        other: &Color,
+        eq: impl Fn(Component, Component) -> bool,
+    ) -> bool {
+        let hue_index = self.space.hue_index();
+
+        self.space == other.space
+            && self.flags == other.flags
+            && [self.c0(), self.c1(), self.c2()]
+                .into_iter()
+                .zip([other.c0(), other.c1(), other.c2()])
+                .enumerate()
+                .all(|(i, (left, right))| match (left, right) {
+                    (Some(left), Some(right)) if hue_index == Some(i) => {
+                        eq(left, closest_hue(right, left))
+                    }
+                    (Some(left), Some(right)) => eq(left, right),
+                    _ => true,
+                })
+            && match (self.alpha(), other.alpha()) {
+                (Some(left), Some(right)) => eq(left, right),
+                _ => true,
+            }
+    }
+
     /// Return the first component of the color.
     pub fn c0(&self) -> Option<Component> {
         if self.flags.contains(Flags::C0_IS_NONE) {
@@ -175,8 +539,41 @@ impl Color {
         }
     }
 
+    /// Create a new [`Color`] from a model, marking exactly the components
+    /// (and alpha) in `flags` as missing, regardless of their values in the
+    /// model. Components that are not marked as missing, but are NaN in the
+    /// model, are set to 0.
+    pub fn from_model_with_flags<T: Model>(
+        model: &T,
+        alpha: Option<Component>,
+        flags: Flags,
+    ) -> Color {
+        let color = model.to_color(alpha);
+
+        let c = |flag: Flags, value: Component| {
+            if flags.contains(flag) {
+                None
+            } else {
+                Some(value)
+            }
+        };
+
+        Color::new(
+            color.space,
+            c(Flags::C0_IS_NONE, color.components.0),
+            c(Flags::C1_IS_NONE, color.components.1),
+            c(Flags::C2_IS_NONE, color.components.2),
+            c(Flags::ALPHA_IS_NONE, color.alpha),
+        )
+    }
+
     /// Return a reference to this color types as the given model.
     pub fn as_model<T: Model + From<Components>>(&self) -> T {
+        self.model_components().into()
+    }
+
+    /// Return the components of this color as they are passed to models.
+    pub(crate) fn model_components(&self) -> Components {
         macro_rules! c {
             ($c:expr) => {{
                 match $c {
@@ -189,7 +586,290 @@ impl Color {
             }};
         }
 
-        Components(c!(self.c0()), c!(self.c1()), c!(self.c2())).into()
+        Components(c!(self.c0()), c!(self.c1()), c!(self.c2()))
+    }
+
+    /// Return a new color with each component (including alpha) that lies
+    /// within `epsilon` of an integer snapped to that integer. Conversions
+    /// often yield values like `0.9999998` or `-1e-9` where `1.0` and `0.0`
+    /// were intended.
+    pub fn snap(&self, epsilon: Component) -> Self {
+        let snap = |value: Component| {
+            let rounded = value.round();
+            if (value - rounded).abs() <= epsilon {
+                // Avoid producing a negative zero.
+                rounded + 0.0
+            } else {
+                value
+            }
+        };
+
+        Self {
+            components: self.components.map(snap),
+            alpha: snap(self.alpha),
+            ..self.clone()
+        }
+    }
+
+    /// Returns true if none of the components (including alpha) are infinite
+    /// or NaN. Missing components are not considered.
+    pub fn is_finite(&self) -> bool {
+        [self.c0(), self.c1(), self.c2(), self.alpha()]
+            .iter()
+            .flatten()
+            .all(|v| v.is_finite())
+    }
+
+    /// Return a new color where infinite components are replaced with the
+    /// largest finite values of the same sign and NaN components are marked
+    /// as missing. Infinite alpha values are clamped to [0..1].
+    pub fn sanitize(&self) -> Self {
+        let mut flags = self.flags;
+
+        let mut sanitize = |value: Component, flag: Flags| {
+            if value.is_nan() {
+                flags.insert(flag);
+                0.0
+            } else {
+                value.clamp(Component::MIN, Component::MAX)
+            }
+        };
+
+        let components = Components(
+            sanitize(self.components.0, Flags::C0_IS_NONE),
+            sanitize(self.components.1, Flags::C1_IS_NONE),
+            sanitize(self.components.2, Flags::C2_IS_NONE),
+        );
+        let alpha = sanitize(self.alpha, Flags::ALPHA_IS_NONE).clamp(0.0, 1.0);
+
+        Self {
+            components,
+            alpha,
+            flags,
+            space: self.space,
+        }
+    }
+
+    /// Returns true if the stored values of this color agree with its flags,
+    /// i.e. no present component is NaN. The stored value of a missing
+    /// component is ignored.
+    pub fn is_consistent(&self) -> bool {
+        self.values_and_flags()
+            .iter()
+            .all(|&(value, flag)| self.flags.contains(flag) || !value.is_nan())
+    }
+
+    /// Return a new color where the stored values agree with the flags (see
+    /// [`Color::is_consistent`]). NaN components are marked as missing and
+    /// stored as 0.
+    pub fn repair(&self) -> Self {
+        let mut result = self.clone();
+
+        for (index, (value, flag)) in self.values_and_flags().into_iter().enumerate() {
+            if value.is_nan() {
+                result.flags.insert(flag);
+                match index {
+                    3 => result.alpha = 0.0,
+                    _ => result.components[index] = 0.0,
+                }
+            }
+        }
+
+        result
+    }
+
+    /// The stored values of all the components (including alpha) paired with
+    /// the flag that marks them as missing.
+    fn values_and_flags(&self) -> [(Component, Flags); 4] {
+        [
+            (self.components.0, Flags::C0_IS_NONE),
+            (self.components.1, Flags::C1_IS_NONE),
+            (self.components.2, Flags::C2_IS_NONE),
+            (self.alpha, Flags::ALPHA_IS_NONE),
+        ]
+    }
+
+    /// Return a new color with each component restricted to the range that
+    /// CSS clamps it to at parse time, e.g. the lightness of [`Space::Lab`]
+    /// to [0..100] and chroma to 0 or more. Hue components are wrapped around
+    /// to [0..360]. Components that CSS does not clamp, e.g. RGB, XYZ and
+    /// Lab a/b, are not changed. Missing components remain missing.
+    /// <https://drafts.csswg.org/css-color-4/#specifying-lab-lch>
+    pub fn clamp_to_space_range(&self) -> Self {
+        let ranges = self.space.clamped_ranges();
+        let kinds = self.space.component_kinds();
+
+        let clamp = |index: usize, value: Component| match ranges[index] {
+            _ if kinds[index] == ComponentKind::Angle => normalize_hue(value),
+            Some((min, max)) => value.clamp(min, max),
+            None => value,
+        };
+
+        Self {
+            components: Components(
+                clamp(0, self.components.0),
+                clamp(1, self.components.1),
+                clamp(2, self.components.2),
+            ),
+            ..self.clone()
+        }
+    }
+
+    /// Return a new color with the alpha component multiplied by `factor`,
+    /// clamped to [0..1]. A missing alpha component remains missing.
+    pub fn fade(&self, factor: Component) -> Self {
+        if self.flags.contains(Flags::ALPHA_IS_NONE) {
+            return self.clone();
+        }
+
+        Self {
+            alpha: (self.alpha * factor).clamp(0.0, 1.0),
+            ..self.clone()
+        }
+    }
+
+    /// Return a new color that is fully opaque, with the alpha component set
+    /// to 1 and no longer missing.
+    pub fn opaque(&self) -> Self {
+        let mut flags = self.flags;
+        flags.remove(Flags::ALPHA_IS_NONE);
+
+        Self {
+            alpha: 1.0,
+            flags,
+            ..self.clone()
+        }
+    }
+
+    /// Return a new color with `f` applied to each of the channels, including
+    /// alpha. `f` receives the index of the channel (0, 1 and 2 for the color
+    /// components and 3 for alpha) and its value. Missing channels are
+    /// skipped and remain missing. The resulting alpha is clamped to [0..1].
+    pub fn map_channels(&self, f: impl Fn(usize, Component) -> Component) -> Self {
+        let mut result = self.clone();
+
+        for (index, (value, flag)) in self.values_and_flags().into_iter().enumerate() {
+            if self.flags.contains(flag) {
+                continue;
+            }
+            match index {
+                3 => result.alpha = f(index, value).clamp(0.0, 1.0),
+                _ => result.components[index] = f(index, value),
+            }
+        }
+
+        result
+    }
+
+    /// Return the inverse of this color by inverting the red, green and blue
+    /// components (`1 - c`) in the sRGB color space. The alpha component is
+    /// preserved and missing components remain missing.
+    ///
+    /// NOTE: Colors in any other color space (including the other RGB color
+    ///       spaces) are converted to [`Space::Srgb`], inverted and converted
+    ///       back to their original color space.
+    pub fn invert(&self) -> Self {
+        let srgb = self.to_space_filled(Space::Srgb);
+        let Components(red, green, blue) = srgb.components.map(|c| 1.0 - c);
+
+        let inverted = Color::new(Space::Srgb, red, green, blue, self.alpha());
+        self.keep_missing(inverted.to_space(self.space))
+    }
+
+    /// Return a lighter color by adding `amount` to the Oklch lightness,
+    /// clamped to [0..1]. The result is converted back to the color space of
+    /// this color and is not mapped into its gamut.
+    ///
+    /// NOTE: Missing components of this color, in any color space, remain
+    ///       missing.
+    pub fn lighten(&self, amount: Component) -> Self {
+        self.adjust_in_oklch(0, |lightness| (lightness + amount).clamp(0.0, 1.0))
+    }
+
+    /// Return a darker color by subtracting `amount` from the Oklch lightness.
+    /// See [`Color::lighten`].
+    pub fn darken(&self, amount: Component) -> Self {
+        self.lighten(-amount)
+    }
+
+    /// Return a more saturated color by scaling the Oklch chroma by
+    /// `1 + amount`, e.g. an `amount` of 0.5 increases the chroma by 50%. The
+    /// chroma never goes below 0. The result is converted back to the color
+    /// space of this color and is not mapped into its gamut.
+    ///
+    /// NOTE: Missing components of this color, in any color space, remain
+    ///       missing.
+    pub fn saturate(&self, amount: Component) -> Self {
+        self.adjust_in_oklch(1, |chroma| (chroma * (1.0 + amount)).max(0.0))
+    }
+
+    /// Return a less saturated color by scaling the Oklch chroma by
+    /// `1 - amount`, e.g. an `amount` of 1 results in a gray. See
+    /// [`Color::saturate`].
+    pub fn desaturate(&self, amount: Component) -> Self {
+        self.saturate(-amount)
+    }
+
+    /// Convert this color to Oklch, apply `f` to the component at `index` if
+    /// it is not missing and convert the result back to the color space of
+    /// this color.
+    fn adjust_in_oklch(&self, index: usize, f: impl Fn(Component) -> Component) -> Self {
+        let mut oklch = self.to_space_filled(Space::Oklch);
+
+        let (value, flag) = oklch.values_and_flags()[index];
+        if !oklch.flags.contains(flag) {
+            oklch.components[index] = f(value);
+        }
+
+        self.keep_missing(oklch.to_space(self.space))
+    }
+
+    /// Convert this color to `space` with its missing components treated as
+    /// 0, as CSS does when converting colors. A missing component would
+    /// otherwise make every component of the converted color missing.
+    /// <https://drafts.csswg.org/css-color-4/#missing>
+    fn to_space_filled(&self, space: Space) -> Self {
+        let filled = Color::new(
+            self.space,
+            self.c0().unwrap_or(0.0),
+            self.c1().unwrap_or(0.0),
+            self.c2().unwrap_or(0.0),
+            self.alpha(),
+        );
+        filled.to_space(space)
+    }
+
+    /// Return `result`, which is in the color space of this color, with the
+    /// components that are missing in this color marked as missing again and
+    /// their stored values left untouched. Converting to another color space
+    /// and back fills in missing components.
+    fn keep_missing(&self, mut result: Self) -> Self {
+        debug_assert_eq!(result.space, self.space);
+
+        for (index, (value, flag)) in self.values_and_flags().into_iter().enumerate() {
+            if self.flags.contains(flag) {
+                result.flags.insert(flag);
+                match index {
+                    3 => result.alpha = value,
+                    _ => result.components[index] = value,
+                }
+            }
+        }
+
+        result
+    }
+
+    /// Return this color with its color space replaced by `space`, WITHOUT
+    /// converting the components. The components, alpha and missing flags are
+    /// kept as is and are from now on interpreted in the new color space. This
+    /// is useful when ingesting raw components of which the color space is
+    /// tracked elsewhere.
+    ///
+    /// NOTE: This changes the color the components represent, e.g. the sRGB
+    ///       components (1, 0, 0) tagged as [`Space::Hsl`] is black. Use
+    ///       [`Color::to_space`] to get the same color in another color space.
+    pub fn with_space(self, space: Space) -> Self {
+        Self { space, ..self }
     }
 }
 
@@ -233,9 +913,25 @@ impl From<Option<Component>> for ComponentDetails {
     }
 }
 
+/// Colors are equal when they are in the same color space, have the same
+/// missing components and the components that are present (including alpha)
+/// are equal. The values stored for missing components are ignored. See
+/// [`Color::approx_eq`] to compare with a tolerance.
+impl PartialEq for Color {
+    fn eq(&self, other: &Self) -> bool {
+        self.space == other.space
+            && self.flags == other.flags
+            && self.c0() == other.c0()
+            && self.c1() == other.c1()
+            && self.c2() == other.c2()
+            && self.alpha() == other.alpha()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::assert_component_eq;
 
     #[test]
     fn create_color_with_correct_components() {
@@ -258,6 +954,71 @@ mod tests {
         assert_eq!(c.space, Space::Srgb);
     }
 
+    #[test]
+    fn space_ids_round_trip() {
+        let mut count = 0;
+        for id in 0..=u8::MAX {
+            if let Some(space) = Space::from_u8(id) {
+                assert_eq!(space.as_u8(), id);
+                count += 1;
+            }
+        }
+        assert_eq!(count, 22);
+
+        assert_eq!(Space::Srgb.as_u8(), 0);
+        assert_eq!(Space::XyzD65.as_u8(), 13);
+        assert_eq!(Space::Hct.as_u8(), 14);
+        assert_eq!(Space::LchD65.as_u8(), 16);
+        assert_eq!(Space::Jzczhz.as_u8(), 18);
+        assert_eq!(Space::ICtCp.as_u8(), 20);
+        assert_eq!(Space::Hsv.as_u8(), 21);
+        assert_eq!(Space::from_u8(22), None);
+    }
+
+    #[test]
+    fn space_names_round_trip() {
+        for space in (0..=u8::MAX).filter_map(Space::from_u8) {
+            assert_eq!(Space::from_name(space.name()), Some(space));
+        }
+
+        assert_eq!(Space::SrgbLinear.name(), "srgb-linear");
+        assert_eq!(Space::from_name("Display-P3"), Some(Space::DisplayP3));
+        assert_eq!(Space::from_name("xyz"), Some(Space::XyzD65));
+        assert_eq!(Space::from_name("rgb"), None);
+    }
+
+    #[test]
+    fn equality_ignores_missing_values() {
+        let mut left = Color::new(Space::Oklch, 0.5, 0.0, None, None);
+        let right = Color::new(Space::Oklch, 0.5, 0.0, None, None);
+        left.components.2 = 120.0;
+        left.alpha = 0.5;
+        assert_eq!(left, right);
+
+        // Missing components only equal other missing components.
+        assert_ne!(left, Color::new(Space::Oklch, 0.5, 0.0, 0.0, None));
+        // Colors in different color spaces are never equal.
+        assert_ne!(
+            Color::new(Space::Srgb, 1.0, 0.0, 0.0, 1.0),
+            Color::new(Space::SrgbLinear, 1.0, 0.0, 0.0, 1.0)
+        );
+    }
+
+    #[test]
+    fn approximate_equality() {
+        let color = Color::new(Space::Hsl, 359.0, 0.5, 0.5, 1.0);
+        assert!(color.approx_eq(&Color::new(Space::Hsl, 1.0, 0.5, 0.5, 1.0), 2.5));
+        assert!(!color.approx_eq(&Color::new(Space::Hsl, 1.0, 0.5, 0.5, 1.0), 1.5));
+        assert!(color.approx_eq(&Color::new(Space::Hsl, 359.0, 0.501, 0.5, 0.999), 1.0e-2));
+        assert!(!color.approx_eq(&Color::new(Space::Hsl, 359.0, 0.6, 0.5, 1.0), 1.0e-2));
+        assert!(!color.approx_eq(&Color::new(Space::Hsl, 359.0, 0.5, 0.5, 0.9), 1.0e-2));
+
+        let missing = Color::new(Space::Hsl, None, 0.5, 0.5, 1.0);
+        assert!(missing.approx_eq(&Color::new(Space::Hsl, None, 0.5, 0.5, 1.0), 1.0e-3));
+        assert!(!missing.approx_eq(&Color::new(Space::Hsl, 0.0, 0.5, 0.5, 1.0), 1.0e-3));
+        assert!(!color.approx_eq(&color.to_space(Space::Hwb), 1.0));
+    }
+
     #[test]
     fn test_component_details() {
         let cd = ComponentDetails::from(10.0);
@@ -281,6 +1042,192 @@ mod tests {
         assert!(!cd.is_none);
     }
 
+    #[test]
+    fn array_conversions() {
+        let components = Components::from([0.1, 0.2, 0.3]);
+        assert_eq!(components, Components(0.1, 0.2, 0.3));
+        assert_eq!(<[Component; 3]>::from(components), [0.1, 0.2, 0.3]);
+
+        assert_eq!(components.to_array(), [0.1, 0.2, 0.3]);
+        assert_eq!(Components::from_array([0.1, 0.2, 0.3]), components);
+
+        let components = Components::from((0.4, 0.5, 0.6));
+        assert_eq!(components, Components(0.4, 0.5, 0.6));
+        assert_eq!(
+            <(Component, Component, Component)>::from(components),
+            (0.4, 0.5, 0.6)
+        );
+
+        let lab = crate::models::Lab::from([50.0, 10.0, -10.0]);
+        assert_eq!(lab.to_components(), Components(50.0, 10.0, -10.0));
+        let array: [Component; 3] = lab.into();
+        assert_eq!(array, [50.0, 10.0, -10.0]);
+    }
+
+    #[test]
+    fn components_arithmetic() {
+        let a = Components(1.0, 2.0, 3.0);
+        let b = Components(0.5, -1.0, 2.0);
+
+        assert_eq!(a + b, Components(1.5, 1.0, 5.0));
+        assert_eq!(a - b, Components(0.5, 3.0, 1.0));
+        assert_eq!(a * 2.0, Components(2.0, 4.0, 6.0));
+        assert_eq!(a / 2.0, Components(0.5, 1.0, 1.5));
+        assert_eq!(a.dot(&b), 4.5);
+        assert_eq!(Components(3.0, 0.0, 4.0).length(), 5.0);
+        assert_eq!(Components(0.0, 0.0, 0.0).length(), 0.0);
+    }
+
+    #[test]
+    fn index_components() {
+        let mut components = Components(0.1, 0.2, 0.3);
+        assert_eq!(components[0], 0.1);
+        assert_eq!(components[1], 0.2);
+        assert_eq!(components[2], 0.3);
+
+        components[1] = 0.5;
+        assert_eq!(components, Components(0.1, 0.5, 0.3));
+    }
+
+    #[test]
+    #[should_panic]
+    fn index_components_out_of_range() {
+        let _ = Components(0.1, 0.2, 0.3)[3];
+    }
+
+    #[test]
+    fn from_model_with_explicit_flags() {
+        use crate::models::Lch;
+
+        // The hue is dropped even though it has a value.
+        let c = Color::from_model_with_flags(
+            &Lch::new(50.0, 20.0, 120.0),
+            Some(0.5),
+            Flags::C2_IS_NONE,
+        );
+        assert_eq!(c.space, Space::Lch);
+        assert_eq!(c.c0(), Some(50.0));
+        assert_eq!(c.c1(), Some(20.0));
+        assert_eq!(c.c2(), None);
+        assert_eq!(c.alpha(), Some(0.5));
+        assert_eq!(c.flags, Flags::C2_IS_NONE);
+
+        // NaN components are not marked as missing if their flag is not set.
+        let c = Color::from_model_with_flags(
+            &Lch::new(50.0, 0.0, Component::NAN),
+            Some(1.0),
+            Flags::ALPHA_IS_NONE,
+        );
+        assert_eq!(c.c2(), Some(0.0));
+        assert_eq!(c.alpha(), None);
+        assert_eq!(c.flags, Flags::ALPHA_IS_NONE);
+    }
+
+    #[test]
+    fn fade_and_opaque() {
+        let c = Color::new(Space::Srgb, 0.2, 0.4, 0.6, 0.8);
+        assert_component_eq!(c.fade(0.5).alpha, 0.4);
+        assert_eq!(c.fade(2.0).alpha, 1.0);
+        assert_eq!(c.fade(-1.0).alpha, 0.0);
+        assert_eq!(c.fade(0.5).flags, Flags::empty());
+
+        let c = Color::new(Space::Srgb, 0.2, None, 0.6, None);
+        let faded = c.fade(0.5);
+        assert_eq!(faded.alpha(), None);
+        assert_eq!(faded.flags, Flags::C1_IS_NONE | Flags::ALPHA_IS_NONE);
+
+        let opaque = c.opaque();
+        assert_eq!(opaque.alpha(), Some(1.0));
+        assert_eq!(opaque.flags, Flags::C1_IS_NONE);
+
+        let opaque = Color::new(Space::Srgb, 0.2, 0.4, 0.6, 0.3).opaque();
+        assert_eq!(opaque.alpha(), Some(1.0));
+        assert_eq!(opaque.flags, Flags::empty());
+    }
+
+    #[test]
+    fn invert_colors() {
+        let black = Color::new(Space::Srgb, 1.0, 1.0, 1.0, 0.5).invert();
+        assert_eq!(black.space, Space::Srgb);
+        assert_eq!(black.components, Components(0.0, 0.0, 0.0));
+        assert_eq!(black.alpha(), Some(0.5));
+
+        let c = Color::new(Space::Srgb, 0.2, 0.4, 0.8, 1.0).invert();
+        assert_component_eq!(c.components.0, 0.8);
+        assert_component_eq!(c.components.1, 0.6);
+        assert_component_eq!(c.components.2, 0.2);
+
+        // White in Oklab inverts to black in Oklab.
+        let black = Color::new(Space::Oklab, 1.0, 0.0, 0.0, 1.0).invert();
+        assert_eq!(black.space, Space::Oklab);
+        assert_component_eq!(black.components.0, 0.0);
+
+        // Missing components remain missing and are not changed.
+        let c = Color::new(Space::Srgb, 0.2, None, 0.8, None).invert();
+        assert_eq!(c.flags, Flags::C1_IS_NONE | Flags::ALPHA_IS_NONE);
+        assert_component_eq!(c.components.0, 0.8);
+        assert_eq!(c.components.1, 0.0);
+        assert_component_eq!(c.components.2, 0.2);
+    }
+
+    #[test]
+    fn lighten_and_darken() {
+        let red = Color::new(Space::Srgb, 1.0, 0.0, 0.0, 0.5);
+        let red_lightness = red.to_space(Space::Oklch).components.0;
+
+        let lighter = red.lighten(0.1);
+        assert_eq!(lighter.space, Space::Srgb);
+        assert_eq!(lighter.alpha(), Some(0.5));
+        assert_component_eq!(
+            lighter.to_space(Space::Oklch).components.0,
+            red_lightness + 0.1
+        );
+        assert_component_eq!(
+            red.darken(0.1).to_space(Space::Oklch).components.0,
+            red_lightness - 0.1
+        );
+
+        // The lightness is clamped.
+        let white = Color::new(Space::Hsl, 0.0, 0.0, 0.5, 1.0).lighten(2.0);
+        assert_eq!(white.space, Space::Hsl);
+        assert_component_eq!(white.components.2, 1.0);
+        let black = Color::new(Space::Oklch, 0.5, 0.1, 120.0, 1.0).darken(2.0);
+        assert_component_eq!(black.components.0, 0.0);
+
+        // A missing lightness remains missing.
+        let missing = Color::new(Space::Oklch, None, 0.1, 120.0, 1.0).lighten(0.2);
+        assert_eq!(missing.c0(), None);
+
+        // Missing components of an sRGB color remain missing.
+        let missing = Color::new(Space::Srgb, 1.0, None, 0.0, None).lighten(0.1);
+        assert_eq!(missing.flags, Flags::C1_IS_NONE | Flags::ALPHA_IS_NONE);
+        assert_eq!(missing.c1(), None);
+        assert!(missing.c0().is_some() && missing.c2().is_some());
+    }
+
+    #[test]
+    fn saturate_and_desaturate() {
+        let color = Color::new(Space::Oklch, 0.6, 0.1, 120.0, 1.0);
+        assert_component_eq!(color.saturate(0.5).components.1, 0.15);
+        assert_component_eq!(color.desaturate(0.5).components.1, 0.05);
+        assert_component_eq!(color.desaturate(2.0).components.1, 0.0);
+        assert_component_eq!(color.saturate(0.5).components.2, 120.0);
+
+        // A fully desaturated sRGB color is gray.
+        let gray = Color::new(Space::Srgb, 0.8, 0.4, 0.2, 1.0).desaturate(1.0);
+        assert_eq!(gray.space, Space::Srgb);
+        assert_component_eq!(gray.components.0, gray.components.1);
+        assert_component_eq!(gray.components.1, gray.components.2);
+
+        // A missing chroma remains missing.
+        let missing = Color::new(Space::Oklch, 0.6, None, 120.0, 1.0).saturate(0.5);
+        assert_eq!(missing.c1(), None);
+
+        let missing = Color::new(Space::Srgb, 0.8, 0.4, None, 1.0).desaturate(0.5);
+        assert_eq!(missing.space, Space::Srgb);
+        assert_eq!(missing.flags, Flags::C2_IS_NONE);
+    }
+
     #[test]
     fn models_use_zero_not_nan() {
         let c = Color::new(
@@ -295,4 +1242,130 @@ mod tests {
         assert_eq!(model.chroma, 0.0);
         assert_eq!(model.hue, 0.0);
     }
+
+    #[test]
+    fn snap_components_close_to_integers() {
+        let c = Color::new(Space::Srgb, 0.9999998, -1.0e-9, 0.5, 0.9999999);
+        let snapped = c.snap(1.0e-6);
+        assert_eq!(snapped.components, Components(1.0, 0.0, 0.5));
+        assert!(snapped.components.1.is_sign_positive());
+        assert_eq!(snapped.alpha, 1.0);
+
+        let c = Color::new(Space::Lab, 49.99, 10.0, None, 1.0);
+        let snapped = c.snap(1.0e-3);
+        assert_eq!(snapped.components.0, 49.99);
+        assert_eq!(snapped.flags, Flags::C2_IS_NONE);
+    }
+
+    #[test]
+    fn sanitize_non_finite_components() {
+        let c = Color::new(Space::Srgb, 0.5, 0.5, 0.5, 1.0);
+        assert!(c.is_finite());
+        assert_eq!(c.sanitize().components, c.components);
+
+        let c = Color::new(
+            Space::XyzD65,
+            Component::INFINITY,
+            Component::NEG_INFINITY,
+            Component::NAN,
+            1.0,
+        );
+        assert!(!c.is_finite());
+
+        let sanitized = c.sanitize();
+        assert!(sanitized.is_finite());
+        assert_eq!(sanitized.components.0, Component::MAX);
+        assert_eq!(sanitized.components.1, Component::MIN);
+        assert_eq!(sanitized.c2(), None);
+        assert_eq!(sanitized.flags, Flags::C2_IS_NONE);
+    }
+
+    #[test]
+    fn repair_inconsistent_flags() {
+        let c = Color::new(Space::Srgb, 0.5, None, 0.5, 1.0);
+        assert!(c.is_consistent());
+
+        let mut c = Color::new(Space::Srgb, 0.5, 0.5, 0.5, None);
+        c.components.0 = Component::NAN;
+        c.alpha = 0.75;
+        assert!(!c.is_consistent());
+
+        let repaired = c.repair();
+        assert!(repaired.is_consistent());
+        assert_eq!(repaired.flags, Flags::C0_IS_NONE | Flags::ALPHA_IS_NONE);
+        assert_eq!(repaired.components, Components(0.0, 0.5, 0.5));
+        // The stored value of a missing component is left alone.
+        assert_eq!(repaired.alpha, 0.75);
+
+        let mut c = Color::new(Space::Srgb, 0.5, None, 0.5, 1.0);
+        c.components.1 = 0.25;
+        assert!(c.is_consistent());
+    }
+
+    #[test]
+    fn map_all_channels() {
+        let c = Color::new(Space::Srgb, 0.2, None, 0.4, 0.5);
+
+        let mapped = c.map_channels(|index, value| value + index as Component);
+        assert_eq!(mapped.c0(), Some(0.2));
+        assert_eq!(mapped.c1(), None);
+        assert_eq!(mapped.c2(), Some(2.4));
+        assert_eq!(mapped.alpha(), Some(1.0));
+
+        let halved = c.map_channels(|_, value| value / 2.0);
+        assert_eq!(halved.components, Components(0.1, 0.0, 0.2));
+        assert_eq!(halved.alpha, 0.25);
+        assert_eq!(halved.flags, Flags::C1_IS_NONE);
+    }
+
+    #[test]
+    fn component_metadata() {
+        assert_eq!(Space::Lab.component_names(), ["L", "a", "b"]);
+        assert_eq!(Space::Hsl.component_names(), ["H", "S", "L"]);
+        assert_eq!(Space::Hsl.component_ranges()[0], Some((0.0, 360.0)));
+        assert_eq!(
+            Space::Hsl.component_kinds(),
+            [
+                ComponentKind::Angle,
+                ComponentKind::Percentage,
+                ComponentKind::Percentage
+            ]
+        );
+        assert_eq!(Space::Oklch.component_kinds()[2], ComponentKind::Angle);
+        assert_eq!(Space::Lab.component_ranges()[0], Some((0.0, 100.0)));
+        assert_eq!(Space::XyzD65.component_ranges(), [None, None, None]);
+    }
+
+    #[test]
+    fn clamp_components_to_space_range() {
+        let c = Color::new(Space::Hsl, 400.0, 1.5, -0.5, 1.0).clamp_to_space_range();
+        assert_eq!(c.components, Components(40.0, 1.0, 0.0));
+
+        let c = Color::new(Space::Lab, 120.0, -200.0, None, 0.5).clamp_to_space_range();
+        assert_eq!(c.components, Components(100.0, -200.0, 0.0));
+        assert_eq!(c.flags, Flags::C2_IS_NONE);
+        assert_eq!(c.alpha, 0.5);
+
+        let c = Color::new(Space::Oklch, 0.5, 0.1, -30.0, 1.0).clamp_to_space_range();
+        assert_eq!(c.components, Components(0.5, 0.1, 330.0));
+
+        let c = Color::new(Space::Oklch, 1.5, -0.1, 30.0, 1.0).clamp_to_space_range();
+        assert_eq!(c.components, Components(1.0, 0.0, 30.0));
+
+        // Valid colors outside the reference ranges are not changed, e.g. the
+        // D65 white point in CIE-XYZ and wide chroma in Lch.
+        let white = Color::new(Space::XyzD65, 0.95047, 1.0, 1.08883, 1.0);
+        assert_eq!(white.clamp_to_space_range(), white);
+        let lch = Color::new(Space::Lch, 50.0, 180.0, 30.0, 1.0);
+        assert_eq!(lch.clamp_to_space_range(), lch);
+    }
+
+    #[test]
+    fn with_space_does_not_convert() {
+        let c = Color::new(Space::Srgb, 0.25, None, 0.75, 0.5).with_space(Space::DisplayP3);
+        assert_eq!(c.space, Space::DisplayP3);
+        assert_eq!(c.components, Components(0.25, 0.0, 0.75));
+        assert_eq!(c.flags, Flags::C1_IS_NONE);
+        assert_eq!(c.alpha, 0.5);
+    }
 }
@@ -1,7 +1,16 @@
 //! Tags for color spaces.
 
+use crate::Component;
+
 /// Represents a color space.
-pub trait ColorSpace: Clone {}
+pub trait ColorSpace: Clone {
+    /// The chroma at or below which a color in the cylindrical polar form of
+    /// this color space is considered achromatic, making its hue powerless.
+    /// The range of chroma differs a lot between color spaces, so the
+    /// threshold is chosen per color space.
+    /// <https://drafts.csswg.org/css-color-4/#color-conversion-code>
+    const ACHROMATIC_THRESHOLD: Component = Component::EPSILON;
+}
 
 /// The sRGB color space.
 #[derive(Clone, Debug)]
@@ -37,13 +46,31 @@ impl ColorSpace for Rec2020 {}
 #[derive(Clone, Debug)]
 pub struct Lab;
 
-impl ColorSpace for Lab {}
+impl ColorSpace for Lab {
+    const ACHROMATIC_THRESHOLD: Component = 0.0015;
+}
+
+/// The CIE-Lab color space with a D65 white point.
+#[derive(Clone, Debug)]
+pub struct LabD65;
+
+impl ColorSpace for LabD65 {
+    const ACHROMATIC_THRESHOLD: Component = 0.0015;
+}
+
+/// The Jzazbz color space.
+#[derive(Clone, Debug)]
+pub struct Jzazbz;
+
+impl ColorSpace for Jzazbz {}
 
 /// The Oklab color space.
 #[derive(Clone, Debug)]
 pub struct Oklab;
 
-impl ColorSpace for Oklab {}
+impl ColorSpace for Oklab {
+    const ACHROMATIC_THRESHOLD: Component = 0.000004;
+}
 
 /// The CIE-XYZ color space.
 #[derive(Clone, Debug)]
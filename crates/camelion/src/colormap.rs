@@ -0,0 +1,146 @@
+//! Perceptually uniform colormaps for scientific visualization.
+
+use std::sync::OnceLock;
+
+use crate::{palette::Ramp, Color, Component, Space};
+
+/// Colormaps commonly used for plotting data.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Colormap {
+    /// Blue to green to yellow, the default colormap of matplotlib.
+    /// <https://bids.github.io/colormap/>
+    Viridis,
+    /// Black to purple to pale yellow.
+    Magma,
+    /// Black to purple to orange to pale yellow.
+    Inferno,
+    /// Blue to purple to yellow.
+    Plasma,
+    /// An improved rainbow colormap, dark blue to green to dark red.
+    Turbo,
+}
+
+impl Colormap {
+    /// Return the color of the colormap at `t` in [0..1] (values outside are
+    /// clamped) in the sRGB color space. The control points of the colormap
+    /// are interpolated in the Oklab color space.
+    pub fn at(&self, t: Component) -> Color {
+        self.ramp().at(t).to_space(Space::Srgb)
+    }
+
+    /// The ramp through the control points of the colormap, built once on
+    /// first use.
+    fn ramp(&self) -> &'static Ramp {
+        static RAMPS: [OnceLock<Ramp>; 5] = [const { OnceLock::new() }; 5];
+
+        RAMPS[*self as usize].get_or_init(|| {
+            let anchors = self.control_points().map(|[red, green, blue]| {
+                let c = |v: u8| v as Component / 255.0;
+                Color::new(Space::Srgb, c(red), c(green), c(blue), 1.0)
+            });
+            Ramp::new(&anchors, Space::Oklab)
+        })
+    }
+
+    /// Evenly spaced control points of the colormap, sampled from the
+    /// published tables as 8-bit sRGB values.
+    fn control_points(&self) -> &'static [[u8; 3]; 9] {
+        match self {
+            Colormap::Viridis => &[
+                [0x44, 0x01, 0x54],
+                [0x47, 0x2D, 0x7B],
+                [0x3B, 0x52, 0x8B],
+                [0x2C, 0x72, 0x8E],
+                [0x21, 0x90, 0x8C],
+                [0x27, 0xAD, 0x81],
+                [0x5D, 0xC8, 0x63],
+                [0xAA, 0xDC, 0x32],
+                [0xFD, 0xE7, 0x25],
+            ],
+            Colormap::Magma => &[
+                [0x00, 0x00, 0x04],
+                [0x1D, 0x11, 0x47],
+                [0x51, 0x12, 0x7C],
+                [0x82, 0x26, 0x81],
+                [0xB6, 0x36, 0x79],
+                [0xE6, 0x51, 0x64],
+                [0xFB, 0x88, 0x61],
+                [0xFE, 0xC2, 0x87],
+                [0xFC, 0xFD, 0xBF],
+            ],
+            Colormap::Inferno => &[
+                [0x00, 0x00, 0x04],
+                [0x1F, 0x0C, 0x48],
+                [0x55, 0x0F, 0x6D],
+                [0x88, 0x22, 0x6A],
+                [0xBA, 0x36, 0x55],
+                [0xE3, 0x59, 0x32],
+                [0xF9, 0x8C, 0x0A],
+                [0xF9, 0xC9, 0x32],
+                [0xFC, 0xFF, 0xA4],
+            ],
+            Colormap::Plasma => &[
+                [0x0D, 0x08, 0x87],
+                [0x4C, 0x02, 0xA1],
+                [0x7E, 0x03, 0xA8],
+                [0xA9, 0x23, 0x95],
+                [0xCC, 0x46, 0x78],
+                [0xE5, 0x6B, 0x5D],
+                [0xF8, 0x94, 0x41],
+                [0xFD, 0xC3, 0x28],
+                [0xF0, 0xF9, 0x21],
+            ],
+            Colormap::Turbo => &[
+                [0x30, 0x12, 0x3B],
+                [0x46, 0x62, 0xD7],
+                [0x36, 0xAA, 0xF9],
+                [0x1A, 0xE4, 0xB6],
+                [0x72, 0xFE, 0x5E],
+                [0xC7, 0xEF, 0x34],
+                [0xFA, 0xBA, 0x39],
+                [0xF6, 0x6B, 0x19],
+                [0x7A, 0x04, 0x03],
+            ],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_component_eq;
+
+    #[test]
+    fn control_points_are_hit() {
+        let start = Colormap::Viridis.at(0.0);
+        assert_component_eq!(start.components.0, 0x44 as Component / 255.0);
+        assert_component_eq!(start.components.1, 0x01 as Component / 255.0);
+        assert_component_eq!(start.components.2, 0x54 as Component / 255.0);
+
+        let middle = Colormap::Magma.at(0.5);
+        assert_component_eq!(middle.components.0, 0xB6 as Component / 255.0);
+        assert_component_eq!(middle.components.1, 0x36 as Component / 255.0);
+        assert_component_eq!(middle.components.2, 0x79 as Component / 255.0);
+
+        let end = Colormap::Turbo.at(2.0);
+        assert_component_eq!(end.components.0, 0x7A as Component / 255.0);
+        assert_component_eq!(end.components.1, 0x04 as Component / 255.0);
+        assert_component_eq!(end.components.2, 0x03 as Component / 255.0);
+    }
+
+    #[test]
+    fn sequential_colormaps_increase_in_lightness() {
+        for colormap in [
+            Colormap::Viridis,
+            Colormap::Magma,
+            Colormap::Inferno,
+            Colormap::Plasma,
+        ] {
+            let lightness = (0..=20)
+                .map(|i| colormap.at(i as Component / 20.0))
+                .map(|c| c.to_space(Space::Oklab).components.0)
+                .collect::<Vec<_>>();
+            assert!(lightness.windows(2).all(|w| w[0] < w[1]), "{:?}", colormap);
+        }
+    }
+}
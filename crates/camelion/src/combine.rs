@@ -0,0 +1,208 @@
+//! Custom per-component arithmetic on colors with possibly missing
+//! components.
+
+use crate::{Color, Component, ComponentDetails, Space};
+
+/// How missing components are handled when combining two colors with
+/// [`Color::combine`].
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum MissingPolicy {
+    /// A missing component takes the value of the other side, like it does
+    /// during interpolation. If the component is missing on both sides, it is
+    /// missing in the result.
+    /// <https://drafts.csswg.org/css-color-4/#interpolation-missing>
+    #[default]
+    CarryForward,
+    /// A missing component is treated as 0. The result is never missing.
+    AsZero,
+    /// If a component is missing on either side, it is missing in the result.
+    Propagate,
+}
+
+impl MissingPolicy {
+    /// Combine a single pair of components according to the policy.
+    fn apply(
+        &self,
+        left: Option<Component>,
+        right: Option<Component>,
+        f: &impl Fn(Component, Component) -> Component,
+    ) -> Option<Component> {
+        match (self, left, right) {
+            (_, Some(left), Some(right)) => Some(f(left, right)),
+            (MissingPolicy::CarryForward, None, None) => None,
+            (MissingPolicy::CarryForward, Some(value), None)
+            | (MissingPolicy::CarryForward, None, Some(value)) => Some(f(value, value)),
+            (MissingPolicy::AsZero, left, right) => {
+                Some(f(left.unwrap_or(0.0), right.unwrap_or(0.0)))
+            }
+            (MissingPolicy::Propagate, _, _) => None,
+        }
+    }
+}
+
+impl Color {
+    /// Combine each of the color components of this color with the matching
+    /// component of `other` using `f`. Both colors are converted to the
+    /// specified [`Space`](color space) first and missing components are
+    /// handled according to `policy`. The result is in the specified color
+    /// space and keeps the alpha of this color.
+    ///
+    /// NOTE: Hue components are passed to `f` as is and the result is not
+    ///       normalized.
+    pub fn combine(
+        &self,
+        other: &Color,
+        space: Space,
+        f: impl Fn(Component, Component) -> Component,
+        policy: MissingPolicy,
+    ) -> Color {
+        let left = self.to_space(space);
+        let right = other.to_space(space);
+
+        let component = |left: Option<Component>, right: Option<Component>| {
+            ComponentDetails::from(policy.apply(left, right, &f))
+        };
+
+        Color::new(
+            space,
+            component(left.c0(), right.c0()),
+            component(left.c1(), right.c1()),
+            component(left.c2(), right.c2()),
+            left.alpha(),
+        )
+    }
+}
+
+/// Add two optional components, where a missing component does not
+/// contribute to the sum.
+fn add_present(left: Option<Component>, right: Option<Component>) -> Option<Component> {
+    match (left, right) {
+        (Some(left), Some(right)) => Some(left + right),
+        (value, None) | (None, value) => value,
+    }
+}
+
+/// Naive component-wise addition of two colors, including alpha, in the color
+/// space of the left hand side. The right hand side is converted to that color
+/// space first. A component missing on one side takes the value of the other
+/// side and stays missing if it is missing on both sides.
+///
+/// NOTE: This is NOT perceptual mixing and hues are not normalized. It is meant
+///       for quick prototyping, e.g. `a * 0.5 + b * 0.5`. Use
+///       [`Color::interpolate`] for mixing colors.
+impl std::ops::Add for Color {
+    type Output = Color;
+
+    fn add(self, rhs: Color) -> Self::Output {
+        let rhs = rhs.to_space(self.space);
+
+        Color::new(
+            self.space,
+            ComponentDetails::from(add_present(self.c0(), rhs.c0())),
+            ComponentDetails::from(add_present(self.c1(), rhs.c1())),
+            ComponentDetails::from(add_present(self.c2(), rhs.c2())),
+            ComponentDetails::from(add_present(self.alpha(), rhs.alpha())),
+        )
+    }
+}
+
+/// Naive scaling of each of the components, including alpha, of a color in its
+/// own color space. Missing components remain missing.
+///
+/// NOTE: This is NOT perceptual and hues are not normalized. See
+///       [`std::ops::Add`] for [`Color`].
+impl std::ops::Mul<Component> for Color {
+    type Output = Color;
+
+    fn mul(self, rhs: Component) -> Self::Output {
+        let scale = |value: Option<Component>| ComponentDetails::from(value.map(|v| v * rhs));
+
+        Color::new(
+            self.space,
+            scale(self.c0()),
+            scale(self.c1()),
+            scale(self.c2()),
+            scale(self.alpha()),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{assert_component_eq, Flags};
+
+    fn add(left: Component, right: Component) -> Component {
+        left + right
+    }
+
+    #[test]
+    fn combine_without_missing_components() {
+        let left = Color::new(Space::SrgbLinear, 0.1, 0.2, 0.3, 0.5);
+        let right = Color::new(Space::SrgbLinear, 0.3, 0.2, 0.1, 1.0);
+
+        let result = left.combine(&right, Space::SrgbLinear, add, MissingPolicy::Propagate);
+        assert_eq!(result.space, Space::SrgbLinear);
+        assert_eq!(result.flags, Flags::empty());
+        assert_component_eq!(result.components.0, 0.4, epsilon = 1.0e-6);
+        assert_component_eq!(result.components.1, 0.4, epsilon = 1.0e-6);
+        assert_component_eq!(result.components.2, 0.4, epsilon = 1.0e-6);
+        assert_eq!(result.alpha, 0.5);
+    }
+
+    #[test]
+    fn combine_with_missing_components() {
+        let left = Color::new(Space::Srgb, None, None, 0.25, 1.0);
+        let right = Color::new(Space::Srgb, 0.5, None, 0.25, 1.0);
+
+        let result = left.combine(&right, Space::Srgb, add, MissingPolicy::CarryForward);
+        assert_eq!(result.c0(), Some(1.0));
+        assert_eq!(result.c1(), None);
+        assert_eq!(result.c2(), Some(0.5));
+
+        let result = left.combine(&right, Space::Srgb, add, MissingPolicy::AsZero);
+        assert_eq!(result.c0(), Some(0.5));
+        assert_eq!(result.c1(), Some(0.0));
+        assert_eq!(result.c2(), Some(0.5));
+
+        let result = left.combine(&right, Space::Srgb, add, MissingPolicy::Propagate);
+        assert_eq!(result.c0(), None);
+        assert_eq!(result.c1(), None);
+        assert_eq!(result.c2(), Some(0.5));
+    }
+
+    #[test]
+    fn ops_blend_components() {
+        let a = Color::new(Space::SrgbLinear, 0.2, 0.4, None, 1.0);
+        let b = Color::new(Space::SrgbLinear, 0.6, 0.8, 0.5, 0.5);
+
+        let result = a * 0.5 + b * 0.5;
+        assert_eq!(result.space, Space::SrgbLinear);
+        assert_component_eq!(result.components.0, 0.4, epsilon = 1.0e-6);
+        assert_component_eq!(result.components.1, 0.6, epsilon = 1.0e-6);
+        assert_component_eq!(result.components.2, 0.25, epsilon = 1.0e-6);
+        assert_component_eq!(result.alpha, 0.75, epsilon = 1.0e-6);
+        assert_eq!(result.flags, Flags::empty());
+
+        let missing = Color::new(Space::SrgbLinear, None, 0.4, 0.2, None) * 2.0;
+        assert_eq!(missing.c0(), None);
+        assert_eq!(missing.c1(), Some(0.8));
+        assert_eq!(missing.alpha(), None);
+
+        let result = missing.clone() + missing;
+        assert_eq!(result.c0(), None);
+        assert_eq!(result.alpha(), None);
+    }
+
+    #[test]
+    fn ops_add_converts_to_left_space() {
+        let white = Color::new(Space::Srgb, 1.0, 1.0, 1.0, 0.0);
+        let black = Color::new(Space::DisplayP3, 0.0, 0.0, 0.0, 0.0);
+
+        let result = white + black;
+        assert_eq!(result.space, Space::Srgb);
+        assert_component_eq!(result.components.0, 1.0, epsilon = 1.0e-4);
+        assert_component_eq!(result.components.1, 1.0, epsilon = 1.0e-4);
+        assert_component_eq!(result.components.2, 1.0, epsilon = 1.0e-4);
+    }
+}
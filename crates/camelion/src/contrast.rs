@@ -0,0 +1,334 @@
+//! Contrast between colors, used to check the readability of text.
+//! <https://www.w3.org/TR/WCAG21/#contrast-minimum>
+//! <https://github.com/Myndex/apca-w3>
+
+use crate::{Color, Component, Space};
+
+/// Colors with a relative luminance below this threshold are considered dark.
+/// This is the luminance where the contrast ratio against black and against
+/// white are the same, sqrt(1.05 * 0.05) - 0.05.
+const DARK_LUMINANCE_THRESHOLD: Component = 0.179;
+
+/// Return the APCA (Accessible Perceptual Contrast Algorithm) lightness
+/// contrast (Lc) of `text` on top of `background`, using the APCA-W3 0.1.9
+/// constants. The result is roughly in the range [-108..106]. It is positive
+/// for dark text on a light background and negative for light text on a dark
+/// background. Contrasts too low to be meaningful are returned as 0.
+/// <https://github.com/Myndex/apca-w3>
+///
+/// NOTE: The colors are converted to sRGB and clamped to [0..1], because
+///       APCA is only defined for colors displayed on an sRGB screen.
+///       Missing components are treated as 0.
+pub fn apca_contrast(text: &Color, background: &Color) -> Component {
+    // The exponents of the power curves for normal (dark on light) and
+    // reverse (light on dark) polarity.
+    const NORMAL_BACKGROUND: Component = 0.56;
+    const NORMAL_TEXT: Component = 0.57;
+    const REVERSE_TEXT: Component = 0.62;
+    const REVERSE_BACKGROUND: Component = 0.65;
+
+    const BLACK_THRESHOLD: Component = 0.022;
+    const BLACK_CLAMP: Component = 1.414;
+    const SCALE: Component = 1.14;
+    const LOW_OFFSET: Component = 0.027;
+    const LOW_CLIP: Component = 0.1;
+    const DELTA_Y_MIN: Component = 0.0005;
+
+    let screen_luminance = |color: &Color| {
+        let srgb = color.to_space(Space::Srgb);
+        let channel = |value: Option<Component>| value.unwrap_or(0.0).clamp(0.0, 1.0).powf(2.4);
+        let y = 0.2126729 * channel(srgb.c0())
+            + 0.7151522 * channel(srgb.c1())
+            + 0.0721750 * channel(srgb.c2());
+
+        // Soft clamp the luminance of very dark colors, to account for flare.
+        if y > BLACK_THRESHOLD {
+            y
+        } else {
+            y + (BLACK_THRESHOLD - y).powf(BLACK_CLAMP)
+        }
+    };
+
+    let text = screen_luminance(text);
+    let background = screen_luminance(background);
+
+    if (background - text).abs() < DELTA_Y_MIN {
+        return 0.0;
+    }
+
+    let contrast = if background > text {
+        // Dark text on a light background.
+        let sapc = (background.powf(NORMAL_BACKGROUND) - text.powf(NORMAL_TEXT)) * SCALE;
+        if sapc < LOW_CLIP {
+            0.0
+        } else {
+            sapc - LOW_OFFSET
+        }
+    } else {
+        // Light text on a dark background.
+        let sapc = (background.powf(REVERSE_BACKGROUND) - text.powf(REVERSE_TEXT)) * SCALE;
+        if sapc > -LOW_CLIP {
+            0.0
+        } else {
+            sapc + LOW_OFFSET
+        }
+    };
+
+    contrast * 100.0
+}
+
+impl Color {
+    /// Return the relative luminance of this color, as defined by WCAG 2.1.
+    /// The color is converted to linear light sRGB and the components are
+    /// weighted with the Rec. 709 coefficients. Missing components are
+    /// treated as 0.
+    /// <https://www.w3.org/TR/WCAG21/#dfn-relative-luminance>
+    ///
+    /// NOTE: Out of gamut colors are not clamped, because clamping changes
+    ///       the luminance of the color.
+    pub fn relative_luminance(&self) -> Component {
+        let linear = self.to_space(Space::SrgbLinear);
+
+        0.2126 * linear.c0().unwrap_or(0.0)
+            + 0.7152 * linear.c1().unwrap_or(0.0)
+            + 0.0722 * linear.c2().unwrap_or(0.0)
+    }
+
+    /// Return the WCAG 2.1 contrast ratio between this color and `other`, in
+    /// the range [1..21]. The order of the colors does not matter.
+    /// <https://www.w3.org/TR/WCAG21/#dfn-contrast-ratio>
+    pub fn contrast_ratio(&self, other: &Color) -> Component {
+        let left = self.relative_luminance();
+        let right = other.relative_luminance();
+
+        (left.max(right) + 0.05) / (left.min(right) + 0.05)
+    }
+
+    /// Return true if this color has a relative luminance below 0.179, which
+    /// means that white text on top of it has a higher contrast ratio than
+    /// black text.
+    pub fn is_dark(&self) -> bool {
+        self.relative_luminance() < DARK_LUMINANCE_THRESHOLD
+    }
+
+    /// Return true if this color is not dark. See [`Color::is_dark`].
+    pub fn is_light(&self) -> bool {
+        !self.is_dark()
+    }
+
+    /// Return either black or white (in the sRGB color space), whichever has
+    /// the highest contrast ratio against this color when used for text on
+    /// top of it.
+    pub fn best_text_color(&self) -> Color {
+        let black = Color::new(Space::Srgb, 0.0, 0.0, 0.0, 1.0);
+        let white = Color::new(Space::Srgb, 1.0, 1.0, 1.0, 1.0);
+
+        if self.contrast_ratio(&white) > self.contrast_ratio(&black) {
+            white
+        } else {
+            black
+        }
+    }
+
+    /// Return a variant of this color that has a contrast ratio of at least
+    /// `target_ratio` against `against`. Only the Oklch lightness is changed,
+    /// the chroma and hue are kept. The lightness is moved in the direction
+    /// that reaches the target ratio with the smallest change. If the target
+    /// can not be reached, the lightness with the highest contrast ratio is
+    /// used. The result is in the same color space as this color.
+    ///
+    /// NOTE: Keeping the chroma at very high or low lightness might result
+    ///       in a color that is out of gamut.
+    pub fn adjust_for_contrast(&self, against: &Color, target_ratio: Component) -> Color {
+        const ITERATIONS: usize = 32;
+
+        if self.contrast_ratio(against) >= target_ratio {
+            return self.clone();
+        }
+
+        let oklch = self.to_space(Space::Oklch);
+        let with_lightness = |lightness: Component| {
+            Color::new(
+                Space::Oklch,
+                lightness,
+                oklch.c1(),
+                oklch.c2(),
+                oklch.alpha(),
+            )
+        };
+        let ratio = |lightness: Component| with_lightness(lightness).contrast_ratio(against);
+
+        // Binary search for the lightness closest to the current lightness
+        // that reaches the target ratio, between the current lightness and
+        // `limit`. Returns None if the ratio is not reached at `limit`.
+        let lightness = oklch.c0().unwrap_or(0.0);
+        let search = |limit: Component| {
+            if ratio(limit) < target_ratio {
+                return None;
+            }
+
+            let (mut low, mut high) = (lightness, limit);
+            for _ in 0..ITERATIONS {
+                let mid = (low + high) / 2.0;
+                if ratio(mid) >= target_ratio {
+                    high = mid;
+                } else {
+                    low = mid;
+                }
+            }
+            Some(high)
+        };
+
+        let adjusted = match (search(0.0), search(1.0)) {
+            (Some(darker), Some(lighter)) => {
+                if lightness - darker <= lighter - lightness {
+                    darker
+                } else {
+                    lighter
+                }
+            }
+            (Some(darker), None) => darker,
+            (None, Some(lighter)) => lighter,
+            (None, None) => {
+                if ratio(0.0) >= ratio(1.0) {
+                    0.0
+                } else {
+                    1.0
+                }
+            }
+        };
+
+        with_lightness(adjusted).to_space(self.space)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{assert_component_eq, LightnessRef};
+
+    #[test]
+    fn contrast_ratio() {
+        let black = Color::new(Space::Srgb, 0.0, 0.0, 0.0, 1.0);
+        let white = Color::new(Space::Srgb, 1.0, 1.0, 1.0, 1.0);
+
+        assert_component_eq!(white.relative_luminance(), 1.0);
+        assert_component_eq!(black.relative_luminance(), 0.0);
+        assert_component_eq!(white.contrast_ratio(&black), 21.0);
+        assert_component_eq!(black.contrast_ratio(&white), 21.0);
+        assert_component_eq!(white.contrast_ratio(&white), 1.0);
+
+        // The input color space does not matter.
+        let gray = Color::new(Space::Srgb, 0.5, 0.5, 0.5, 1.0);
+        assert_component_eq!(
+            gray.to_space(Space::Oklch).relative_luminance(),
+            gray.relative_luminance()
+        );
+    }
+
+    #[test]
+    fn apca() {
+        let gray = |value: Component| Color::new(Space::Srgb, value, value, value, 1.0);
+        let black = gray(0.0);
+        let white = gray(1.0);
+        let mid = gray(136.0 / 255.0);
+        let light = gray(170.0 / 255.0);
+
+        // Reference values from the APCA-W3 0.1.9 implementation.
+        let tests = [
+            (&black, &white, 106.04067),
+            (&white, &black, -107.88473),
+            (&mid, &white, 63.05647),
+            (&white, &mid, -68.54146),
+            (&black, &light, 58.14626),
+            (&light, &black, -56.24113),
+        ];
+        for (text, background, expected) in tests {
+            let lc = apca_contrast(text, background);
+            assert!((lc - expected).abs() < 1.0e-2, "{} != {}", lc, expected);
+        }
+
+        // The input color space does not matter.
+        assert_component_eq!(
+            apca_contrast(&mid.to_space(Space::Oklch), &white.to_space(Space::Lab)),
+            apca_contrast(&mid, &white)
+        );
+
+        // Contrasts that are too low are clipped to 0.
+        assert_eq!(apca_contrast(&mid, &mid), 0.0);
+        assert_eq!(apca_contrast(&gray(0.5), &gray(0.52)), 0.0);
+    }
+
+    #[test]
+    fn relative_luminance_out_of_gamut() {
+        // Out of gamut colors keep their linear light values, so a color
+        // brighter than sRGB white has a luminance above 1.
+        let bright = Color::new(Space::SrgbLinear, 1.5, 1.5, 1.5, 1.0);
+        assert_component_eq!(bright.relative_luminance(), 1.5);
+
+        // A saturated Display-P3 green has a negative sRGB red component,
+        // which is not clamped to 0.
+        let green = Color::new(Space::DisplayP3, 0.0, 1.0, 0.0, 1.0);
+        let linear = green.to_space(Space::SrgbLinear);
+        assert!(linear.c0().unwrap() < 0.0);
+        assert_component_eq!(
+            green.relative_luminance(),
+            0.2126 * linear.c0().unwrap()
+                + 0.7152 * linear.c1().unwrap()
+                + 0.0722 * linear.c2().unwrap()
+        );
+
+        // Missing components are treated as 0.
+        let red = Color::new(Space::SrgbLinear, 1.0, None, None, 1.0);
+        assert_component_eq!(red.relative_luminance(), 0.2126);
+    }
+
+    #[test]
+    fn text_color() {
+        let navy = Color::new(Space::Srgb, 0.0, 0.0, 0.5, 1.0);
+        assert!(navy.is_dark());
+        assert!(!navy.is_light());
+        assert_eq!(navy.best_text_color().components.0, 1.0);
+
+        let yellow = Color::new(Space::Srgb, 1.0, 1.0, 0.0, 1.0);
+        assert!(yellow.is_light());
+        assert_eq!(yellow.best_text_color().components.0, 0.0);
+
+        // Around the threshold, the text color follows the lightness.
+        for value in [0.40, 0.45, 0.5, 0.55, 0.6] {
+            let gray = Color::new(Space::Srgb, value, value, value, 1.0);
+            let text = gray.best_text_color();
+            assert_eq!(gray.is_dark(), text.components.0 == 1.0, "{}", value);
+        }
+    }
+
+    #[test]
+    fn adjust_for_contrast() {
+        let white = Color::new(Space::Srgb, 1.0, 1.0, 1.0, 1.0);
+        let black = Color::new(Space::Srgb, 0.0, 0.0, 0.0, 1.0);
+        let blue = Color::new(Space::Srgb, 0.4, 0.6, 1.0, 1.0);
+
+        // Darken against white.
+        let adjusted = blue.adjust_for_contrast(&white, 4.5);
+        assert_eq!(adjusted.space, Space::Srgb);
+        assert!(adjusted.contrast_ratio(&white) >= 4.5);
+        assert!(adjusted.contrast_ratio(&white) < 4.6);
+        assert!(adjusted.lightness(LightnessRef::Oklab) < blue.lightness(LightnessRef::Oklab));
+        assert!((adjusted.chroma() - blue.chroma()).abs() < 1.0e-3);
+        assert!((adjusted.hue().unwrap() - blue.hue().unwrap()).abs() < 0.1);
+
+        // Lighten against black.
+        let adjusted = blue.adjust_for_contrast(&black, 12.0);
+        assert!(adjusted.contrast_ratio(&black) >= 12.0);
+        assert!(adjusted.lightness(LightnessRef::Oklab) > blue.lightness(LightnessRef::Oklab));
+
+        // Already enough contrast.
+        assert_eq!(blue.adjust_for_contrast(&black, 3.0), blue);
+
+        // The target can not be reached against a mid gray, so the lightness
+        // with the highest contrast is used.
+        let gray = Color::new(Space::Srgb, 0.5, 0.5, 0.5, 1.0);
+        let adjusted = gray.adjust_for_contrast(&gray, 21.0);
+        assert_component_eq!(adjusted.contrast_ratio(&gray), black.contrast_ratio(&gray));
+    }
+}
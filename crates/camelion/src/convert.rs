@@ -21,141 +21,382 @@
 //! .to_polar();                    // convert to lch.
 //! ```
 
+use std::collections::BTreeMap;
+
 use crate::{
     color::{Color, Components, Space},
+    delta_e::delta_eok,
+    math::{transform, transform_3x3, Transform},
     models::{
-        A98Rgb, A98RgbLinear, DisplayP3, DisplayP3Linear, Hsl, Hwb, Lab, Lch, Model, Oklab, Oklch,
-        ProPhotoRgb, ProPhotoRgbLinear, Rec2020, Rec2020Linear, Srgb, SrgbLinear, XyzD50, XyzD65,
-        D50, D65,
+        A98Rgb, A98RgbLinear, Base, DisplayP3, DisplayP3Linear, Hct, Hsl, Hsv, Hwb, ICtCp, Ipt,
+        Jzazbz, Jzczhz, Lab, LabD65, Lch, LchD65, Oklab, Oklch, ProPhotoRgb, ProPhotoRgbLinear,
+        Rec2020, Rec2020Linear, Srgb, SrgbLinear, ToBase, XyzD50, XyzD65, D50, D65,
     },
+    Component,
 };
 
 impl Color {
     /// Convert this color from its current color space/notation to the
     /// specified color space/notation.
+    ///
+    /// NOTE: All conversion math is done in [`crate::Component`] precision.
+    ///       With the default 32-bit components, long conversion chains (e.g.
+    ///       ProPhoto RGB to Lab) can differ in the 5th to 6th decimal place.
+    ///       Enable the `f64` feature to do all conversions in 64-bit
+    ///       precision.
     pub fn to_space(&self, space: Space) -> Self {
-        use Space as S;
-
         if self.space == space {
             return self.clone();
         }
 
-        // Handle direct conversions.
-        match (self.space, space) {
-            (S::Srgb, S::SrgbLinear) => {
-                return self
-                    .as_model::<Srgb>()
-                    .to_linear_light()
-                    .to_color(self.alpha())
-            }
-            (S::SrgbLinear, S::Srgb) => {
-                return self
-                    .as_model::<SrgbLinear>()
-                    .to_gamma_encoded()
-                    .to_color(self.alpha())
-            }
-            (S::Srgb, S::Hsl) => return self.as_model::<Srgb>().to_hsl().to_color(self.alpha()),
-            (S::Hsl, S::Srgb) => return self.as_model::<Hsl>().to_srgb().to_color(self.alpha()),
-            (S::Srgb, S::Hwb) => return self.as_model::<Srgb>().to_hwb().to_color(self.alpha()),
-            (S::Hwb, S::Srgb) => return self.as_model::<Hwb>().to_srgb().to_color(self.alpha()),
-            (S::XyzD50, S::XyzD65) => {
-                return self
-                    .as_model::<XyzD50>()
-                    .transfer::<D65>()
-                    .to_color(self.alpha());
-            }
-            (S::XyzD65, S::XyzD50) => {
-                return self
-                    .as_model::<XyzD65>()
-                    .transfer::<D50>()
-                    .to_color(self.alpha())
-            }
-            (S::Hsl, S::Hwb) => {
-                return self
-                    .as_model::<Hsl>()
-                    .to_srgb()
-                    .to_hwb()
-                    .to_color(self.alpha())
-            }
-            (S::Hwb, S::Hsl) => {
-                return self
-                    .as_model::<Hwb>()
-                    .to_srgb()
-                    .to_hsl()
-                    .to_color(self.alpha())
-            }
-            (S::Lab, S::Lch) | (S::Oklab, S::Oklch) => {
-                return self.as_model::<Lab>().to_polar().to_color(self.alpha())
-            }
-            (S::Lch, S::Lab) | (S::Oklch, S::Oklab) => {
-                return self
-                    .as_model::<Lch>()
-                    .to_rectangular()
-                    .to_color(self.alpha())
-            }
-            _ => {}
+        let components = self.model_components();
+        let converted = match direct_conversion(self.space, space) {
+            Some(convert) => convert(components),
+            None => from_base(space)(to_base(self.space)(components)),
+        };
+
+        to_color(space, converted, self.alpha())
+    }
+
+    /// Convert this color to every color space, keyed by color space. The
+    /// color is converted to CIE-XYZ once and every other color space is
+    /// derived from that, which is cheaper than calling [`Color::to_space`]
+    /// for each color space. The entry for the color space of this color is
+    /// an unchanged copy.
+    pub fn to_space_all(&self) -> BTreeMap<Space, Color> {
+        let base = to_base(self.space)(self.model_components());
+
+        (0..=u8::MAX)
+            .filter_map(Space::from_u8)
+            .map(|space| {
+                let color = if space == self.space {
+                    self.clone()
+                } else {
+                    to_color(space, from_base(space)(base.clone()), self.alpha())
+                };
+                (space, color)
+            })
+            .collect()
+    }
+
+    /// Convert all the components in `src` from the `from` color space to
+    /// the `to` color space, writing the results to `out`. The conversion
+    /// path is resolved once for the whole slice, which is much faster than
+    /// calling [`Color::to_space`] for each color when converting large
+    /// buffers, e.g. images. Conversions between [`Space::SrgbLinear`],
+    /// [`Space::XyzD50`] and [`Space::XyzD65`] are combined into a single
+    /// matrix multiplication.
+    ///
+    /// NOTE: Components are handled like models do: a NaN value in `src` is
+    ///       treated as a missing component and powerless components in
+    ///       `out` are NaN. See the module documentation.
+    ///
+    /// Panics if `src` and `out` do not have the same length.
+    pub fn convert_many(src: &[Components], from: Space, to: Space, out: &mut [Components]) {
+        assert_eq!(
+            src.len(),
+            out.len(),
+            "source and destination must have the same length"
+        );
+
+        let conversion = Conversion::new(from, to);
+        for (src, out) in src.iter().zip(out.iter_mut()) {
+            *out = conversion.convert(*src);
         }
+    }
+}
 
-        macro_rules! to_base {
-            ($m:ident) => {{
-                self.as_model::<$m>().to_base()
-            }};
+/// A conversion between two color spaces, resolved once so that it can be
+/// applied to many components.
+enum Conversion {
+    /// The color spaces are the same.
+    Identity,
+    /// Both color spaces are linear transforms of CIE-XYZ, so the whole
+    /// conversion is a single matrix.
+    Matrix(Transform),
+    /// A conversion that does not go through the CIE-XYZ hub.
+    Direct(fn(Components) -> Components),
+    /// A conversion through the CIE-XYZ hub.
+    Hub(fn(Components) -> Base, fn(Base) -> Components),
+}
+
+impl Conversion {
+    fn new(from: Space, to: Space) -> Self {
+        fn is_linear(space: Space) -> bool {
+            matches!(space, Space::SrgbLinear | Space::XyzD50 | Space::XyzD65)
         }
 
-        // The rest converts to XyzD50.
-        use crate::models::ToBase;
-        let base = match self.space {
-            S::Srgb => to_base!(Srgb),
-            S::SrgbLinear => to_base!(SrgbLinear),
-            S::Hsl => to_base!(Hsl),
-            S::Hwb => to_base!(Hwb),
-            S::Lab => to_base!(Lab),
-            S::Lch => to_base!(Lch),
-            S::Oklab => to_base!(Oklab),
-            S::Oklch => to_base!(Oklch),
-            S::XyzD50 => to_base!(XyzD50),
-            S::XyzD65 => to_base!(XyzD65),
-            S::DisplayP3 => to_base!(DisplayP3),
-            S::A98Rgb => to_base!(A98Rgb),
-            S::ProPhotoRgb => to_base!(ProPhotoRgb),
-            S::Rec2020 => to_base!(Rec2020),
-        };
+        if from == to {
+            return Self::Identity;
+        }
+
+        if let Some(convert) = direct_conversion(from, to) {
+            return Self::Direct(convert);
+        }
+
+        let (to_base, from_base) = (to_base(from), from_base(to));
+        if !is_linear(from) || !is_linear(to) {
+            return Self::Hub(to_base, from_base);
+        }
+
+        // The rows of the matrix are the converted unit vectors.
+        let x = from_base(to_base(Components(1.0, 0.0, 0.0)));
+        let y = from_base(to_base(Components(0.0, 1.0, 0.0)));
+        let z = from_base(to_base(Components(0.0, 0.0, 1.0)));
+        Self::Matrix(transform_3x3(x.0, x.1, x.2, y.0, y.1, y.2, z.0, z.1, z.2))
+    }
+
+    #[inline]
+    fn convert(&self, components: Components) -> Components {
+        match self {
+            Self::Identity => components,
+            Self::Matrix(matrix) => transform(matrix, components),
+            Self::Direct(convert) => convert(components),
+            Self::Hub(to_base, from_base) => from_base(to_base(components)),
+        }
+    }
+}
+
+/// Create a color from the result of a conversion. NaN components are
+/// powerless and are marked as missing.
+fn to_color(space: Space, components: Components, alpha: Option<Component>) -> Color {
+    let c = |value: Component| if value.is_nan() { None } else { Some(value) };
+
+    Color::new(
+        space,
+        c(components.0),
+        c(components.1),
+        c(components.2),
+        alpha,
+    )
+}
+
+/// Return the conversion between two color spaces that does not go through
+/// the CIE-XYZ hub, if there is one.
+fn direct_conversion(from: Space, to: Space) -> Option<fn(Components) -> Components> {
+    use Space as S;
+
+    let convert: fn(Components) -> Components = match (from, to) {
+        (S::Srgb, S::SrgbLinear) => |c| Srgb::from(c).to_linear_light().to_components(),
+        (S::SrgbLinear, S::Srgb) => |c| SrgbLinear::from(c).to_gamma_encoded().to_components(),
+        (S::Srgb, S::Hsl) => |c| Srgb::from(c).to_hsl().to_components(),
+        (S::Hsl, S::Srgb) => |c| Hsl::from(c).to_srgb().to_components(),
+        (S::Srgb, S::Hwb) => |c| Srgb::from(c).to_hwb().to_components(),
+        (S::Hwb, S::Srgb) => |c| Hwb::from(c).to_srgb().to_components(),
+        (S::XyzD50, S::XyzD65) => |c| XyzD50::from(c).transfer::<D65>().to_components(),
+        (S::XyzD65, S::XyzD50) => |c| XyzD65::from(c).transfer::<D50>().to_components(),
+        (S::Srgb, S::Hsv) => |c| Srgb::from(c).to_hsv().to_components(),
+        (S::Hsv, S::Srgb) => |c| Hsv::from(c).to_srgb().to_components(),
+        (S::Hsl, S::Hwb) => |c| Hsl::from(c).to_srgb().to_hwb().to_components(),
+        (S::Hwb, S::Hsl) => |c| Hwb::from(c).to_srgb().to_hsl().to_components(),
+        (S::Hsl, S::Hsv) => |c| Hsl::from(c).to_srgb().to_hsv().to_components(),
+        (S::Hsv, S::Hsl) => |c| Hsv::from(c).to_srgb().to_hsl().to_components(),
+        (S::Hwb, S::Hsv) => |c| Hwb::from(c).to_srgb().to_hsv().to_components(),
+        (S::Hsv, S::Hwb) => |c| Hsv::from(c).to_srgb().to_hwb().to_components(),
+        (S::Lab, S::Lch) => |c| Lab::from(c).to_polar().to_components(),
+        (S::Lch, S::Lab) => |c| Lch::from(c).to_rectangular().to_components(),
+        (S::LabD65, S::LchD65) => |c| LabD65::from(c).to_polar().to_components(),
+        (S::LchD65, S::LabD65) => |c| LchD65::from(c).to_rectangular().to_components(),
+        (S::Jzazbz, S::Jzczhz) => |c| Jzazbz::from(c).to_polar().to_components(),
+        (S::Jzczhz, S::Jzazbz) => |c| Jzczhz::from(c).to_rectangular().to_components(),
+        (S::Oklab, S::Oklch) => |c| Oklab::from(c).to_polar().to_components(),
+        (S::Oklch, S::Oklab) => |c| Oklch::from(c).to_rectangular().to_components(),
+        _ => return None,
+    };
+
+    Some(convert)
+}
+
+/// Return the conversion from the specified color space to the CIE-XYZ hub
+/// that all conversions without a direct path go through.
+fn to_base(space: Space) -> fn(Components) -> Base {
+    use Space as S;
 
-        match space {
-            S::Srgb => SrgbLinear::from(base.transfer())
+    macro_rules! to_base {
+        ($m:ident) => {{
+            |c| $m::from(c).to_base()
+        }};
+    }
+
+    match space {
+        S::Srgb => to_base!(Srgb),
+        S::SrgbLinear => to_base!(SrgbLinear),
+        S::Hsl => to_base!(Hsl),
+        S::Hwb => to_base!(Hwb),
+        S::Hsv => to_base!(Hsv),
+        S::Lab => to_base!(Lab),
+        S::Lch => to_base!(Lch),
+        S::Oklab => to_base!(Oklab),
+        S::Oklch => to_base!(Oklch),
+        S::XyzD50 => to_base!(XyzD50),
+        S::XyzD65 => to_base!(XyzD65),
+        S::DisplayP3 => to_base!(DisplayP3),
+        S::A98Rgb => to_base!(A98Rgb),
+        S::ProPhotoRgb => to_base!(ProPhotoRgb),
+        S::Rec2020 => to_base!(Rec2020),
+        S::Hct => to_base!(Hct),
+        S::LabD65 => to_base!(LabD65),
+        S::LchD65 => to_base!(LchD65),
+        S::Jzazbz => to_base!(Jzazbz),
+        S::Jzczhz => to_base!(Jzczhz),
+        S::Ipt => to_base!(Ipt),
+        S::ICtCp => to_base!(ICtCp),
+    }
+}
+
+/// Return the conversion from the CIE-XYZ hub to the specified color space.
+fn from_base(space: Space) -> fn(Base) -> Components {
+    use Space as S;
+
+    match space {
+        S::Srgb => |base| {
+            SrgbLinear::from(base.transfer())
                 .to_gamma_encoded()
-                .to_color(self.alpha()),
-            S::SrgbLinear => SrgbLinear::from(base.transfer()).to_color(self.alpha()),
-            S::Hsl => SrgbLinear::from(base.transfer())
+                .to_components()
+        },
+        S::SrgbLinear => |base| SrgbLinear::from(base.transfer()).to_components(),
+        S::Hsl => |base| {
+            SrgbLinear::from(base.transfer())
                 .to_gamma_encoded()
                 .to_hsl()
-                .to_color(self.alpha()),
-            S::Hwb => SrgbLinear::from(base.transfer())
+                .to_components()
+        },
+        S::Hwb => |base| {
+            SrgbLinear::from(base.transfer())
                 .to_gamma_encoded()
                 .to_hwb()
-                .to_color(self.alpha()),
-            S::Lab => Lab::from(base.transfer()).to_color(self.alpha()),
-            S::Lch => Lab::from(base.transfer()).to_polar().to_color(self.alpha()),
-            S::Oklab => Oklab::from(base.transfer()).to_color(self.alpha()),
-            S::Oklch => Oklab::from(base.transfer())
-                .to_polar()
-                .to_color(self.alpha()),
-            S::DisplayP3 => DisplayP3Linear::from(base.transfer())
+                .to_components()
+        },
+        S::Hsv => |base| {
+            SrgbLinear::from(base.transfer())
                 .to_gamma_encoded()
-                .to_color(self.alpha()),
-            S::A98Rgb => A98RgbLinear::from(base.transfer())
+                .to_hsv()
+                .to_components()
+        },
+        S::Lab => |base| Lab::from(base.transfer()).to_components(),
+        S::Lch => |base| Lab::from(base.transfer()).to_polar().to_components(),
+        S::Oklab => |base| Oklab::from(base.transfer()).to_components(),
+        S::Oklch => |base| Oklab::from(base.transfer()).to_polar().to_components(),
+        S::DisplayP3 => |base| {
+            DisplayP3Linear::from(base.transfer())
                 .to_gamma_encoded()
-                .to_color(self.alpha()),
-            S::ProPhotoRgb => ProPhotoRgbLinear::from(base.transfer())
+                .to_components()
+        },
+        S::A98Rgb => |base| {
+            A98RgbLinear::from(base.transfer())
                 .to_gamma_encoded()
-                .to_color(self.alpha()),
-            S::Rec2020 => Rec2020Linear::from(base.transfer())
+                .to_components()
+        },
+        S::ProPhotoRgb => |base| {
+            ProPhotoRgbLinear::from(base.transfer())
                 .to_gamma_encoded()
-                .to_color(self.alpha()),
-            S::XyzD50 => base.transfer::<D50>().to_color(self.alpha()),
-            S::XyzD65 => base.transfer::<D65>().to_color(self.alpha()),
+                .to_components()
+        },
+        S::Rec2020 => |base| {
+            Rec2020Linear::from(base.transfer())
+                .to_gamma_encoded()
+                .to_components()
+        },
+        S::XyzD50 => |base| base.transfer::<D50>().to_components(),
+        S::XyzD65 => |base| base.transfer::<D65>().to_components(),
+        S::Hct => |base| Hct::from(base).to_components(),
+        S::LabD65 => |base| LabD65::from(base).to_components(),
+        S::LchD65 => |base| LabD65::from(base).to_polar().to_components(),
+        S::Jzazbz => |base| Jzazbz::from(base).to_components(),
+        S::Jzczhz => |base| Jzazbz::from(base).to_polar().to_components(),
+        S::Ipt => |base| Ipt::from(base).to_components(),
+        S::ICtCp => |base| ICtCp::from(base).to_components(),
+    }
+}
+
+impl Color {
+    /// Remove the gamma encoding from this color, moving it from
+    /// [`Space::Srgb`] to [`Space::SrgbLinear`].
+    ///
+    /// NOTE: Only sRGB has a linear light counterpart in [`Space`]. Colors in
+    ///       any other color space (including the other RGB color spaces) are
+    ///       returned unchanged. Use the models, e.g.
+    ///       [`DisplayP3::to_linear_light`], for those.
+    pub fn to_linear(&self) -> Self {
+        match self.space {
+            Space::Srgb => self.to_space(Space::SrgbLinear),
+            _ => self.clone(),
+        }
+    }
+
+    /// Apply gamma encoding to this color, moving it from
+    /// [`Space::SrgbLinear`] to [`Space::Srgb`].
+    ///
+    /// NOTE: Colors in any other color space are returned unchanged. See
+    ///       [`Color::to_linear`].
+    pub fn to_gamma(&self) -> Self {
+        match self.space {
+            Space::SrgbLinear => self.to_space(Space::Srgb),
+            _ => self.clone(),
         }
     }
+
+    /// Convert this color to [`Space::SrgbLinear`] and return the red, green,
+    /// blue and alpha components as `f32`s, clipped to [0..1], ready to be
+    /// uploaded to a GPU. The color components are multiplied by alpha if
+    /// `alpha_mode` is [`AlphaMode::Premultiplied`].
+    ///
+    /// NOTE: Missing components (including alpha) are treated as 0.
+    #[allow(clippy::unnecessary_cast)] // Component can be an f64.
+    pub fn to_linear_srgb_f32(&self, alpha_mode: AlphaMode) -> [f32; 4] {
+        let linear = self.to_space(Space::SrgbLinear).clip();
+
+        let alpha = linear.alpha.clamp(0.0, 1.0);
+        let Components(red, green, blue) = match alpha_mode {
+            AlphaMode::Straight => linear.components,
+            AlphaMode::Premultiplied => linear.components.map(|c| c * alpha),
+        };
+
+        [red as f32, green as f32, blue as f32, alpha as f32]
+    }
+}
+
+/// The largest error [`conversion_roundtrip_error`] returns for any pair of
+/// color spaces: a deltaE OK (the euclidean distance in Oklab) of 1e-4.
+pub const ROUNDTRIP_TOLERANCE: Component = 1.0e-4;
+
+/// Return the largest error when converting colors from the `from` color
+/// space to the `to` color space and back again, as the deltaE OK between
+/// the original and the round-tripped color. A grid of colors inside the
+/// sRGB gamut is used as samples.
+///
+/// Every pair of color spaces round-trips within [`ROUNDTRIP_TOLERANCE`].
+pub fn conversion_roundtrip_error(from: Space, to: Space) -> Component {
+    const STEPS: usize = 5;
+
+    let step = |v: usize| v as Component / (STEPS - 1) as Component;
+
+    (0..STEPS * STEPS * STEPS)
+        .map(|i| {
+            let sample = Color::new(
+                Space::Srgb,
+                step(i % STEPS),
+                step(i / STEPS % STEPS),
+                step(i / (STEPS * STEPS)),
+                1.0,
+            )
+            .to_space(from);
+
+            let back = sample.to_space(to).to_space(from);
+            delta_eok(&sample, &back)
+        })
+        .fold(0.0, Component::max)
+}
+
+/// How the alpha component is applied to the color components when
+/// exporting them.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum AlphaMode {
+    /// The color components are not multiplied by alpha.
+    #[default]
+    Straight,
+    /// The color components are multiplied by alpha.
+    Premultiplied,
 }
 
 impl Srgb {
@@ -168,6 +409,11 @@ impl Srgb {
     pub fn to_hwb(&self) -> Hwb {
         util::rgb_to_hwb(&Components(self.red, self.green, self.blue)).into()
     }
+
+    /// Convert a color specified in the sRGB color space to the HSV notation.
+    pub fn to_hsv(&self) -> Hsv {
+        util::rgb_to_hsv(&Components(self.red, self.green, self.blue)).into()
+    }
 }
 
 impl Hsl {
@@ -177,6 +423,13 @@ impl Hsl {
     }
 }
 
+impl Hsv {
+    /// Convert this color from the HSV notation to the sRGB color space.
+    pub fn to_srgb(&self) -> Srgb {
+        util::hsv_to_rgb(&Components(self.hue, self.saturation, self.value)).into()
+    }
+}
+
 impl Hwb {
     /// Convert this color from the HWB notation to the sRGB color space.
     pub fn to_srgb(&self) -> Srgb {
@@ -231,6 +484,12 @@ mod util {
                 (max - lightness) / lightness.min(1.0 - lightness)
             };
 
+        // Colors far outside the sRGB gamut can produce a negative saturation.
+        // If so, rotate the hue by 180 degrees and use a positive saturation.
+        if saturation < 0.0 {
+            return Components(normalize_hue(hue + 180.0), -saturation, lightness);
+        }
+
         Components(hue, saturation, lightness)
     }
 
@@ -256,6 +515,40 @@ mod util {
         Components(f!(0.0), f!(8.0), f!(4.0))
     }
 
+    /// Convert from RGB notation to HSV notation. The hue is powerless (NaN)
+    /// if the saturation is 0.
+    pub fn rgb_to_hsv(from: &Components) -> Components {
+        let (hue, min, max) = rgb_to_hue_with_min_max(from);
+
+        let saturation = if almost_zero(max) {
+            0.0
+        } else {
+            (max - min) / max
+        };
+
+        Components(hue, saturation, max)
+    }
+
+    /// Convert from HSV notation to RGB notation.
+    pub fn hsv_to_rgb(from: &Components) -> Components {
+        let Components(hue, saturation, value) = from.map(normalize);
+
+        if saturation <= 0.0 {
+            return Components(value, value, value);
+        }
+
+        let hue = normalize_hue(hue);
+
+        macro_rules! f {
+            ($n:expr) => {{
+                let k = ($n + hue / 60.0) % 6.0;
+                value - value * saturation * k.min(4.0 - k).clamp(0.0, 1.0)
+            }};
+        }
+
+        Components(f!(5.0), f!(3.0), f!(1.0))
+    }
+
     /// Convert from RGB notation to HWB notation.
     /// <https://drafts.csswg.org/css-color-4/#rgb-to-hwb>
     pub fn rgb_to_hwb(from: &Components) -> Components {
@@ -293,6 +586,97 @@ mod tests {
         Component,
     };
 
+    #[test]
+    fn every_space_round_trips() {
+        let spaces = (0..=u8::MAX).filter_map(Space::from_u8).collect::<Vec<_>>();
+
+        for &from in &spaces {
+            for &to in &spaces {
+                let error = conversion_roundtrip_error(from, to);
+                assert!(
+                    error <= ROUNDTRIP_TOLERANCE,
+                    "{:?} -> {:?} -> {:?} error {} is too large",
+                    from,
+                    to,
+                    from,
+                    error
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn convert_many_matches_to_space() {
+        let src = [
+            Components(1.0, 0.5, 0.25),
+            Components(0.2, 0.8, 0.4),
+            Components(0.5, 0.5, 0.5),
+            Components(0.0, 0.0, 0.0),
+        ];
+        let spaces = (0..=u8::MAX).filter_map(Space::from_u8).collect::<Vec<_>>();
+
+        for &from in &spaces {
+            // Use valid colors in every color space.
+            let colors = src
+                .iter()
+                .map(|c| Color::new(Space::Srgb, c.0, c.1, c.2, 1.0).to_space(from))
+                .collect::<Vec<_>>();
+            let src = colors
+                .iter()
+                .map(|c| c.model_components())
+                .collect::<Vec<_>>();
+
+            for &to in &spaces {
+                let mut out = vec![Components(0.0, 0.0, 0.0); src.len()];
+                Color::convert_many(&src, from, to, &mut out);
+
+                for (color, out) in colors.iter().zip(out) {
+                    let expected = color.to_space(to).model_components();
+                    assert_component_eq!(out.0, expected.0, epsilon = 1.0e-3);
+                    assert_component_eq!(out.1, expected.1, epsilon = 1.0e-3);
+                    assert_component_eq!(out.2, expected.2, epsilon = 1.0e-3);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn convert_many_with_a_matrix() {
+        let src = vec![Components(0.25, 0.5, 0.75); 1000];
+        let mut out = vec![Components(0.0, 0.0, 0.0); src.len()];
+        Color::convert_many(&src, Space::XyzD65, Space::SrgbLinear, &mut out);
+
+        let expected = Color::new(Space::XyzD65, 0.25, 0.5, 0.75, 1.0).to_space(Space::SrgbLinear);
+        for c in out {
+            assert_component_eq!(c.0, expected.components.0, epsilon = 1.0e-5);
+            assert_component_eq!(c.1, expected.components.1, epsilon = 1.0e-5);
+            assert_component_eq!(c.2, expected.components.2, epsilon = 1.0e-5);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn convert_many_with_different_lengths() {
+        let src = [Components(0.0, 0.0, 0.0); 2];
+        let mut out = [Components(0.0, 0.0, 0.0); 3];
+        Color::convert_many(&src, Space::Srgb, Space::Lab, &mut out);
+    }
+
+    #[test]
+    fn convert_to_every_space() {
+        let color = Color::new(Space::DisplayP3, 0.8, 0.4, 0.2, 0.5);
+        let all = color.to_space_all();
+
+        assert_eq!(all.len(), (0..=u8::MAX).filter_map(Space::from_u8).count());
+        assert_eq!(all[&Space::DisplayP3], color);
+        for (space, converted) in &all {
+            let expected = color.to_space(*space);
+            assert_eq!(converted.space, *space);
+            assert_eq!(converted.alpha(), Some(0.5));
+            assert!(delta_eok(converted, &expected) < 1.0e-4, "{:?}", space);
+        }
+    }
+
     #[test]
     fn test_conversions() {
         use Space as S;
@@ -343,6 +727,20 @@ mod tests {
             (S::Hwb, 25.000000, 0.117647, 0.176471, S::Rec2020, 0.669266, 0.401900, 0.142716),
             (S::Hwb, 25.000000, 0.117647, 0.176471, S::XyzD50, 0.337301, 0.245449, 0.031959),
             (S::Hwb, 25.000000, 0.117647, 0.176471, S::XyzD65, 0.318634, 0.239006, 0.041637),
+
+            (S::Hsv, 25.000000, 0.857143, 0.823529, S::Srgb, 0.823529, 0.411765, 0.117647),
+            (S::Hsv, 25.000000, 0.857143, 0.823529, S::Hsl, 25.000000, 0.750000, 0.470588),
+            (S::Hsv, 25.000000, 0.857143, 0.823529, S::Hwb, 25.000000, 0.117647, 0.176471),
+            (S::Hsv, 25.000000, 0.857143, 0.823529, S::Hsv, 25.000000, 0.857143, 0.823529),
+            (S::Hsv, 25.000000, 0.857143, 0.823529, S::Lab, 56.629300, 39.237080, 57.553769),
+            (S::Hsv, 25.000000, 0.857143, 0.823529, S::Oklch, 0.634398, 0.154992, 50.266483),
+            (S::Hsv, 25.000000, 0.857143, 0.823529, S::XyzD65, 0.318634, 0.239006, 0.041637),
+            (S::Srgb, 0.823529, 0.411765, 0.117647, S::Hsv, 25.000000, 0.857143, 0.823529),
+            (S::Hsl, 25.000000, 0.750000, 0.470588, S::Hsv, 25.000000, 0.857143, 0.823529),
+            (S::Hwb, 25.000000, 0.117647, 0.176471, S::Hsv, 25.000000, 0.857143, 0.823529),
+            (S::Lab, 56.629300, 39.237080, 57.553769, S::Hsv, 25.000023, 0.857143, 0.823529),
+            (S::Oklch, 0.634398, 0.154992, 50.266483, S::Hsv, 25.000000, 0.857143, 0.823529),
+            (S::XyzD65, 0.318634, 0.239006, 0.041637, S::Hsv, 25.000000, 0.857143, 0.823529),
             (S::Lab, 56.629300, 39.237080, 57.553769, S::Srgb, 0.823529, 0.411765, 0.117647),
             (S::Lab, 56.629300, 39.237080, 57.553769, S::Hsl, 25.000023, 0.750000, 0.470588),
             (S::Lab, 56.629300, 39.237080, 57.553769, S::Hwb, 25.000023, 0.117647, 0.176471),
@@ -518,6 +916,17 @@ mod tests {
         assert!(Srgb::new(0.5, 0.5, 0.5).to_hsl().hue.is_nan());
     }
 
+    #[test]
+    fn hsv_hue_is_powerless_if_there_is_no_saturation() {
+        assert!(Srgb::new(1.0, 1.0, 1.0).to_hsv().hue.is_nan());
+        assert!(Srgb::new(0.0, 0.0, 0.0).to_hsv().hue.is_nan());
+        assert!(Srgb::new(0.5, 0.5, 0.5).to_hsv().hue.is_nan());
+
+        let gray = Color::new(Space::Srgb, 0.5, 0.5, 0.5, 1.0).to_space(Space::Hsv);
+        assert_eq!(gray.c0(), None);
+        assert_eq!(gray.c1(), Some(0.0));
+    }
+
     #[test]
     fn hwb_to_rgb() {
         // hwb(40deg 30% 40%)
@@ -534,6 +943,30 @@ mod tests {
         // assert_component_eq!((srgb.components.2 * 255.0).round() as u8, 77);
     }
 
+    #[test]
+    fn linear_srgb_f32_for_gpus() {
+        let c = Color::new(Space::Srgb, 1.0, 0.5, 0.0, 0.5);
+
+        let straight = c.to_linear_srgb_f32(AlphaMode::Straight);
+        assert_component_eq!(straight[0] as Component, 1.0);
+        assert_component_eq!(straight[1] as Component, 0.214041);
+        assert_component_eq!(straight[2] as Component, 0.0);
+        assert_eq!(straight[3], 0.5);
+
+        let premultiplied = c.to_linear_srgb_f32(AlphaMode::Premultiplied);
+        assert_component_eq!(premultiplied[0] as Component, 0.5);
+        assert_component_eq!(premultiplied[1] as Component, 0.107021);
+        assert_component_eq!(premultiplied[2] as Component, 0.0);
+        assert_eq!(premultiplied[3], 0.5);
+
+        // Out of gamut components are clipped.
+        let c = Color::new(Space::SrgbLinear, 1.5, -0.5, 0.25, 1.0);
+        assert_eq!(
+            c.to_linear_srgb_f32(AlphaMode::Straight),
+            [1.0, 0.0, 0.25, 1.0]
+        );
+    }
+
     #[test]
     fn converting_a_color_should_maintain_source_alpha() {
         let hsl = Color::new(Space::Hsl, 120.0, 0.4, 0.4, None);
@@ -552,6 +985,32 @@ mod tests {
         assert_eq!(result.alpha(), Some(1.0));
     }
 
+    #[test]
+    fn to_linear_and_gamma() {
+        let srgb = Color::new(Space::Srgb, 0.823529, 0.411765, 0.117647, 0.5);
+
+        let linear = srgb.to_linear();
+        assert_eq!(linear.space, Space::SrgbLinear);
+        assert_component_eq!(linear.components.0, 0.644480);
+        assert_component_eq!(linear.components.1, 0.141263);
+        assert_component_eq!(linear.components.2, 0.012983);
+        assert_eq!(linear.alpha, 0.5);
+
+        // Already linear.
+        assert_eq!(linear.to_linear().components, linear.components);
+
+        let gamma = linear.to_gamma();
+        assert_eq!(gamma.space, Space::Srgb);
+        assert_component_eq!(gamma.components.0, 0.823529);
+        assert_component_eq!(gamma.components.1, 0.411765);
+        assert_component_eq!(gamma.components.2, 0.117647);
+
+        // Color spaces without a linear light counterpart are unchanged.
+        let lab = Color::new(Space::Lab, 50.0, 10.0, 20.0, 1.0);
+        assert_eq!(lab.to_linear().space, Space::Lab);
+        assert_eq!(lab.to_gamma().components, lab.components);
+    }
+
     #[test]
     fn rgb_to_hsl() {
         // color(srgb 0.46 0.52 0.28 / 0.5)
@@ -561,4 +1020,18 @@ mod tests {
         assert_component_eq!(hsl.components.1, 0.3);
         assert_component_eq!(hsl.components.2, 0.4);
     }
+
+    #[test]
+    fn rgb_to_hsl_with_negative_saturation() {
+        // Colors far outside of the sRGB gamut rotate the hue instead of
+        // producing a negative saturation.
+        let srgb = Color::new(Space::Srgb, 1.59343, 0.58802, 1.40564, 1.0);
+        let hsl = srgb.to_space(Space::Hsl);
+        assert!(hsl.components.1 > 0.0);
+
+        let srgb = hsl.to_space(Space::Srgb);
+        assert_component_eq!(srgb.components.0, 1.59343);
+        assert_component_eq!(srgb.components.1, 0.58802);
+        assert_component_eq!(srgb.components.2, 1.40564);
+    }
 }
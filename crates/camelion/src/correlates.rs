@@ -0,0 +1,181 @@
+//! Perceptual correlates (chroma, hue and lightness) and the correlated color
+//! temperature of colors in any color space.
+
+use crate::{
+    color_space::{self, ColorSpace},
+    models::XyzD65,
+    Color, Component, Space,
+};
+
+/// The largest distance from the Planckian locus in the CIE 1960 UCS
+/// (Duv) for which a correlated color temperature is still meaningful.
+/// <https://en.wikipedia.org/wiki/Color_temperature#Correlated_color_temperature>
+const MAX_LOCUS_DISTANCE: Component = 0.05;
+
+/// Return the CIE 1960 UCS (u, v) coordinates of a color, or None if the
+/// color is black.
+fn uv_1960(xyz: &XyzD65) -> Option<(Component, Component)> {
+    let denominator = xyz.x + 15.0 * xyz.y + 3.0 * xyz.z;
+    if denominator <= 0.0 {
+        return None;
+    }
+
+    Some((4.0 * xyz.x / denominator, 6.0 * xyz.y / denominator))
+}
+
+/// The definition of lightness used by [`Color::lightness`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum LightnessRef {
+    /// The CIE-Lab lightness (L*), in the range [0..100].
+    Cielab,
+    /// The Oklab lightness, in the range [0..1].
+    Oklab,
+    /// The HSL lightness, in the range [0..1].
+    Hsl,
+}
+
+impl Color {
+    /// Return the lightness of this color using the given definition of
+    /// lightness. A missing lightness component is returned as 0.
+    pub fn lightness(&self, reference: LightnessRef) -> Component {
+        match reference {
+            LightnessRef::Cielab => self.to_space(Space::Lab).c0(),
+            LightnessRef::Oklab => self.to_space(Space::Oklab).c0(),
+            LightnessRef::Hsl => self.to_space(Space::Hsl).c2(),
+        }
+        .unwrap_or(0.0)
+    }
+
+    /// Return the chroma of this color. The color is converted to the Oklch
+    /// color space, so the chroma is comparable between colors in any color
+    /// space. A missing chroma component is returned as 0.
+    pub fn chroma(&self) -> Component {
+        self.to_space(Space::Oklch).c1().unwrap_or(0.0)
+    }
+
+    /// Return the hue of this color in degrees, or None if the hue is
+    /// powerless (or missing). The color is converted to the Oklch color
+    /// space, so the hue is comparable between colors in any color space.
+    pub fn hue(&self) -> Option<Component> {
+        let oklch = self.to_space(Space::Oklch);
+
+        if oklch.c1().unwrap_or(0.0) <= color_space::Oklab::ACHROMATIC_THRESHOLD {
+            return None;
+        }
+
+        oklch.c2()
+    }
+
+    /// Return an estimate of the correlated color temperature (CCT) of this
+    /// color in kelvin, using McCamy's approximation from the xy
+    /// chromaticity. Returns None for black and for colors that are too far
+    /// from the Planckian locus, or outside
+    /// [`XyzD65::BLACKBODY_RANGE`], for a temperature to be meaningful.
+    /// <https://doi.org/10.1002/col.5080170211>
+    ///
+    /// NOTE: McCamy's approximation is most accurate between 2000K and
+    ///       12500K.
+    pub fn cct(&self) -> Option<Component> {
+        let xyz = self.to_space(Space::XyzD65).as_model::<XyzD65>();
+
+        let sum = xyz.x + xyz.y + xyz.z;
+        if sum.is_nan() || sum <= 0.0 {
+            return None;
+        }
+        let (x, y) = (xyz.x / sum, xyz.y / sum);
+
+        let n = (x - 0.3320) / (0.1858 - y);
+        let cct = 449.0 * n * n * n + 3525.0 * n * n + 6823.3 * n + 5520.33;
+
+        let (min, max) = XyzD65::BLACKBODY_RANGE;
+        if !(min..=max).contains(&cct) {
+            return None;
+        }
+
+        // Compare the chromaticity with the blackbody at the estimated
+        // temperature.
+        let (u, v) = uv_1960(&xyz)?;
+        let (locus_u, locus_v) = uv_1960(&XyzD65::from_blackbody(cct))?;
+        let distance = ((u - locus_u).powi(2) + (v - locus_v).powi(2)).sqrt();
+        if distance > MAX_LOCUS_DISTANCE {
+            return None;
+        }
+
+        Some(cct)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_component_eq;
+
+    #[test]
+    fn chroma_and_hue_from_any_space() {
+        let oklch = Color::new(Space::Oklch, 0.6, 0.15, 140.0, 1.0);
+        assert_component_eq!(oklch.chroma(), 0.15);
+        assert_component_eq!(oklch.hue().unwrap(), 140.0);
+
+        let srgb = oklch.to_space(Space::Srgb);
+        assert_component_eq!(srgb.chroma(), 0.15);
+        assert_component_eq!(srgb.hue().unwrap(), 140.0);
+
+        let lab = oklch.to_space(Space::Lab);
+        assert_component_eq!(lab.chroma(), 0.15);
+        assert_component_eq!(lab.hue().unwrap(), 140.0);
+    }
+
+    #[test]
+    fn correlated_color_temperature() {
+        // The D65 white point is 6504K.
+        let white = Color::new(Space::Srgb, 1.0, 1.0, 1.0, 1.0);
+        assert!((white.cct().unwrap() - 6504.0).abs() < 10.0);
+
+        // Blackbody colors round trip, regardless of their luminance.
+        for kelvin in [2700.0, 4000.0, 5000.0, 9000.0] {
+            let xyz = XyzD65::from_blackbody(kelvin);
+            let color = Color::new(Space::XyzD65, xyz.x * 0.5, xyz.y * 0.5, xyz.z * 0.5, 1.0);
+            let cct = color.cct().unwrap();
+            assert!(
+                (cct - kelvin).abs() / kelvin < 0.01,
+                "{} != {}",
+                cct,
+                kelvin
+            );
+        }
+
+        // Saturated colors and black have no meaningful temperature.
+        assert_eq!(Color::new(Space::Srgb, 0.0, 1.0, 0.0, 1.0).cct(), None);
+        assert_eq!(Color::new(Space::Srgb, 1.0, 0.0, 1.0, 1.0).cct(), None);
+        assert_eq!(Color::new(Space::Srgb, 0.0, 0.0, 0.0, 1.0).cct(), None);
+    }
+
+    #[test]
+    fn lightness_references() {
+        let white = Color::new(Space::Srgb, 1.0, 1.0, 1.0, 1.0);
+        assert_component_eq!(white.lightness(LightnessRef::Cielab), 100.0);
+        assert_component_eq!(white.lightness(LightnessRef::Oklab), 1.0);
+        assert_component_eq!(white.lightness(LightnessRef::Hsl), 1.0);
+
+        // Pure yellow and blue have the same HSL lightness, but are very
+        // different perceptually.
+        let yellow = Color::new(Space::Srgb, 1.0, 1.0, 0.0, 1.0);
+        let blue = Color::new(Space::Srgb, 0.0, 0.0, 1.0, 1.0);
+        assert_component_eq!(yellow.lightness(LightnessRef::Hsl), 0.5);
+        assert_component_eq!(blue.lightness(LightnessRef::Hsl), 0.5);
+        assert_component_eq!(yellow.lightness(LightnessRef::Cielab), 97.607);
+        assert_component_eq!(blue.lightness(LightnessRef::Cielab), 29.568);
+        assert_component_eq!(yellow.lightness(LightnessRef::Oklab), 0.968);
+        assert_component_eq!(blue.lightness(LightnessRef::Oklab), 0.452);
+    }
+
+    #[test]
+    fn achromatic_colors_have_no_hue() {
+        let gray = Color::new(Space::Srgb, 0.5, 0.5, 0.5, 1.0);
+        assert_component_eq!(gray.chroma(), 0.0);
+        assert_eq!(gray.hue(), None);
+
+        let missing = Color::new(Space::Oklch, 0.5, 0.1, None, 1.0);
+        assert_eq!(missing.hue(), None);
+    }
+}
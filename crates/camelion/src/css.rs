@@ -0,0 +1,159 @@
+//! Serialization of colors to CSS color values.
+//! <https://drafts.csswg.org/css-color-4/#serializing-color-values>
+
+use crate::{Color, Component, Space};
+
+/// Format a number with 6 decimal places, or fewer for values of 1 or more
+/// so that at most 6 digits are written (more if the integer part needs
+/// them), without trailing zeros. E.g. 123.456789 is written as `123.457` and
+/// 0.0001234567 as `0.000123`. Infinite and NaN values can not be
+/// written as plain numbers, so they are written as the `calc()` constants.
+/// <https://drafts.csswg.org/css-values-4/#calc-error-constants>
+fn format_number(value: Component) -> String {
+    if value.is_nan() {
+        return "calc(NaN)".to_string();
+    }
+    if value.is_infinite() {
+        let sign = if value < 0.0 { "-" } else { "" };
+        return format!("calc({}infinity)", sign);
+    }
+
+    let integer_digits = if value.abs() >= 1.0 {
+        value.abs().log10().floor() as usize + 1
+    } else {
+        0
+    };
+    let formatted = format!("{:.*}", 6usize.saturating_sub(integer_digits), value);
+    let trimmed = if formatted.contains('.') {
+        formatted.trim_end_matches('0').trim_end_matches('.')
+    } else {
+        &formatted
+    };
+
+    // Avoid serializing tiny negative values as "-0".
+    if trimmed == "-0" {
+        "0".to_string()
+    } else {
+        trimmed.to_string()
+    }
+}
+
+/// Format a component, or `none` if it is missing. The value is multiplied
+/// by `scale` first.
+fn format_component(value: Option<Component>, scale: Component, suffix: &str) -> String {
+    match value {
+        Some(value) => format!("{}{}", format_number(value * scale), suffix),
+        None => "none".to_string(),
+    }
+}
+
+impl Color {
+    /// Serialize this color to a CSS color value, using the function that
+    /// matches its color space, e.g. `color(display-p3 1 0 0)`, `lab(50 10 20)`
+    /// or `hsl(120 50% 50% / 0.5)`. Missing components serialize as `none`
+    /// and alpha is omitted when it is 1. Hues are in degrees, without a
+    /// unit.
+    ///
+    /// NOTE: Color spaces that are not part of CSS are converted first:
+    ///       [`Space::Hsv`] to `color(srgb r g b)` and the others (e.g.
+    ///       [`Space::Hct`] or [`Space::Jzazbz`]) to `color(xyz-d65 x y z)`.
+    pub fn to_css_string(&self) -> String {
+        let (prefix, scales, suffixes) = match self.space {
+            Space::Srgb => ("color(srgb ", [1.0; 3], ["", "", ""]),
+            Space::SrgbLinear => ("color(srgb-linear ", [1.0; 3], ["", "", ""]),
+            Space::DisplayP3 => ("color(display-p3 ", [1.0; 3], ["", "", ""]),
+            Space::A98Rgb => ("color(a98-rgb ", [1.0; 3], ["", "", ""]),
+            Space::ProPhotoRgb => ("color(prophoto-rgb ", [1.0; 3], ["", "", ""]),
+            Space::Rec2020 => ("color(rec2020 ", [1.0; 3], ["", "", ""]),
+            Space::XyzD50 => ("color(xyz-d50 ", [1.0; 3], ["", "", ""]),
+            Space::XyzD65 => ("color(xyz-d65 ", [1.0; 3], ["", "", ""]),
+            Space::Hsl => ("hsl(", [1.0, 100.0, 100.0], ["", "%", "%"]),
+            Space::Hwb => ("hwb(", [1.0, 100.0, 100.0], ["", "%", "%"]),
+            Space::Lab => ("lab(", [1.0; 3], ["", "", ""]),
+            Space::Lch => ("lch(", [1.0; 3], ["", "", ""]),
+            Space::Oklab => ("oklab(", [1.0; 3], ["", "", ""]),
+            Space::Oklch => ("oklch(", [1.0; 3], ["", "", ""]),
+            Space::Hsv => return self.to_space(Space::Srgb).to_css_string(),
+            Space::Hct
+            | Space::LabD65
+            | Space::LchD65
+            | Space::Jzazbz
+            | Space::Jzczhz
+            | Space::Ipt
+            | Space::ICtCp => return self.to_space(Space::XyzD65).to_css_string(),
+        };
+
+        let components = [self.c0(), self.c1(), self.c2()]
+            .into_iter()
+            .zip(scales)
+            .zip(suffixes)
+            .map(|((value, scale), suffix)| format_component(value, scale, suffix))
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        let alpha = match self.alpha() {
+            Some(1.0) => String::new(),
+            alpha => format!(" / {}", format_component(alpha, 1.0, "")),
+        };
+
+        format!("{}{}{})", prefix, components, alpha)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serialize_functions() {
+        let srgb = Color::new(Space::Srgb, 1.0, 0.5, 0.0, 1.0);
+        assert_eq!(srgb.to_css_string(), "color(srgb 1 0.5 0)");
+
+        let p3 = Color::new(Space::DisplayP3, 0.25, 0.5, 0.75, 0.5);
+        assert_eq!(p3.to_css_string(), "color(display-p3 0.25 0.5 0.75 / 0.5)");
+
+        let lab = Color::new(Space::Lab, 50.0, -10.0, 20.5, 1.0);
+        assert_eq!(lab.to_css_string(), "lab(50 -10 20.5)");
+
+        let oklch = Color::new(Space::Oklch, 0.7, 0.1, 120.0, 1.0);
+        assert_eq!(oklch.to_css_string(), "oklch(0.7 0.1 120)");
+
+        let hsl = Color::new(Space::Hsl, 120.0, 0.3, 0.4, 0.25);
+        assert_eq!(hsl.to_css_string(), "hsl(120 30% 40% / 0.25)");
+
+        let hwb = Color::new(Space::Hwb, 200.0, 0.1, 0.2, 1.0);
+        assert_eq!(hwb.to_css_string(), "hwb(200 10% 20%)");
+    }
+
+    #[test]
+    fn serialize_missing_components() {
+        let lch = Color::new(Space::Lch, 50.0, 0.0, None, None);
+        assert_eq!(lch.to_css_string(), "lch(50 0 none / none)");
+
+        let hsl = Color::new(Space::Hsl, None, 0.0, 0.5, 1.0);
+        assert_eq!(hsl.to_css_string(), "hsl(none 0% 50%)");
+    }
+
+    #[test]
+    fn serialize_non_css_spaces() {
+        let hct = Color::new(Space::Hct, 120.0, 40.0, 50.0, 1.0);
+        assert!(hct.to_css_string().starts_with("color(xyz-d65 "));
+
+        let hsv = Color::new(Space::Hsv, 0.0, 1.0, 1.0, 1.0);
+        assert_eq!(hsv.to_css_string(), "color(srgb 1 0 0)");
+        assert_eq!(format_number(-0.0000001), "0");
+        assert_eq!(format_number(123.456789), "123.457");
+        assert_eq!(format_number(0.0001234567), "0.000123");
+    }
+
+    #[test]
+    fn serialize_non_finite_numbers() {
+        assert_eq!(format_number(Component::INFINITY), "calc(infinity)");
+        assert_eq!(format_number(Component::NEG_INFINITY), "calc(-infinity)");
+        assert_eq!(format_number(Component::NAN), "calc(NaN)");
+        assert_eq!(format_number(1000000.0), "1000000");
+
+        let xyz = Color::new(Space::XyzD65, Component::INFINITY, 0.5, 0.0, 1.0);
+        assert_eq!(xyz.to_css_string(), "color(xyz-d65 calc(infinity) 0.5 0)");
+    }
+}
@@ -0,0 +1,351 @@
+//! Color difference (deltaE) functions.
+//! <https://drafts.csswg.org/css-color-4/#color-difference>
+
+use crate::{Color, Component, Components, Space};
+
+/// The metric used to calculate the difference between two colors.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum DeltaMetric {
+    /// deltaE OK, the euclidean distance in the oklab color space.
+    /// <https://drafts.csswg.org/css-color-4/#color-difference-OK>
+    #[default]
+    Ok,
+    /// deltaE 1976, the euclidean distance in the CIE-Lab color space.
+    /// <https://drafts.csswg.org/css-color-4/#color-difference-76>
+    Cie76,
+    /// deltaE 2000 (CIEDE2000), in the CIE-Lab color space, with all the
+    /// parametric factors set to 1. See [`Color::delta_e_2000`].
+    Cie2000,
+}
+
+/// Calculate the euclidean distance between two colors after converting them
+/// to the given color space.
+fn euclidean(reference: &Color, sample: &Color, space: Space) -> Component {
+    let reference = reference.to_space(space);
+    let sample = sample.to_space(space);
+
+    let d = sample.components - reference.components;
+    (d.0 * d.0 + d.1 * d.1 + d.2 * d.2).sqrt()
+}
+
+/// Calculate deltaE OK (simple root sum of squares).
+/// <https://drafts.csswg.org/css-color-4/#color-difference-OK>
+pub(crate) fn delta_eok(reference: &Color, sample: &Color) -> Component {
+    // Delta is calculated in the oklab color space.
+    euclidean(reference, sample, Space::Oklab)
+}
+
+impl Color {
+    /// Calculate the difference between this color and `other` using the
+    /// specified metric.
+    pub fn delta_e(&self, other: &Color, metric: DeltaMetric) -> Component {
+        match metric {
+            DeltaMetric::Ok => delta_eok(self, other),
+            DeltaMetric::Cie76 => euclidean(self, other, Space::Lab),
+            DeltaMetric::Cie2000 => self.delta_e_2000(other),
+        }
+    }
+
+    /// Calculate deltaE 2000 (CIEDE2000) between this color and `other`.
+    /// Both colors are converted to the CIE-Lab color space first.
+    /// <https://doi.org/10.1002/col.20070>
+    pub fn delta_e_2000(&self, other: &Color) -> Component {
+        self.delta_e_2000_with(other, 1.0, 1.0, 1.0)
+    }
+
+    /// Same as [`Color::delta_e_2000`], but with the parametric weighting
+    /// factors for lightness (`kl`), chroma (`kc`) and hue (`kh`). The
+    /// factors are 1 for reference conditions. Some industries use other
+    /// values, e.g. a `kl` of 2 for textiles.
+    pub fn delta_e_2000_with(
+        &self,
+        other: &Color,
+        kl: Component,
+        kc: Component,
+        kh: Component,
+    ) -> Component {
+        const POW_25_7: Component = 6103515625.0;
+
+        let Components(l1, a1, b1) = self.to_space(Space::Lab).components;
+        let Components(l2, a2, b2) = other.to_space(Space::Lab).components;
+
+        // Adjust the a components to improve the performance for neutral
+        // colors.
+        let c_bar = ((a1 * a1 + b1 * b1).sqrt() + (a2 * a2 + b2 * b2).sqrt()) / 2.0;
+        let c_bar_7 = c_bar.powi(7);
+        let g = 0.5 * (1.0 - (c_bar_7 / (c_bar_7 + POW_25_7)).sqrt());
+
+        let a1 = (1.0 + g) * a1;
+        let a2 = (1.0 + g) * a2;
+        let c1 = (a1 * a1 + b1 * b1).sqrt();
+        let c2 = (a2 * a2 + b2 * b2).sqrt();
+
+        let hue = |a: Component, b: Component| {
+            if a == 0.0 && b == 0.0 {
+                0.0
+            } else {
+                b.atan2(a).to_degrees().rem_euclid(360.0)
+            }
+        };
+        let h1 = hue(a1, b1);
+        let h2 = hue(a2, b2);
+        let achromatic = c1 * c2 == 0.0;
+
+        // The differences in lightness, chroma and hue.
+        let delta_l = l2 - l1;
+        let delta_c = c2 - c1;
+        let delta_h = if achromatic {
+            0.0
+        } else if (h2 - h1).abs() <= 180.0 {
+            h2 - h1
+        } else if h2 - h1 > 180.0 {
+            h2 - h1 - 360.0
+        } else {
+            h2 - h1 + 360.0
+        };
+        let delta_h = 2.0 * (c1 * c2).sqrt() * (delta_h / 2.0).to_radians().sin();
+
+        // The means of lightness, chroma and hue.
+        let l_mean = (l1 + l2) / 2.0;
+        let c_mean = (c1 + c2) / 2.0;
+        let h_mean = if achromatic {
+            h1 + h2
+        } else if (h1 - h2).abs() <= 180.0 {
+            (h1 + h2) / 2.0
+        } else if h1 + h2 < 360.0 {
+            (h1 + h2 + 360.0) / 2.0
+        } else {
+            (h1 + h2 - 360.0) / 2.0
+        };
+
+        // The weighting functions.
+        let cos = |degrees: Component| degrees.to_radians().cos();
+        let t = 1.0 - 0.17 * cos(h_mean - 30.0)
+            + 0.24 * cos(2.0 * h_mean)
+            + 0.32 * cos(3.0 * h_mean + 6.0)
+            - 0.20 * cos(4.0 * h_mean - 63.0);
+        let l_50 = (l_mean - 50.0) * (l_mean - 50.0);
+        let s_l = 1.0 + 0.015 * l_50 / (20.0 + l_50).sqrt();
+        let s_c = 1.0 + 0.045 * c_mean;
+        let s_h = 1.0 + 0.015 * c_mean * t;
+
+        // The rotation term for the blue region.
+        let delta_theta = 30.0 * (-((h_mean - 275.0) / 25.0).powi(2)).exp();
+        let c_mean_7 = c_mean.powi(7);
+        let r_c = 2.0 * (c_mean_7 / (c_mean_7 + POW_25_7)).sqrt();
+        let r_t = -(2.0 * delta_theta).to_radians().sin() * r_c;
+
+        let l = delta_l / (kl * s_l);
+        let c = delta_c / (kc * s_c);
+        let h = delta_h / (kh * s_h);
+
+        (l * l + c * c + h * h + r_t * c * h).sqrt()
+    }
+
+    /// Calculate the euclidean distance (root sum of squares) between this
+    /// color and `other` after converting both to the specified color space.
+    /// Hue components are compared along the shorter arc between them and a
+    /// component that is missing on either side does not add to the distance.
+    ///
+    /// NOTE: The result depends on the chosen color space and the ranges of
+    ///       its components, e.g. hues are in degrees while the other
+    ///       components of [`Space::Oklch`] are much smaller. Use
+    ///       [`Color::delta_e`] for a perceptual difference.
+    pub fn distance(&self, other: &Color, space: Space) -> Component {
+        let left = self.to_space(space);
+        let right = other.to_space(space);
+        let hue_index = space.hue_index();
+
+        [
+            (left.c0(), right.c0()),
+            (left.c1(), right.c1()),
+            (left.c2(), right.c2()),
+        ]
+        .into_iter()
+        .enumerate()
+        .map(|(index, pair)| match pair {
+            (Some(left), Some(right)) if hue_index == Some(index) => {
+                (right - left + 180.0).rem_euclid(360.0) - 180.0
+            }
+            (Some(left), Some(right)) => right - left,
+            _ => 0.0,
+        })
+        .map(|d| d * d)
+        .sum::<Component>()
+        .sqrt()
+    }
+}
+
+/// Statistics about the differences between two sets of colors.
+#[derive(Clone, Debug)]
+pub struct DiffStats {
+    /// The largest difference found.
+    pub max: Component,
+    /// The average difference.
+    pub mean: Component,
+    /// All the differences, sorted in ascending order.
+    deltas: Vec<Component>,
+}
+
+impl DiffStats {
+    /// Return the difference at the given percentile in the range [0..100],
+    /// using the nearest-rank method.
+    pub fn percentile(&self, percentile: Component) -> Component {
+        if self.deltas.is_empty() {
+            return 0.0;
+        }
+
+        let rank = (percentile.clamp(0.0, 100.0) / 100.0 * self.deltas.len() as Component).ceil();
+        self.deltas[(rank as usize).saturating_sub(1)]
+    }
+}
+
+/// Calculate statistics about the differences between each pair of colors in
+/// `a` and `b` using the specified metric.
+///
+/// Panics if `a` and `b` have different lengths.
+pub fn color_diff_stats(a: &[Color], b: &[Color], metric: DeltaMetric) -> DiffStats {
+    assert_eq!(a.len(), b.len(), "color slices must have equal lengths");
+
+    let mut deltas = a
+        .iter()
+        .zip(b)
+        .map(|(a, b)| a.delta_e(b, metric))
+        .collect::<Vec<_>>();
+    deltas.sort_by(Component::total_cmp);
+
+    let max = deltas.last().copied().unwrap_or(0.0);
+    let mean = if deltas.is_empty() {
+        0.0
+    } else {
+        deltas.iter().sum::<Component>() / deltas.len() as Component
+    };
+
+    DiffStats { max, mean, deltas }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_component_eq;
+
+    #[test]
+    fn delta_e_metrics() {
+        let white = Color::new(Space::Srgb, 1.0, 1.0, 1.0, 1.0);
+        let black = Color::new(Space::Srgb, 0.0, 0.0, 0.0, 1.0);
+
+        assert_component_eq!(white.delta_e(&black, DeltaMetric::Ok), 1.0);
+        assert_component_eq!(white.delta_e(&black, DeltaMetric::Cie76) / 100.0, 1.0);
+        assert_eq!(white.delta_e(&white, DeltaMetric::Ok), 0.0);
+    }
+
+    #[test]
+    fn delta_e_2000_sharma_test_data() {
+        // All 34 pairs of test data from "The CIEDE2000 Color-Difference
+        // Formula: Implementation Notes, Supplementary Test Data, and
+        // Mathematical Observations" by G. Sharma, W. Wu and E. N. Dalal.
+        #[rustfmt::skip]
+        #[allow(clippy::type_complexity)]
+        const TESTS: &[((Component, Component, Component), (Component, Component, Component), Component)] = &[
+            ((50.0, 2.6772, -79.7751), (50.0, 0.0, -82.7485), 2.0425),
+            ((50.0, 3.1571, -77.2803), (50.0, 0.0, -82.7485), 2.8615),
+            ((50.0, 2.8361, -74.0200), (50.0, 0.0, -82.7485), 3.4412),
+            ((50.0, -1.3802, -84.2814), (50.0, 0.0, -82.7485), 1.0000),
+            ((50.0, -1.1848, -84.8006), (50.0, 0.0, -82.7485), 1.0000),
+            ((50.0, -0.9009, -85.5211), (50.0, 0.0, -82.7485), 1.0000),
+            ((50.0, 0.0, 0.0), (50.0, -1.0, 2.0), 2.3669),
+            ((50.0, -1.0, 2.0), (50.0, 0.0, 0.0), 2.3669),
+            ((50.0, 2.49, -0.001), (50.0, -2.49, 0.0009), 7.1792),
+            ((50.0, 2.49, -0.001), (50.0, -2.49, 0.0010), 7.1792),
+            ((50.0, 2.49, -0.001), (50.0, -2.49, 0.0011), 7.2195),
+            ((50.0, 2.49, -0.001), (50.0, -2.49, 0.0012), 7.2195),
+            ((50.0, -0.001, 2.49), (50.0, 0.0009, -2.49), 4.8045),
+            ((50.0, -0.001, 2.49), (50.0, 0.0010, -2.49), 4.8045),
+            ((50.0, -0.001, 2.49), (50.0, 0.0011, -2.49), 4.7461),
+            ((50.0, 2.5, 0.0), (50.0, 0.0, -2.5), 4.3065),
+            ((50.0, 2.5, 0.0), (73.0, 25.0, -18.0), 27.1492),
+            ((50.0, 2.5, 0.0), (61.0, -5.0, 29.0), 22.8977),
+            ((50.0, 2.5, 0.0), (56.0, -27.0, -3.0), 31.9030),
+            ((50.0, 2.5, 0.0), (58.0, 24.0, 15.0), 19.4535),
+            ((50.0, 2.5, 0.0), (50.0, 3.1736, 0.5854), 1.0000),
+            ((50.0, 2.5, 0.0), (50.0, 3.2972, 0.0), 1.0000),
+            ((50.0, 2.5, 0.0), (50.0, 1.8634, 0.5757), 1.0000),
+            ((50.0, 2.5, 0.0), (50.0, 3.2592, 0.3350), 1.0000),
+            ((60.2574, -34.0099, 36.2677), (60.4626, -34.1751, 39.4387), 1.2644),
+            ((63.0109, -31.0961, -5.8663), (62.8187, -29.7946, -4.0864), 1.2630),
+            ((61.2901, 3.7196, -5.3901), (61.4292, 2.2480, -4.9620), 1.8731),
+            ((35.0831, -44.1164, 3.7933), (35.0232, -40.0716, 1.5901), 1.8645),
+            ((22.7233, 20.0904, -46.6940), (23.0331, 14.9730, -42.5619), 2.0373),
+            ((36.4612, 47.8580, 18.3852), (36.2715, 50.5065, 21.2231), 1.4146),
+            ((90.8027, -2.0831, 1.4410), (91.1528, -1.6435, 0.0447), 1.4441),
+            ((90.9257, -0.5406, -0.9208), (88.6381, -0.8985, -0.7239), 1.5381),
+            ((6.7747, -0.2908, -2.4247), (5.8714, -0.0985, -2.2286), 0.6377),
+            ((2.0776, 0.0795, -1.1350), (0.9033, -0.0636, -0.5514), 0.9082),
+        ];
+
+        for &((l1, a1, b1), (l2, a2, b2), expected) in TESTS {
+            let left = Color::new(Space::Lab, l1, a1, b1, 1.0);
+            let right = Color::new(Space::Lab, l2, a2, b2, 1.0);
+            let delta = left.delta_e_2000(&right);
+            assert_component_eq!(delta, expected, epsilon = 1.0e-4);
+            assert_eq!(left.delta_e(&right, DeltaMetric::Cie2000), delta);
+        }
+    }
+
+    #[test]
+    fn delta_e_2000_parametric_factors() {
+        let left = Color::new(Space::Lab, 50.0, 10.0, 10.0, 1.0);
+        let right = Color::new(Space::Lab, 60.0, 10.0, 10.0, 1.0);
+
+        // Only the lightness differs, so kL scales the difference.
+        let reference = left.delta_e_2000(&right);
+        assert_eq!(left.delta_e_2000_with(&right, 1.0, 1.0, 1.0), reference);
+        assert_component_eq!(
+            left.delta_e_2000_with(&right, 2.0, 1.0, 1.0),
+            reference / 2.0
+        );
+        assert_eq!(left.delta_e_2000_with(&right, 1.0, 3.0, 3.0), reference);
+    }
+
+    #[test]
+    fn distance_in_space() {
+        let white = Color::new(Space::Srgb, 1.0, 1.0, 1.0, 1.0);
+        let black = Color::new(Space::Srgb, 0.0, 0.0, 0.0, 1.0);
+        assert_component_eq!(
+            white.distance(&black, Space::Srgb),
+            (3.0 as Component).sqrt()
+        );
+        assert_component_eq!(
+            white.distance(&black, Space::Oklab),
+            white.delta_e(&black, DeltaMetric::Ok)
+        );
+
+        // Hues are compared along the shorter arc.
+        let left = Color::new(Space::Oklch, 0.5, 0.1, 350.0, 1.0);
+        let right = Color::new(Space::Oklch, 0.5, 0.1, 10.0, 1.0);
+        assert_component_eq!(left.distance(&right, Space::Oklch), 20.0);
+
+        // Missing components do not add to the distance.
+        let missing = Color::new(Space::Oklch, 0.5, 0.1, None, 1.0);
+        assert_component_eq!(left.distance(&missing, Space::Oklch), 0.0);
+    }
+
+    #[test]
+    fn diff_stats() {
+        let gray = |v| Color::new(Space::Oklab, v, 0.0, 0.0, 1.0);
+
+        let a = [gray(0.1), gray(0.2), gray(0.3), gray(0.4)];
+        let b = [gray(0.1), gray(0.3), gray(0.5), gray(0.8)];
+
+        let stats = color_diff_stats(&a, &b, DeltaMetric::Ok);
+        assert_component_eq!(stats.max, 0.4);
+        assert_component_eq!(stats.mean, 0.175);
+        assert_component_eq!(stats.percentile(50.0), 0.1);
+        assert_component_eq!(stats.percentile(75.0), 0.2);
+        assert_component_eq!(stats.percentile(100.0), 0.4);
+
+        let empty = color_diff_stats(&[], &[], DeltaMetric::Ok);
+        assert_eq!(empty.max, 0.0);
+        assert_eq!(empty.percentile(95.0), 0.0);
+    }
+}
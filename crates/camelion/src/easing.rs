@@ -0,0 +1,243 @@
+//! Easing functions, used to change the pace of an interpolation.
+//! <https://drafts.csswg.org/css-easing-1/>
+
+use crate::{interpolate::Interpolation, Color, Component};
+
+/// An easing function, matching the CSS `<easing-function>` values.
+/// <https://drafts.csswg.org/css-easing-1/#easing-functions>
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Easing {
+    /// Progress at a constant rate, the same as `linear` in CSS.
+    #[default]
+    Linear,
+    /// Start slowly and speed up, the same as `ease-in` in CSS.
+    EaseIn,
+    /// Start quickly and slow down, the same as `ease-out` in CSS.
+    EaseOut,
+    /// Start slowly, speed up and then slow down again, the same as
+    /// `ease-in-out` in CSS.
+    EaseInOut,
+    /// A cubic Bézier curve from (0, 0) to (1, 1) with the control points
+    /// (x1, y1) and (x2, y2), the same as `cubic-bezier(x1, y1, x2, y2)` in
+    /// CSS.
+    ///
+    /// NOTE: The x coordinates must be in [0..1] for the curve to be a
+    ///       function of x, so they are clamped to that range.
+    CubicBezier(Component, Component, Component, Component),
+}
+
+impl Easing {
+    /// Return the eased progress for the input progress `t`. The result is
+    /// 0 at 0 and 1 at 1, but might fall outside [0..1] in between, e.g. for
+    /// curves that overshoot. Values of `t` outside [0..1] are extrapolated
+    /// along the tangents at the ends of the curve.
+    pub fn ease(&self, t: Component) -> Component {
+        match *self {
+            Easing::Linear => t,
+            Easing::EaseIn => CubicBezier::new(0.42, 0.0, 1.0, 1.0).solve(t),
+            Easing::EaseOut => CubicBezier::new(0.0, 0.0, 0.58, 1.0).solve(t),
+            Easing::EaseInOut => CubicBezier::new(0.42, 0.0, 0.58, 1.0).solve(t),
+            Easing::CubicBezier(x1, y1, x2, y2) => CubicBezier::new(x1, y1, x2, y2).solve(t),
+        }
+    }
+}
+
+/// A cubic Bézier curve from (0, 0) to (1, 1), evaluated like browsers do.
+/// The curve is stored in polynomial form, e.g. x(t) = ((ax * t + bx) * t +
+/// cx) * t.
+struct CubicBezier {
+    ax: Component,
+    bx: Component,
+    cx: Component,
+    ay: Component,
+    by: Component,
+    cy: Component,
+    /// The slope of the curve at (0, 0), used to extrapolate before 0.
+    start_gradient: Component,
+    /// The slope of the curve at (1, 1), used to extrapolate after 1.
+    end_gradient: Component,
+}
+
+impl CubicBezier {
+    fn new(x1: Component, y1: Component, x2: Component, y2: Component) -> Self {
+        let x1 = x1.clamp(0.0, 1.0);
+        let x2 = x2.clamp(0.0, 1.0);
+
+        let cx = 3.0 * x1;
+        let bx = 3.0 * (x2 - x1) - cx;
+        let cy = 3.0 * y1;
+        let by = 3.0 * (y2 - y1) - cy;
+
+        // If a control point is on an end point, the slope is taken from the
+        // other control point.
+        let start_gradient = if x1 > 0.0 {
+            y1 / x1
+        } else if y1 == 0.0 && x2 > 0.0 {
+            y2 / x2
+        } else if y1 == 0.0 && y2 == 0.0 {
+            1.0
+        } else {
+            0.0
+        };
+        let end_gradient = if x2 < 1.0 {
+            (y2 - 1.0) / (x2 - 1.0)
+        } else if y2 == 1.0 && x1 < 1.0 {
+            (y1 - 1.0) / (x1 - 1.0)
+        } else if y2 == 1.0 && y1 == 1.0 {
+            1.0
+        } else {
+            0.0
+        };
+
+        Self {
+            ax: 1.0 - cx - bx,
+            bx,
+            cx,
+            ay: 1.0 - cy - by,
+            by,
+            cy,
+            start_gradient,
+            end_gradient,
+        }
+    }
+
+    fn sample_x(&self, t: Component) -> Component {
+        ((self.ax * t + self.bx) * t + self.cx) * t
+    }
+
+    fn sample_y(&self, t: Component) -> Component {
+        ((self.ay * t + self.by) * t + self.cy) * t
+    }
+
+    fn sample_x_derivative(&self, t: Component) -> Component {
+        (3.0 * self.ax * t + 2.0 * self.bx) * t + self.cx
+    }
+
+    /// Find the curve parameter for `x`, using Newton's method and falling
+    /// back to a binary search if that does not converge.
+    fn solve_x(&self, x: Component) -> Component {
+        const EPSILON: Component = 1.0e-6;
+        const NEWTON_ITERATIONS: usize = 8;
+        const BISECTION_ITERATIONS: usize = 64;
+
+        let mut t = x;
+        for _ in 0..NEWTON_ITERATIONS {
+            let error = self.sample_x(t) - x;
+            if error.abs() < EPSILON {
+                return t;
+            }
+            let derivative = self.sample_x_derivative(t);
+            if derivative.abs() < EPSILON {
+                break;
+            }
+            t -= error / derivative;
+        }
+
+        let (mut low, mut high) = (0.0, 1.0);
+        let mut t = x;
+        for _ in 0..BISECTION_ITERATIONS {
+            let sample = self.sample_x(t);
+            if (sample - x).abs() < EPSILON {
+                break;
+            }
+            if x > sample {
+                low = t;
+            } else {
+                high = t;
+            }
+            t = (low + high) / 2.0;
+        }
+
+        t
+    }
+
+    /// Return the y coordinate of the curve at `x`.
+    fn solve(&self, x: Component) -> Component {
+        if x < 0.0 {
+            return self.start_gradient * x;
+        }
+        if x > 1.0 {
+            return 1.0 + self.end_gradient * (x - 1.0);
+        }
+
+        self.sample_y(self.solve_x(x))
+    }
+}
+
+impl Interpolation {
+    /// Calculate an interpolated color at `t`, after changing the pace of
+    /// the interpolation with `easing`. The eased value is used like `t` in
+    /// [`Interpolation::at`], so the midpoint, premultiplication and hue
+    /// interpolation are applied as usual.
+    pub fn at_eased(&self, t: Component, easing: Easing) -> Color {
+        self.at(easing.ease(t))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{assert_component_eq, Space};
+
+    #[test]
+    fn easing_end_points() {
+        for easing in [
+            Easing::Linear,
+            Easing::EaseIn,
+            Easing::EaseOut,
+            Easing::EaseInOut,
+            Easing::CubicBezier(0.25, 0.1, 0.25, 1.0),
+            Easing::CubicBezier(0.5, -0.5, 0.5, 1.5),
+        ] {
+            assert_component_eq!(easing.ease(0.0), 0.0);
+            assert_component_eq!(easing.ease(1.0), 1.0);
+        }
+    }
+
+    #[test]
+    fn cubic_bezier() {
+        // Symmetric curves pass through the center.
+        assert_component_eq!(Easing::EaseInOut.ease(0.5), 0.5);
+
+        // ease-in is slow at the start, ease-out is fast at the start.
+        assert!(Easing::EaseIn.ease(0.25) < 0.25);
+        assert!(Easing::EaseOut.ease(0.25) > 0.25);
+        assert_component_eq!(Easing::EaseIn.ease(0.25), 1.0 - Easing::EaseOut.ease(0.75));
+
+        // A bezier curve with control points on the diagonal is linear.
+        let linear = Easing::CubicBezier(0.25, 0.25, 0.75, 0.75);
+        for t in [0.1, 0.3, 0.6, 0.9] {
+            assert!((linear.ease(t) - t).abs() < 1.0e-5);
+        }
+
+        // The `ease` keyword in CSS, compared to the exact curve.
+        let ease = Easing::CubicBezier(0.25, 0.1, 0.25, 1.0);
+        assert!((ease.ease(0.25) - 0.4085).abs() < 1.0e-3);
+        assert!((ease.ease(0.5) - 0.8024).abs() < 1.0e-3);
+
+        // Curves can overshoot and are extrapolated outside [0..1].
+        let overshoot = Easing::CubicBezier(0.5, -0.5, 0.5, 1.5);
+        assert!(overshoot.ease(0.1) < 0.0);
+        assert!(overshoot.ease(0.9) > 1.0);
+        assert_component_eq!(Easing::EaseInOut.ease(-1.0), 0.0);
+        assert_component_eq!(Easing::EaseInOut.ease(2.0), 1.0);
+        assert_component_eq!(Easing::Linear.ease(2.0), 2.0);
+        assert_component_eq!(Easing::CubicBezier(0.0, 0.0, 1.0, 1.0).ease(-1.0), -1.0);
+    }
+
+    #[test]
+    fn interpolate_eased() {
+        let black = Color::new(Space::Srgb, 0.0, 0.0, 0.0, 1.0);
+        let white = Color::new(Space::Srgb, 1.0, 1.0, 1.0, 1.0);
+        let interpolation = black.interpolate(&white, Space::Srgb);
+
+        assert_eq!(
+            interpolation.at_eased(0.3, Easing::Linear),
+            interpolation.at(0.3)
+        );
+
+        let eased = interpolation.at_eased(0.25, Easing::EaseIn);
+        assert_component_eq!(eased.components.0, Easing::EaseIn.ease(0.25));
+        assert!(eased.components.0 < interpolation.at(0.25).components.0);
+    }
+}
@@ -0,0 +1,99 @@
+//! A compact, fixed size binary encoding of colors, suitable for storing large
+//! amounts of colors.
+
+use crate::{Color, Component, Components, Flags, Space};
+
+const COMPONENT_SIZE: usize = std::mem::size_of::<Component>();
+
+impl Color {
+    /// The number of bytes in the binary encoding of a color. See
+    /// [`Color::to_bytes`].
+    pub const ENCODED_SIZE: usize = 2 + 4 * COMPONENT_SIZE;
+
+    /// Encode this color into a fixed size binary layout: the color space id
+    /// (see [`Space::as_u8`]), the flags, followed by the three color
+    /// components and alpha as little-endian floats. The bits of each float
+    /// are preserved exactly and missing components are encoded in the flags.
+    ///
+    /// NOTE: The floats are stored as [`Component`]s, so the size of the
+    ///       encoding depends on the `f64` feature.
+    pub fn to_bytes(&self) -> [u8; Color::ENCODED_SIZE] {
+        let mut bytes = [0; Color::ENCODED_SIZE];
+        bytes[0] = self.space.as_u8();
+        bytes[1] = self.flags.bits();
+
+        let Components(c0, c1, c2) = self.components;
+        for (i, value) in [c0, c1, c2, self.alpha].into_iter().enumerate() {
+            let start = 2 + i * COMPONENT_SIZE;
+            bytes[start..start + COMPONENT_SIZE].copy_from_slice(&value.to_le_bytes());
+        }
+
+        bytes
+    }
+
+    /// Decode a color previously encoded with [`Color::to_bytes`]. Returns
+    /// None if the color space id or the flags are not valid.
+    pub fn from_bytes(bytes: &[u8; Color::ENCODED_SIZE]) -> Option<Color> {
+        let space = Space::from_u8(bytes[0])?;
+        let flags = Flags::from_bits(bytes[1])?;
+
+        let value = |i: usize| {
+            let start = 2 + i * COMPONENT_SIZE;
+            let mut value = [0; COMPONENT_SIZE];
+            value.copy_from_slice(&bytes[start..start + COMPONENT_SIZE]);
+            Component::from_le_bytes(value)
+        };
+
+        Some(Color {
+            components: Components(value(0), value(1), value(2)),
+            alpha: value(3),
+            flags,
+            space,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bytes_round_trip() {
+        let color = Color::new(Space::Oklch, 0.62, 0.1234567, 321.5, 0.25);
+        let bytes = color.to_bytes();
+        assert_eq!(bytes.len(), 2 + 4 * COMPONENT_SIZE);
+        assert_eq!(bytes[0], Space::Oklch.as_u8());
+        assert_eq!(bytes[1], 0);
+
+        let back = Color::from_bytes(&bytes).unwrap();
+        assert_eq!(back.space, Space::Oklch);
+        assert_eq!(back.flags, Flags::empty());
+        assert_eq!(
+            back.components.to_array().map(Component::to_bits),
+            [0.62, 0.1234567, 321.5].map(Component::to_bits)
+        );
+        assert_eq!(back.alpha.to_bits(), (0.25 as Component).to_bits());
+    }
+
+    #[test]
+    fn bytes_keep_missing_components() {
+        let color = Color::new(Space::Hsl, None, 0.5, 0.25, None);
+        let back = Color::from_bytes(&color.to_bytes()).unwrap();
+
+        assert_eq!(back.flags, Flags::C0_IS_NONE | Flags::ALPHA_IS_NONE);
+        assert_eq!(back.c0(), None);
+        assert_eq!(back.c1(), Some(0.5));
+        assert_eq!(back.alpha(), None);
+    }
+
+    #[test]
+    fn invalid_bytes() {
+        let mut bytes = Color::new(Space::Srgb, 1.0, 0.0, 0.0, 1.0).to_bytes();
+        bytes[0] = 0xFF;
+        assert!(Color::from_bytes(&bytes).is_none());
+
+        let mut bytes = Color::new(Space::Srgb, 1.0, 0.0, 0.0, 1.0).to_bytes();
+        bytes[1] = 0x80;
+        assert!(Color::from_bytes(&bytes).is_none());
+    }
+}
@@ -0,0 +1,106 @@
+//! A single error type for all the fallible operations in the crate.
+
+use crate::{GamutError, OutOfGamut, Space, UnknownHueInterpolationMethod};
+
+/// Any error returned by the crate. The more specific error types of
+/// individual operations can be converted into it with `?`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Error {
+    /// Some input could not be parsed. Holds a description of the problem.
+    Parse(String),
+    /// A color is outside the gamut limits of a color space.
+    OutOfGamut(OutOfGamut),
+    /// A numeric color space id does not belong to any color space. See
+    /// [`Space::from_u8`].
+    UnknownSpace(u8),
+    /// A matrix that had to be inverted is singular.
+    SingularMatrix,
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parse(message) => write!(f, "parse error: {}", message),
+            Error::OutOfGamut(err) => err.fmt(f),
+            Error::UnknownSpace(id) => write!(f, "unknown color space id {}", id),
+            Error::SingularMatrix => write!(f, "matrix is singular"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::OutOfGamut(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<OutOfGamut> for Error {
+    fn from(value: OutOfGamut) -> Self {
+        Error::OutOfGamut(value)
+    }
+}
+
+impl From<GamutError> for Error {
+    fn from(value: GamutError) -> Self {
+        Error::OutOfGamut(OutOfGamut {
+            space: value.color.space,
+            components: value.color.components,
+        })
+    }
+}
+
+impl From<UnknownHueInterpolationMethod> for Error {
+    fn from(value: UnknownHueInterpolationMethod) -> Self {
+        Error::Parse(value.to_string())
+    }
+}
+
+impl TryFrom<u8> for Space {
+    type Error = Error;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        Space::from_u8(value).ok_or(Error::UnknownSpace(value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Color, HueInterpolationMethod};
+
+    #[test]
+    fn convert_into_error() {
+        fn parse(input: &str) -> Result<(), Error> {
+            input.parse::<HueInterpolationMethod>()?;
+            Ok(())
+        }
+        assert_eq!(parse("longer hue"), Ok(()));
+        assert_eq!(
+            parse("sideways hue"),
+            Err(Error::Parse(
+                "unknown hue interpolation method \"sideways hue\"".to_string()
+            ))
+        );
+
+        let red = Color::new(Space::DisplayP3, 1.0, 0.0, 0.0, 1.0);
+        let err = Error::from(red.to_space_checked(Space::Srgb).unwrap_err());
+        match &err {
+            Error::OutOfGamut(OutOfGamut { space, .. }) => assert_eq!(*space, Space::Srgb),
+            _ => panic!("unexpected error {:?}", err),
+        }
+        assert!(std::error::Error::source(&err).is_some());
+    }
+
+    #[test]
+    fn space_from_id() {
+        assert_eq!(Space::try_from(Space::Oklab.as_u8()), Ok(Space::Oklab));
+        assert_eq!(Space::try_from(255), Err(Error::UnknownSpace(255)));
+        assert_eq!(
+            Error::UnknownSpace(255).to_string(),
+            "unknown color space id 255"
+        );
+    }
+}
@@ -1,58 +1,220 @@
 //! Gamut mapping functions.
 //! <https://drafts.csswg.org/css-color-4/#gamut-mapping>
 
-use crate::{Color, Component, Space};
+use crate::{
+    delta_e::delta_eok,
+    models::{
+        A98RgbLinear, Base, DisplayP3Linear, Model, ProPhotoRgbLinear, Rec2020Linear, Srgb,
+        SrgbLinear, ToBase,
+    },
+    Color, Component, Components, Space,
+};
+
+/// Error returned when a color is outside the gamut limits of the color space
+/// it was converted to.
+#[derive(Clone, Debug, PartialEq)]
+pub struct OutOfGamut {
+    /// The color space the color was converted to.
+    pub space: Space,
+    /// The converted components, with at least one outside the gamut limits.
+    pub components: Components,
+}
+
+impl std::fmt::Display for OutOfGamut {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "components {} are outside the gamut limits of {:?}",
+            self.components, self.space
+        )
+    }
+}
+
+impl std::error::Error for OutOfGamut {}
+
+/// Error returned by [`Color::to_space_checked`] when the converted color is
+/// outside the gamut limits of the destination color space.
+#[derive(Clone, Debug)]
+pub struct GamutError {
+    /// The converted color, with components outside the gamut limits.
+    pub color: Color,
+    /// The converted color mapped into the gamut limits with
+    /// [`Color::map_into_gamut_limits`].
+    pub mapped: Color,
+}
+
+impl std::fmt::Display for GamutError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "components {} are outside the gamut limits of {:?}",
+            self.color.components, self.color.space
+        )
+    }
+}
+
+impl std::error::Error for GamutError {}
+
+/// The method used to map a color into the gamut limits of its color space.
+/// See [`Color::map_into_gamut_with`].
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum GamutMethod {
+    /// Clip each of the components to [0..1]. This is fast, but can shift
+    /// the hue and lightness of the color noticeably.
+    Clip,
+    /// Reduce the Oklch chroma with a binary search until the color is in
+    /// gamut, as specified by CSS.
+    /// <https://drafts.csswg.org/css-color-4/#binsearch>
+    #[default]
+    OklchChroma,
+    /// Same as [`GamutMethod::OklchChroma`], but reduce the CIE-Lch chroma
+    /// instead.
+    LchChroma,
+}
 
 #[allow(clippy::manual_range_contains)]
 fn in_zero_to_one(value: Component) -> bool {
     value >= 0.0 && value <= 1.0
 }
 
-/// Calculate deltaE OK (simple root sum of squares).
-/// <https://drafts.csswg.org/css-color-4/#color-difference-OK>
-fn delta_eok(reference: &Color, sample: &Color) -> Component {
-    // Delta is calculated in the oklab color space.
-    let reference = reference.to_space(Space::Oklab);
-    let sample = sample.to_space(Space::Oklab);
+/// Returns whether all the components are inside [0..1], allowing them to
+/// be outside by up to `tolerance` to absorb rounding errors in the
+/// conversions.
+fn in_zero_to_one_within(Components(c0, c1, c2): Components, tolerance: Component) -> bool {
+    let inside = |v: Component| (-tolerance..=1.0 + tolerance).contains(&v);
+    inside(c0) && inside(c1) && inside(c2)
+}
 
-    let d = sample.components - reference.components;
-    (d.0 * d.0 + d.1 * d.1 + d.2 * d.2).sqrt()
+/// The polar color space in which the CSS gamut mapping algorithm reduces
+/// the chroma of a color.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum ChromaReduction {
+    /// Reduce the Oklch chroma, as specified by CSS.
+    Oklch,
+    /// Reduce the CIE-Lch chroma.
+    Lch,
 }
 
+impl ChromaReduction {
+    /// The color space in which the chroma is reduced.
+    fn space(self) -> Space {
+        match self {
+            ChromaReduction::Oklch => Space::Oklch,
+            ChromaReduction::Lch => Space::Lch,
+        }
+    }
+
+    /// The lightness of white in the reduction space, used to scale the
+    /// lightness limits and the chroma precision of the binary search.
+    fn white(self) -> Component {
+        match self {
+            ChromaReduction::Oklch => 1.0,
+            ChromaReduction::Lch => 100.0,
+        }
+    }
+}
+
+/// The JND (just noticeable difference) in deltaE OK of the CSS gamut
+/// mapping algorithm.
+const JND: Component = 0.02;
+
+/// The precision of the CSS gamut mapping algorithm.
+const EPSILON: Component = 1.0e-4;
+
 impl Color {
     /// If this color is not within gamut limits of it's color space, then a
     /// gamut mapping is applied to map the components into range.
     /// <https://drafts.csswg.org/css-color-4/#binsearch>
     pub fn map_into_gamut_limits(&self) -> Self {
+        self.map_into_gamut_with(GamutMethod::OklchChroma)
+    }
+
+    /// If this color is not within gamut limits of it's color space, then
+    /// the gamut mapping `method` is applied to map the components into
+    /// range. Colors in color spaces without gamut limits are returned
+    /// unchanged.
+    ///
+    /// NOTE: `Hsl`, `Hwb` and `Hsv` colors are mapped in [`Space::Srgb`],
+    ///       see [`Color::in_gamut`].
+    pub fn map_into_gamut_with(&self, method: GamutMethod) -> Self {
+        match method {
+            GamutMethod::Clip => match self.space.gamut_space() {
+                Some(gamut) if !self.in_gamut() => self.to_space(gamut).clip().to_space(self.space),
+                _ => self.clone(),
+            },
+            GamutMethod::OklchChroma => self.reduce_chroma_into_gamut(ChromaReduction::Oklch).0,
+            GamutMethod::LchChroma => self.reduce_chroma_into_gamut(ChromaReduction::Lch).0,
+        }
+    }
+
+    /// Same as [`Color::map_into_gamut_limits`], but also returns the deltaE
+    /// OK between this color and the mapped color. Useful for flagging colors
+    /// that shifted a lot during mapping.
+    pub fn map_into_gamut_limits_reported(&self) -> (Self, Component) {
+        let mapped = self.map_into_gamut_limits();
+        let delta = delta_eok(self, &mapped);
+        (mapped, delta)
+    }
+
+    /// Same as [`Color::map_into_gamut_limits`], but also returns the number
+    /// of binary search iterations that were run to find the mapped color.
+    /// Colors that are returned early (already in gamut, no gamut limits or
+    /// close enough to their clipped color) report 0 iterations.
+    pub fn map_into_gamut_limits_with_iterations(&self) -> (Self, u32) {
+        self.reduce_chroma_into_gamut(ChromaReduction::Oklch)
+    }
+
+    /// The CSS gamut mapping algorithm, reducing the chroma in the color
+    /// space of `reduction`. The specification reduces the Oklch chroma.
+    /// Returns the mapped color and the number of binary search iterations.
+    ///
+    /// NOTE: The difference between colors is always measured with deltaE
+    ///       OK, so the JND (just noticeable difference) has the same meaning
+    ///       for both reduction spaces.
+    fn reduce_chroma_into_gamut(&self, reduction: ChromaReduction) -> (Self, u32) {
+        let white = reduction.white();
+
         // 1. if destination has no gamut limits (XYZ-D65, XYZ-D50, Lab, LCH,
         //    Oklab, Oklch) return origin.
         if matches!(
             self.space,
-            Space::Lab | Space::Lch | Space::Oklab | Space::Oklch | Space::XyzD50 | Space::XyzD65
+            Space::Lab
+                | Space::Lch
+                | Space::Oklab
+                | Space::Oklch
+                | Space::XyzD50
+                | Space::XyzD65
+                | Space::Hct
+                | Space::LabD65
+                | Space::LchD65
+                | Space::Jzazbz
+                | Space::Jzczhz
+                | Space::Ipt
+                | Space::ICtCp
         ) {
-            return self.clone();
+            return (self.clone(), 0);
         }
 
         // Local optimization: If the color is already in gamut, then we can
         // skip the binary search and return the color.
         if self.in_gamut() {
-            return self.clone();
+            return (self.clone(), 0);
         }
 
-        // 2. let origin_Oklch be origin converted from origin color space to
-        //    the Oklch color space.
-        let origin_oklch = self.to_space(Space::Oklch);
+        // 2. let origin_polar be origin converted from origin color space to
+        //    the reduction color space.
+        let origin_polar = self.to_space(reduction.space());
 
-        // 3. if the Lightness of origin_Oklch is greater than or equal to
+        // 3. if the Lightness of origin_polar is greater than or equal to
         //    100%, return { 1 1 1 origin.alpha } in destination.
-        if origin_oklch.components.0 >= 1.0 {
-            return Color::new(self.space, 1.0, 1.0, 1.0, self.alpha);
+        if origin_polar.components.0 >= white {
+            return (Color::new(self.space, 1.0, 1.0, 1.0, self.alpha), 0);
         }
 
-        // 4. if the Lightness of origin_Oklch is less than than or equal to
+        // 4. if the Lightness of origin_polar is less than than or equal to
         //    0%, return { 0 0 0 origin.alpha } in destination.
-        if origin_oklch.components.0 <= 0.0 {
-            return Color::new(self.space, 0.0, 0.0, 0.0, self.alpha);
+        if origin_polar.components.0 <= 0.0 {
+            return (Color::new(self.space, 0.0, 0.0, 0.0, self.alpha), 0);
         }
 
         // 5. let inGamut(color) be a function which returns true if, when
@@ -61,7 +223,7 @@ impl Color {
         //    of sRGB.
         //    See [`Color::in_gamut`].
 
-        // 6. if inGamut(origin_Oklch) is true, convert origin_Oklch to
+        // 6. if inGamut(origin_polar) is true, convert origin_polar to
         //    destination and return it as the gamut mapped color.
         // We already made a check at the top.
 
@@ -70,88 +232,126 @@ impl Color {
         // See [`delta_eok`] function.
 
         // 8. let JND be 0.02
-        const JND: Component = 0.02;
-
         // 9. let epsilon be 0.0001
-        const EPSILON: Component = 1.0e-4;
+        // See [`JND`] and [`EPSILON`].
 
         // 10. let clip(color) be a function which converts color to
         //     destination, converts all negative components to zero, converts
         //     all components greater that one to one, and returns the result.
         // See [`Color::clip`].
 
-        // 11. set min to zero
-        let mut min = 0.0;
-
-        // 12. set max to the Oklch chroma of origin_Oklch.
-        let mut max = origin_oklch.components.1;
-
-        // 13. let min_inGamut be a boolean that represents when min is still
-        //     in gamut, and set it to true
-        let mut min_in_gamut = true;
-
-        let mut current = origin_oklch.clone();
-        let mut current_in_space = self.clone();
-
         // If we are already clipped, then we can return the clipped color and
         // avoid the binary search completely.
-        let clipped = current_in_space.clip();
-        if delta_eok(&current, &clipped) < JND {
-            return clipped;
+        let clipped = self.clip();
+        if delta_eok(&origin_polar, &clipped) < JND {
+            return (clipped, 0);
         }
 
-        // 14. while (max - min is greater than epsilon) repeat the following
-        //     steps.
-        while max - min > EPSILON {
-            // 14.1. set chroma to (min + max) / 2
-            let chroma = (min + max) / 2.0;
+        // The precision of the binary search, in the chroma units of the
+        // reduction space.
+        let chroma_epsilon = EPSILON * white;
 
-            // 14.2. set current to origin_Oklch and then set the chroma
-            //       component to chroma
-            current.components.1 = chroma;
-
-            current_in_space = current.to_space(self.space);
-
-            // 14.3. if min_inGamut is true and also if inGamut(current) is
-            //       true, set min to chroma and continue to repeat these steps.
-            if min_in_gamut && current_in_space.in_gamut() {
-                min = chroma;
-                continue;
-            }
-
-            // 14.4. otherwise, if inGamut(current) is false carry out these
-            //       steps:
-
-            // 14.4.1. set clipped to clip(current)
-            let clipped = current_in_space.clip();
+        let (mapped, _, iterations) = search_chroma(
+            &origin_polar,
+            self.space,
+            chroma_epsilon,
+            true,
+            Color::in_gamut,
+        );
+        (mapped, iterations)
+    }
 
-            // 14.4.2. set E to delta(clipped, current)
-            let e = delta_eok(&clipped, &current);
+    /// Convert this color to the `destination` color space, reducing its
+    /// Oklch chroma just enough to be inside the gamut of `destination`. The
+    /// Oklch lightness and hue are kept exactly. Unlike
+    /// [`Color::map_into_gamut_limits`], the result is never blended with a
+    /// clipped color, which makes it more predictable at the cost of losing
+    /// more chroma.
+    ///
+    /// NOTE: The result is clipped to remove rounding errors from the
+    ///       conversion. Colors with a lightness outside [0..1] are
+    ///       therefore clipped to black or white.
+    pub fn clamp_chroma_to_gamut(&self, destination: Space) -> Color {
+        let converted = self.to_space(destination);
+
+        let Some(gamut) = destination.gamut_space() else {
+            return converted;
+        };
+        if converted.in_gamut() {
+            return converted;
+        }
 
-            // 14.4.3. if E < JND
-            if e < JND {
-                // 14.4.3.1. if (JND - E < epsilon) return clipped as the gamut
-                //           mapped color
-                if JND - e < EPSILON {
-                    return clipped;
-                }
+        let oklch = self.to_space(Space::Oklch);
+        let Components(lightness, chroma, hue) = oklch.components;
+        let max = max_chroma_in(gamut, lightness, hue);
 
-                // 14.4.3.2. otherwise
+        Color::new(
+            Space::Oklch,
+            oklch.c0(),
+            oklch.c1().map(|_| chroma.min(max)),
+            oklch.c2(),
+            oklch.alpha(),
+        )
+        .to_space(gamut)
+        .clip()
+        .to_space(destination)
+    }
 
-                // 14.4.3.2.1. set min_inGamut to false
-                min_in_gamut = false;
+    /// Convert this color to the specified color space. Unlike
+    /// [`Color::to_space`], an error is returned if the converted color is
+    /// outside the gamut limits of the destination color space. The error
+    /// carries the gamut mapped color, so callers can choose between mapping
+    /// and rejecting the color.
+    pub fn to_space_checked(&self, space: Space) -> Result<Color, GamutError> {
+        let color = self.to_space(space);
+        if color.in_gamut() {
+            Ok(color)
+        } else {
+            let mapped = color.map_into_gamut_limits();
+            Err(GamutError { color, mapped })
+        }
+    }
 
-                // 14.4.3.2.2. set min to chroma
-                min = chroma;
-            } else {
-                // 14.4.4. otherwise, set max to chroma and continue to repeat
-                //         these steps
-                max = chroma;
-            }
+    /// Return whether this color can be displayed on an sRGB display as is,
+    /// together with the color to display, converted to [`Space::Srgb`] and
+    /// mapped into its gamut limits if needed.
+    pub fn srgb_displayable(&self) -> (bool, Color) {
+        match self.to_space_checked(Space::Srgb) {
+            Ok(color) => (true, color),
+            Err(err) => (false, err.mapped),
         }
+    }
 
-        // 15. return current as the gamut mapped color current
-        current_in_space
+    /// Return the narrowest of the [`Space::Srgb`], [`Space::DisplayP3`] and
+    /// [`Space::Rec2020`] gamuts that contains this color, so it can be
+    /// converted to that color space without clipping. If none of them
+    /// contain this color, the color space of this color is returned.
+    pub fn tightest_gamut(&self) -> Space {
+        // Allow for rounding errors in the conversions, e.g. white in
+        // display-p3 converted to sRGB is not exactly 1.
+        const TOLERANCE: Component = 1.0e-5;
+
+        [Space::Srgb, Space::DisplayP3, Space::Rec2020]
+            .into_iter()
+            .find(|space| in_zero_to_one_within(self.to_space(*space).components, TOLERANCE))
+            .unwrap_or(self.space)
+    }
+
+    /// Convert this color to sRGB, map it into the sRGB gamut and quantize it
+    /// to 8 bits per component. This is the path to use to display a color.
+    ///
+    /// NOTE: The gamut mapping is done in Oklch as described by
+    ///       [`Color::map_into_gamut_limits`], which preserves the lightness and
+    ///       hue of colors outside the sRGB gamut, unlike clipping.
+    pub fn to_srgb_u8_mapped(&self) -> [u8; 3] {
+        let mapped = self.to_space(Space::Srgb).map_into_gamut_limits();
+        Srgb::new(
+            mapped.components.0,
+            mapped.components.1,
+            mapped.components.2,
+        )
+        .to_integer(8)
+        .map(|v| v as u8)
     }
 
     /// Return a color with each of the components clipped (clamped to [0..1]).
@@ -169,7 +369,8 @@ impl Color {
     /// Returns true if the color is within its gamut limits.
     ///
     /// Mainly for RGB based colors, checking components to be inside [0..1].
-    /// `Hsl` and `Hwb` are converted to [`Space::Srgb`] before being checked.
+    /// `Hsl`, `Hwb` and `Hsv` are converted to [`Space::Srgb`] before being
+    /// checked.
     pub fn in_gamut(&self) -> bool {
         match self.space {
             Space::Srgb
@@ -182,15 +383,307 @@ impl Color {
                     && in_zero_to_one(self.components.1)
                     && in_zero_to_one(self.components.2)
             }
-            Space::Hsl | Space::Hwb => self.to_space(Space::Srgb).in_gamut(),
+            Space::Hsl | Space::Hwb | Space::Hsv => self.to_space(Space::Srgb).in_gamut(),
+            Space::Lab
+            | Space::Lch
+            | Space::Oklab
+            | Space::Oklch
+            | Space::XyzD50
+            | Space::XyzD65
+            | Space::Hct
+            | Space::LabD65
+            | Space::LchD65
+            | Space::Jzazbz
+            | Space::Jzczhz
+            | Space::Ipt
+            | Space::ICtCp => true,
+        }
+    }
+
+    /// Return the index of each component that is outside [0..1] together
+    /// with how far it is outside, negative for components below 0 and
+    /// positive for components above 1. Like [`Color::in_gamut`], `Hsl`,
+    /// `Hwb` and `Hsv` are converted to [`Space::Srgb`] first, so the indices
+    /// refer to the red, green and blue components. The result is empty for colors
+    /// that are in gamut or in a color space without gamut limits.
+    pub fn gamut_violations(&self) -> Vec<(usize, Component)> {
+        match self.space {
+            Space::Hsl | Space::Hwb | Space::Hsv => self.to_space(Space::Srgb).gamut_violations(),
+            _ if self.space.gamut_space().is_none() => vec![],
+            _ => {
+                let Components(c0, c1, c2) = self.components;
+                [c0, c1, c2]
+                    .into_iter()
+                    .enumerate()
+                    .filter_map(|(index, value)| {
+                        if value < 0.0 {
+                            Some((index, value))
+                        } else if value > 1.0 {
+                            Some((index, value - 1.0))
+                        } else {
+                            None
+                        }
+                    })
+                    .collect()
+            }
+        }
+    }
+}
+
+impl Space {
+    /// Return the RGB color space that defines the gamut limits of this color
+    /// space, or None if this color space has no gamut limits.
+    fn gamut_space(&self) -> Option<Space> {
+        match self {
+            Space::Srgb
+            | Space::SrgbLinear
+            | Space::DisplayP3
+            | Space::A98Rgb
+            | Space::ProPhotoRgb
+            | Space::Rec2020 => Some(*self),
+            Space::Hsl | Space::Hwb | Space::Hsv => Some(Space::Srgb),
             Space::Lab
             | Space::Lch
             | Space::Oklab
             | Space::Oklch
             | Space::XyzD50
-            | Space::XyzD65 => true,
+            | Space::XyzD65
+            | Space::Hct
+            | Space::LabD65
+            | Space::LchD65
+            | Space::Jzazbz
+            | Space::Jzczhz
+            | Space::Ipt
+            | Space::ICtCp => None,
         }
     }
+
+    /// Returns whether the gamut of this color space contains the entire
+    /// gamut of `other`, e.g. [`Space::Rec2020`] contains [`Space::Srgb`].
+    /// Returns None if either of the color spaces has no gamut limits, e.g.
+    /// [`Space::Lab`].
+    pub fn gamut_contains(&self, other: Space) -> Option<bool> {
+        // Allow for rounding errors in the conversions, e.g. white in sRGB
+        // converted to display-p3 is not exactly 1.
+        const TOLERANCE: Component = 1.0e-3;
+
+        let outer = self.gamut_space()?;
+        let inner = other.gamut_space()?;
+
+        // The gamut of an RGB color space is a parallelepiped in linear light,
+        // so it is enough to check that the corners of the inner gamut are
+        // inside the outer gamut. The corners are the same whether they are
+        // gamma encoded or not.
+        let contains = (0..8).all(|corner| {
+            let c = |bit: u32| (corner >> bit & 1) as Component;
+            let color = Color::new(inner, c(0), c(1), c(2), 1.0).to_space(outer);
+            in_zero_to_one_within(color.components, TOLERANCE)
+        });
+
+        Some(contains)
+    }
+}
+
+/// Convert linear light components in the given RGB color space to the base
+/// color space.
+fn linear_to_base(space: Space, Components(r, g, b): Components) -> Base {
+    match space {
+        Space::Srgb => SrgbLinear::new(r, g, b).to_base(),
+        Space::DisplayP3 => DisplayP3Linear::new(r, g, b).to_base(),
+        Space::A98Rgb => A98RgbLinear::new(r, g, b).to_base(),
+        Space::ProPhotoRgb => ProPhotoRgbLinear::new(r, g, b).to_base(),
+        Space::Rec2020 => Rec2020Linear::new(r, g, b).to_base(),
+        _ => unreachable!("not an RGB color space"),
+    }
+}
+
+/// Estimate the fraction of the gamut volume of `outer` (in CIE-XYZ) that is
+/// covered by the gamut of `inner`, e.g. the gamut of [`Space::Srgb`] covers
+/// roughly 72% of [`Space::DisplayP3`].
+///
+/// The volume is sampled on a regular grid, so the result is accurate to
+/// about a percent. If `inner` has no gamut limits, it covers all of `outer`
+/// (1.0). Otherwise if `outer` has no gamut limits, the coverage is 0.0.
+pub fn gamut_coverage(inner: Space, outer: Space) -> Component {
+    const STEPS: usize = 24;
+
+    let Some(inner) = inner.gamut_space() else {
+        return 1.0;
+    };
+    let Some(outer) = outer.gamut_space() else {
+        return 0.0;
+    };
+
+    // Linear light sRGB has the same gamut as sRGB.
+    let rgb = |space: Space| match space {
+        Space::SrgbLinear => Space::Srgb,
+        space => space,
+    };
+    let (inner, outer) = (rgb(inner), rgb(outer));
+
+    if inner == outer {
+        return 1.0;
+    }
+
+    // Sampling uniformly in linear light is sampling uniformly in CIE-XYZ,
+    // because the two are related by a linear transform.
+    let at = |i: usize| (i as Component + 0.5) / STEPS as Component;
+
+    let mut covered = 0;
+    for r in 0..STEPS {
+        for g in 0..STEPS {
+            for b in 0..STEPS {
+                let base = linear_to_base(outer, Components(at(r), at(g), at(b)));
+                if base.to_color(None).to_space(inner).in_gamut() {
+                    covered += 1;
+                }
+            }
+        }
+    }
+
+    covered as Component / (STEPS * STEPS * STEPS) as Component
+}
+
+/// Steps 11 to 15 of the CSS gamut mapping algorithm: a binary search that
+/// reduces the chroma of `origin`, a color in the reduction space, until it
+/// is inside the gamut of `destination` according to `in_gamut`, to a
+/// precision of `chroma_epsilon`. If `clip` is false, colors are never
+/// clipped when they are within the JND, so the search finds the largest
+/// chroma that is still in gamut instead.
+///
+/// Returns the mapped color in `destination`, the largest chroma that was
+/// found to be in gamut and the number of iterations.
+fn search_chroma(
+    origin: &Color,
+    destination: Space,
+    chroma_epsilon: Component,
+    clip: bool,
+    in_gamut: impl Fn(&Color) -> bool,
+) -> (Color, Component, u32) {
+    // 11. set min to zero
+    let mut min = 0.0;
+
+    // 12. set max to the chroma of origin.
+    let mut max = origin.components.1;
+
+    // 13. let min_inGamut be a boolean that represents when min is still
+    //     in gamut, and set it to true
+    let mut min_in_gamut = true;
+
+    let mut current = origin.clone();
+    let mut current_in_space = origin.to_space(destination);
+
+    let mut iterations = 0;
+
+    // 14. while (max - min is greater than epsilon) repeat the following
+    //     steps.
+    while max - min > chroma_epsilon {
+        iterations += 1;
+
+        // 14.1. set chroma to (min + max) / 2
+        let chroma = (min + max) / 2.0;
+
+        // 14.2. set current to origin and then set the chroma
+        //       component to chroma
+        current.components.1 = chroma;
+
+        current_in_space = current.to_space(destination);
+
+        // 14.3. if min_inGamut is true and also if inGamut(current) is
+        //       true, set min to chroma and continue to repeat these steps.
+        if min_in_gamut && in_gamut(&current_in_space) {
+            min = chroma;
+            continue;
+        }
+
+        // 14.4. otherwise, if inGamut(current) is false carry out these
+        //       steps:
+
+        // 14.4.1. set clipped to clip(current)
+        let clipped = current_in_space.clip();
+
+        // 14.4.2. set E to delta(clipped, current)
+        let e = delta_eok(&clipped, &current);
+
+        // 14.4.3. if E < JND
+        if clip && e < JND {
+            // 14.4.3.1. if (JND - E < epsilon) return clipped as the gamut
+            //           mapped color
+            if JND - e < EPSILON {
+                return (clipped, min, iterations);
+            }
+
+            // 14.4.3.2. otherwise
+
+            // 14.4.3.2.1. set min_inGamut to false
+            min_in_gamut = false;
+
+            // 14.4.3.2.2. set min to chroma
+            min = chroma;
+        } else {
+            // 14.4.4. otherwise, set max to chroma and continue to repeat
+            //         these steps
+            max = chroma;
+        }
+    }
+
+    // 15. return current as the gamut mapped color current
+    (current_in_space, min, iterations)
+}
+
+/// Find the largest Oklch chroma with the given lightness and hue that is
+/// inside the gamut of the RGB color space `gamut` with a binary search.
+fn max_chroma_in(gamut: Space, lightness: Component, hue: Component) -> Component {
+    // Larger than the chroma of any color inside the gamut of an RGB color
+    // space.
+    const MAX_CHROMA: Component = 1.0;
+    const PRECISION: Component = 1.0e-5;
+    // Allow for rounding errors in the conversions, e.g. achromatic colors
+    // converted to sRGB might have tiny negative components.
+    const TOLERANCE: Component = 1.0e-5;
+
+    // Only black and white are in gamut at the ends of the lightness range.
+    if lightness <= 0.0 || lightness >= 1.0 {
+        return 0.0;
+    }
+
+    let origin = Color::new(Space::Oklch, lightness, MAX_CHROMA, hue, 1.0);
+    let (_, chroma, _) = search_chroma(&origin, gamut, PRECISION, false, |color| {
+        in_zero_to_one_within(color.components, TOLERANCE)
+    });
+    chroma
+}
+
+/// Return the largest Oklch chroma with the given lightness and hue that is
+/// still inside the gamut of `space`, found with a binary search. Useful for
+/// limiting a chroma slider in a color picker.
+///
+/// Returns infinity if `space` has no gamut limits, e.g. [`Space::Lab`].
+pub fn max_chroma(space: Space, lightness: Component, hue: Component) -> Component {
+    match space.gamut_space() {
+        Some(gamut) => max_chroma_in(gamut, lightness, hue),
+        None => Component::INFINITY,
+    }
+}
+
+/// Trace the outline of the gamut of `space` in Oklch at the given hue. The
+/// result holds `samples` (lightness, chroma) pairs, with the lightness
+/// evenly spaced over [0..1] and the chroma being the largest chroma at that
+/// lightness that is still inside the gamut.
+///
+/// Returns an empty list if `space` has no gamut limits, e.g. [`Space::Lab`].
+pub fn gamut_boundary(space: Space, hue: Component, samples: usize) -> Vec<(Component, Component)> {
+    let Some(gamut) = space.gamut_space() else {
+        return vec![];
+    };
+
+    let last = samples.saturating_sub(1).max(1) as Component;
+    (0..samples)
+        .map(|i| {
+            let lightness = i as Component / last;
+            (lightness, max_chroma_in(gamut, lightness, hue))
+        })
+        .collect()
 }
 
 #[cfg(test)]
@@ -198,6 +691,171 @@ mod tests {
     use super::*;
     use crate::assert_component_eq;
 
+    #[test]
+    fn clamp_chroma_to_gamut() {
+        let green = Color::new(Space::DisplayP3, 0.0, 1.0, 0.0, 1.0);
+        let original = green.to_space(Space::Oklch);
+
+        let clamped = green.clamp_chroma_to_gamut(Space::Srgb);
+        assert_eq!(clamped.space, Space::Srgb);
+        assert!(clamped.in_gamut());
+
+        let oklch = clamped.to_space(Space::Oklch);
+        assert!((oklch.components.0 - original.components.0).abs() < 1.0e-4);
+        assert!((oklch.components.2 - original.components.2).abs() < 1.0e-2);
+        assert!(oklch.components.1 < original.components.1);
+
+        // Colors already in gamut are only converted.
+        let red = Color::new(Space::Srgb, 1.0, 0.0, 0.0, 1.0);
+        assert_eq!(
+            red.clamp_chroma_to_gamut(Space::DisplayP3),
+            red.to_space(Space::DisplayP3)
+        );
+
+        // Color spaces without gamut limits do not change the chroma.
+        assert_eq!(green.clamp_chroma_to_gamut(Space::Oklch), original);
+
+        // HSL uses the sRGB gamut.
+        let hsl = green.clamp_chroma_to_gamut(Space::Hsl);
+        assert_eq!(hsl.space, Space::Hsl);
+        assert!(hsl.in_gamut());
+    }
+
+    #[test]
+    fn map_into_gamut_with() {
+        let green = Color::new(Space::DisplayP3, 0.0, 1.0, 0.0, 1.0).to_space(Space::Srgb);
+        assert!(!green.in_gamut());
+
+        let clipped = green.map_into_gamut_with(GamutMethod::Clip);
+        assert_eq!(clipped, green.clip());
+
+        assert_eq!(
+            green.map_into_gamut_with(GamutMethod::OklchChroma),
+            green.map_into_gamut_limits()
+        );
+        assert_eq!(GamutMethod::default(), GamutMethod::OklchChroma);
+
+        let lch = green.map_into_gamut_with(GamutMethod::LchChroma);
+        assert_eq!(lch.space, Space::Srgb);
+        assert!(lch.in_gamut());
+        let original = green.to_space(Space::Lch);
+        let mapped = lch.to_space(Space::Lch);
+        assert!(mapped.components.1 < original.components.1);
+
+        // HSL is clipped in sRGB, so the hue is not clamped to [0..1].
+        let hsl = green.to_space(Space::Hsl);
+        let clipped = hsl.map_into_gamut_with(GamutMethod::Clip);
+        assert_eq!(clipped.space, Space::Hsl);
+        assert!(clipped.in_gamut());
+        assert_component_eq!(clipped.components.0, 120.0);
+
+        // Colors without gamut limits or in gamut are not changed.
+        let oklch = Color::new(Space::Oklch, 0.9, 0.5, 120.0, 1.0);
+        let red = Color::new(Space::Srgb, 1.0, 0.0, 0.0, 1.0);
+        for method in [
+            GamutMethod::Clip,
+            GamutMethod::OklchChroma,
+            GamutMethod::LchChroma,
+        ] {
+            assert_eq!(oklch.map_into_gamut_with(method), oklch);
+            assert_eq!(red.map_into_gamut_with(method), red);
+        }
+    }
+
+    #[test]
+    fn gamut_violations() {
+        let green = Color::new(Space::DisplayP3, 0.0, 1.0, 0.0, 1.0);
+        let violations = green.to_space(Space::Srgb).gamut_violations();
+        assert_eq!(violations.len(), 3);
+        assert!(violations[0].1 < 0.0);
+        assert!(violations[1].1 > 0.0);
+        assert!(violations[2].1 < 0.0);
+
+        let bright = Color::new(Space::Srgb, 1.5, 0.5, 0.0, 1.0);
+        assert_eq!(bright.gamut_violations(), vec![(0, 0.5)]);
+
+        let hsl = Color::new(Space::Hsl, 120.0, 1.5, 0.5, 1.0);
+        assert!(!hsl.gamut_violations().is_empty());
+
+        assert!(Color::new(Space::Srgb, 0.2, 0.4, 0.6, 1.0)
+            .gamut_violations()
+            .is_empty());
+        assert!(Color::new(Space::Oklch, 0.9, 0.5, 120.0, 1.0)
+            .gamut_violations()
+            .is_empty());
+    }
+
+    #[test]
+    fn tightest_gamut() {
+        let white = Color::new(Space::DisplayP3, 1.0, 1.0, 1.0, 1.0);
+        assert_eq!(white.tightest_gamut(), Space::Srgb);
+
+        let orange = Color::new(Space::Lab, 60.0, 30.0, 50.0, 1.0);
+        assert_eq!(orange.tightest_gamut(), Space::Srgb);
+
+        let p3_red = Color::new(Space::DisplayP3, 1.0, 0.0, 0.0, 1.0);
+        assert_eq!(p3_red.tightest_gamut(), Space::DisplayP3);
+
+        let rec2020_green = Color::new(Space::Rec2020, 0.0, 1.0, 0.0, 1.0);
+        assert_eq!(rec2020_green.tightest_gamut(), Space::Rec2020);
+
+        // Not inside any of the gamuts.
+        let imaginary = Color::new(Space::XyzD65, 0.0, 1.0, 0.0, 1.0);
+        assert_eq!(imaginary.tightest_gamut(), Space::XyzD65);
+    }
+
+    #[test]
+    fn srgb_displayable() {
+        let orange = Color::new(Space::Lab, 60.0, 30.0, 50.0, 1.0);
+        let (displayable, color) = orange.srgb_displayable();
+        assert!(displayable);
+        assert_eq!(color.space, Space::Srgb);
+        assert_eq!(color.components, orange.to_space(Space::Srgb).components);
+
+        let red = Color::new(Space::DisplayP3, 1.0, 0.0, 0.0, 0.5);
+        let (displayable, color) = red.srgb_displayable();
+        assert!(!displayable);
+        assert_eq!(color.space, Space::Srgb);
+        assert!(color.in_gamut());
+        assert_eq!(color.alpha, 0.5);
+    }
+
+    #[test]
+    fn srgb_u8_is_gamut_mapped() {
+        let red = Color::new(Space::Srgb, 1.0, 0.0, 0.0, 1.0);
+        assert_eq!(red.to_srgb_u8_mapped(), [255, 0, 0]);
+
+        let gray = Color::new(Space::Oklch, 0.5, 0.0, None, 1.0);
+        assert_eq!(gray.to_srgb_u8_mapped(), [99, 99, 99]);
+
+        // color(display-p3 0 1 0) is outside the sRGB gamut. Mapping keeps the
+        // blue component, clipping would zero it.
+        let green = Color::new(Space::DisplayP3, 0.0, 1.0, 0.0, 1.0);
+        let clipped = green.to_space(Space::Srgb).clip();
+        let [red, _, blue] = green.to_srgb_u8_mapped();
+        assert_eq!(red, 0);
+        assert!(blue > (clipped.components.2 * 255.0).round() as u8);
+    }
+
+    #[test]
+    fn checked_conversion() {
+        let red = Color::new(Space::Srgb, 1.0, 0.0, 0.0, 1.0);
+        let p3 = red.to_space_checked(Space::DisplayP3).unwrap();
+        assert_eq!(p3.space, Space::DisplayP3);
+
+        // color(display-p3 1 0 0) is outside the sRGB gamut.
+        let red = Color::new(Space::DisplayP3, 1.0, 0.0, 0.0, 1.0);
+        let err = red.to_space_checked(Space::Srgb).unwrap_err();
+        assert_eq!(err.color.space, Space::Srgb);
+        assert!(!err.color.in_gamut());
+        assert_eq!(err.mapped.space, Space::Srgb);
+        assert!(err.mapped.in_gamut());
+        assert_component_eq!(err.mapped.components.1, 0.044557024);
+
+        // Color spaces without gamut limits never fail.
+        assert!(red.to_space_checked(Space::Oklch).is_ok());
+    }
+
     #[test]
     fn map_red() {
         // color(display-p3 1 0 0)
@@ -209,6 +867,65 @@ mod tests {
         assert_component_eq!(mapped.components.2, 0.045930356761375773);
     }
 
+    #[test]
+    fn count_binary_search_iterations() {
+        let (_, iterations) =
+            Color::new(Space::Srgb, 0.5, 0.5, 0.5, 1.0).map_into_gamut_limits_with_iterations();
+        assert_eq!(iterations, 0);
+
+        // color(display-p3 1 0 0)
+        let source = Color::new(Space::DisplayP3, 1.0, 0.0, 0.0, 1.0).to_space(Space::Srgb);
+        let (mapped, iterations) = source.map_into_gamut_limits_with_iterations();
+        assert!(iterations > 0);
+        assert_eq!(mapped.components, source.map_into_gamut_limits().components);
+    }
+
+    #[test]
+    fn report_residual_delta() {
+        let (mapped, delta) =
+            Color::new(Space::Srgb, 0.5, 0.5, 0.5, 1.0).map_into_gamut_limits_reported();
+        assert_eq!(mapped.components, Components(0.5, 0.5, 0.5));
+        assert_eq!(delta, 0.0);
+
+        // color(display-p3 0 1 0) shifts a lot more than a color just outside
+        // of the sRGB gamut.
+        let green = Color::new(Space::DisplayP3, 0.0, 1.0, 0.0, 1.0).to_space(Space::Srgb);
+        let (_, green_delta) = green.map_into_gamut_limits_reported();
+
+        let near = Color::new(Space::Srgb, 1.01, 0.5, 0.5, 1.0);
+        let (_, near_delta) = near.map_into_gamut_limits_reported();
+
+        assert!(near_delta > 0.0);
+        assert!(green_delta > near_delta);
+    }
+
+    #[test]
+    fn gamut_containment() {
+        assert_eq!(Space::Srgb.gamut_contains(Space::Srgb), Some(true));
+        assert_eq!(Space::Rec2020.gamut_contains(Space::Srgb), Some(true));
+        // The red primary of display-p3 lies just outside of rec2020.
+        assert_eq!(Space::Rec2020.gamut_contains(Space::DisplayP3), Some(false));
+        assert_eq!(Space::DisplayP3.gamut_contains(Space::Hsl), Some(true));
+        assert_eq!(Space::Srgb.gamut_contains(Space::DisplayP3), Some(false));
+        assert_eq!(Space::A98Rgb.gamut_contains(Space::DisplayP3), Some(false));
+        assert_eq!(Space::DisplayP3.gamut_contains(Space::A98Rgb), Some(false));
+        assert_eq!(Space::Lab.gamut_contains(Space::Srgb), None);
+        assert_eq!(Space::Srgb.gamut_contains(Space::Oklch), None);
+    }
+
+    #[test]
+    fn gamut_coverage_estimates() {
+        assert_eq!(gamut_coverage(Space::Srgb, Space::Hsl), 1.0);
+        assert_eq!(gamut_coverage(Space::Lab, Space::Srgb), 1.0);
+        assert_eq!(gamut_coverage(Space::Srgb, Space::Lab), 0.0);
+
+        // Rec2020 contains the whole sRGB gamut.
+        assert_eq!(gamut_coverage(Space::Rec2020, Space::Srgb), 1.0);
+
+        let coverage = gamut_coverage(Space::Srgb, Space::DisplayP3);
+        assert!(coverage > 0.7 && coverage < 0.75);
+    }
+
     #[test]
     fn find_gamut_intersection_linearly() {
         // This test is just here for a sanity check against the gamut mapping
@@ -243,4 +960,50 @@ mod tests {
         assert_component_eq!(result.components.1, 0.20348036);
         assert_component_eq!(result.components.2, 0.15877128);
     }
+
+    #[test]
+    fn max_chroma_at_lightness_and_hue() {
+        let red = Color::new(Space::Srgb, 1.0, 0.0, 0.0, 1.0).to_space(Space::Oklch);
+        let Components(lightness, chroma, hue) = red.components;
+
+        // sRGB red is a corner of the sRGB gamut.
+        assert!((max_chroma(Space::Srgb, lightness, hue) - chroma).abs() < 1.0e-3);
+        assert!((max_chroma(Space::Hsl, lightness, hue) - chroma).abs() < 1.0e-3);
+        assert!(max_chroma(Space::DisplayP3, lightness, hue) > chroma);
+
+        assert_eq!(max_chroma(Space::Srgb, 0.0, hue), 0.0);
+        assert_eq!(max_chroma(Space::Srgb, 1.0, hue), 0.0);
+        assert_eq!(
+            max_chroma(Space::Oklch, lightness, hue),
+            Component::INFINITY
+        );
+    }
+
+    #[test]
+    fn trace_gamut_boundary() {
+        let boundary = gamut_boundary(Space::Srgb, 29.233885, 11);
+        assert_eq!(boundary.len(), 11);
+
+        // The gamut narrows to a point at black and white.
+        assert_eq!(boundary[0], (0.0, 0.0));
+        assert_eq!(boundary[10], (1.0, 0.0));
+
+        // Every point is on the edge of the gamut.
+        let oklch = |lightness, chroma| Color::new(Space::Oklch, lightness, chroma, 29.233885, 1.0);
+        for &(lightness, chroma) in &boundary {
+            let inside = oklch(lightness, chroma * 0.99);
+            assert!(delta_eok(&inside, &inside.to_space(Space::Srgb).clip()) < 1.0e-4);
+        }
+        for &(lightness, chroma) in &boundary[1..10] {
+            assert!(!oklch(lightness, chroma * 1.01)
+                .to_space(Space::Srgb)
+                .in_gamut());
+        }
+
+        // The display-p3 gamut is wider than the sRGB gamut.
+        let p3 = gamut_boundary(Space::DisplayP3, 29.233885, 11);
+        assert!(p3[5].1 > boundary[5].1);
+
+        assert!(gamut_boundary(Space::Oklab, 0.0, 11).is_empty());
+    }
 }
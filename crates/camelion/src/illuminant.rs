@@ -0,0 +1,286 @@
+//! Standard illuminants and chromatic adaptation between them.
+//! <http://www.brucelindbloom.com/index.html?Eqn_ChromAdapt.html>
+
+use crate::{
+    color::Components,
+    math::{transform, transform_3x3, Transform},
+    models::{WhitePoint, D50, D55, D65, D75},
+    Color, Space,
+};
+
+/// Bradford cone response matrix.
+#[rustfmt::skip]
+#[allow(clippy::excessive_precision)]
+const BRADFORD_XYZ_TO_LMS: Transform = transform_3x3(
+     0.8951, -0.7502,  0.0389,
+     0.2664,  1.7135, -0.0685,
+    -0.1614,  0.0367,  1.0296,
+);
+
+/// Inverse of the Bradford cone response matrix.
+#[rustfmt::skip]
+#[allow(clippy::excessive_precision)]
+const BRADFORD_LMS_TO_XYZ: Transform = transform_3x3(
+     0.9869929, 0.4323053, -0.0085287,
+    -0.1470543, 0.5183603,  0.0400428,
+     0.1599627, 0.0492912,  0.9684867,
+);
+
+/// CIECAM02 (CAT02) cone response matrix.
+#[rustfmt::skip]
+#[allow(clippy::excessive_precision)]
+const CAT02_XYZ_TO_LMS: Transform = transform_3x3(
+     0.7328, -0.7036, 0.0030,
+     0.4296,  1.6975, 0.0136,
+    -0.1624,  0.0061, 0.9834,
+);
+
+/// Inverse of the CIECAM02 (CAT02) cone response matrix.
+#[rustfmt::skip]
+#[allow(clippy::excessive_precision)]
+const CAT02_LMS_TO_XYZ: Transform = transform_3x3(
+     1.0961238, 0.4543690, -0.0096276,
+    -0.2788690, 0.4735332, -0.0056980,
+     0.1827452, 0.0720978,  1.0153256,
+);
+
+/// Von Kries (Hunt-Pointer-Estevez) cone response matrix.
+#[rustfmt::skip]
+#[allow(clippy::excessive_precision)]
+const VON_KRIES_XYZ_TO_LMS: Transform = transform_3x3(
+     0.40024, -0.22630, 0.0,
+     0.70760,  1.16532, 0.0,
+    -0.08081,  0.04570, 0.91822,
+);
+
+/// Inverse of the Von Kries (Hunt-Pointer-Estevez) cone response matrix.
+#[rustfmt::skip]
+#[allow(clippy::excessive_precision)]
+const VON_KRIES_LMS_TO_XYZ: Transform = transform_3x3(
+     1.8599364,  0.3611914, 0.0,
+    -1.1293816,  0.6388125, 0.0,
+     0.2198974, -0.0000064, 1.0890636,
+);
+
+/// The cone response models that can be used for chromatic adaptation.
+/// <http://www.brucelindbloom.com/index.html?Eqn_ChromAdapt.html>
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum AdaptationMethod {
+    /// The Bradford transform, which is what CSS uses to convert between the
+    /// D50 and D65 white points.
+    #[default]
+    Bradford,
+    /// The chromatic adaptation transform from the CIECAM02 color appearance
+    /// model.
+    Cat02,
+    /// The Von Kries transform, using the Hunt-Pointer-Estevez cone response.
+    VonKries,
+}
+
+impl AdaptationMethod {
+    /// The matrices to convert from CIE-XYZ to the cone response domain and
+    /// back.
+    fn matrices(&self) -> (Transform, Transform) {
+        match self {
+            AdaptationMethod::Bradford => (BRADFORD_XYZ_TO_LMS, BRADFORD_LMS_TO_XYZ),
+            AdaptationMethod::Cat02 => (CAT02_XYZ_TO_LMS, CAT02_LMS_TO_XYZ),
+            AdaptationMethod::VonKries => (VON_KRIES_XYZ_TO_LMS, VON_KRIES_LMS_TO_XYZ),
+        }
+    }
+}
+
+/// Standard CIE illuminants (2° observer).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Illuminant {
+    /// Incandescent/tungsten light.
+    A,
+    /// Horizon light, the white point used by CIE-Lab.
+    D50,
+    /// Mid-morning/mid-afternoon daylight.
+    D55,
+    /// Noon daylight, the white point used by sRGB.
+    D65,
+    /// North sky daylight.
+    D75,
+    /// Cool white fluorescent light.
+    F2,
+    /// Broad-band daylight fluorescent light.
+    F7,
+    /// Narrow tri-band fluorescent light.
+    F11,
+}
+
+impl Illuminant {
+    /// The CIE-XYZ coordinates of the white point of this illuminant, with Y
+    /// normalized to 1.
+    pub fn white_point(&self) -> Components {
+        match self {
+            Illuminant::A => Components(1.09850, 1.0, 0.35585),
+            Illuminant::D50 => D50::WHITE_POINT,
+            Illuminant::D55 => D55::WHITE_POINT,
+            Illuminant::D65 => D65::WHITE_POINT,
+            Illuminant::D75 => D75::WHITE_POINT,
+            Illuminant::F2 => Components(0.99187, 1.0, 0.67395),
+            Illuminant::F7 => Components(0.95044, 1.0, 1.08755),
+            Illuminant::F11 => Components(1.00966, 1.0, 0.64370),
+        }
+    }
+}
+
+/// A precomputed chromatic adaptation from one white point to another.
+/// Building the adaptation once avoids recalculating the matrix when adapting
+/// many colors between the same pair of white points.
+#[derive(Clone, Debug)]
+pub struct Adaptation {
+    matrix: Transform,
+}
+
+impl Adaptation {
+    /// Create a Bradford adaptation between the CIE-XYZ coordinates of two
+    /// white points, with Y normalized to 1.
+    pub fn new(from: Components, to: Components) -> Self {
+        Self::with_method(from, to, AdaptationMethod::Bradford)
+    }
+
+    /// Create an adaptation between the CIE-XYZ coordinates of two white
+    /// points, with Y normalized to 1, using the given cone response model.
+    /// The matrix is calculated as `M⁻¹ · diag(to / from) · M`, where the
+    /// white points are in the cone response domain of `M`.
+    pub fn with_method(from: Components, to: Components, method: AdaptationMethod) -> Self {
+        let (xyz_to_lms, lms_to_xyz) = method.matrices();
+        let from = transform(&xyz_to_lms, from);
+        let to = transform(&xyz_to_lms, to);
+
+        #[rustfmt::skip]
+        let scale = transform_3x3(
+            to.0 / from.0, 0.0,           0.0,
+            0.0,           to.1 / from.1, 0.0,
+            0.0,           0.0,           to.2 / from.2,
+        );
+
+        Self {
+            matrix: xyz_to_lms.then(&scale).then(&lms_to_xyz),
+        }
+    }
+
+    /// Create an adaptation between the white points of two illuminants.
+    pub fn between(from: Illuminant, to: Illuminant) -> Self {
+        Self::new(from.white_point(), to.white_point())
+    }
+
+    /// Adapt CIE-XYZ coordinates relative to the source white point to the
+    /// target white point.
+    pub fn adapt(&self, xyz: Components) -> Components {
+        transform(&self.matrix, xyz)
+    }
+}
+
+impl Color {
+    /// Simulate how this color, measured as a surface color under the `from`
+    /// illuminant, would appear under the `to` illuminant. The chromatic
+    /// adaptation is done with the Bradford transform and the result is
+    /// returned in the color space of this color.
+    pub fn under_illuminant(&self, from: Illuminant, to: Illuminant) -> Color {
+        if from == to {
+            return self.clone();
+        }
+
+        let xyz = self.to_space(Space::XyzD65);
+        let Components(x, y, z) = Adaptation::between(from, to).adapt(xyz.components);
+
+        Color::new(Space::XyzD65, x, y, z, self.alpha()).to_space(self.space)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{assert_component_eq, Component};
+
+    #[test]
+    fn white_adapts_to_the_target_white_point() {
+        let Components(x, y, z) = D65::WHITE_POINT;
+        let white = Color::new(Space::XyzD65, x, y, z, 1.0);
+
+        let tungsten = white.under_illuminant(Illuminant::D65, Illuminant::A);
+        let Components(x, y, z) = Illuminant::A.white_point();
+        assert_component_eq!(tungsten.components.0, x);
+        assert_component_eq!(tungsten.components.1, y);
+        assert_component_eq!(tungsten.components.2, z);
+    }
+
+    #[test]
+    fn matches_the_d65_to_d50_transfer() {
+        let color = Color::new(Space::XyzD65, 0.3, 0.4, 0.5, 1.0);
+
+        let adapted = color.under_illuminant(Illuminant::D65, Illuminant::D50);
+        let transferred = color.to_space(Space::XyzD50);
+        assert_component_eq!(adapted.components.0, transferred.components.0);
+        assert_component_eq!(adapted.components.1, transferred.components.1);
+        assert_component_eq!(adapted.components.2, transferred.components.2);
+    }
+
+    #[test]
+    fn reuse_adaptation() {
+        let adaptation = Adaptation::between(Illuminant::D65, Illuminant::F11);
+        let back = Adaptation::between(Illuminant::F11, Illuminant::D65);
+
+        for color in [Components(0.2, 0.3, 0.4), Components(0.9, 0.8, 0.1)] {
+            let Components(x, y, z) = back.adapt(adaptation.adapt(color));
+            assert_component_eq!(x, color.0);
+            assert_component_eq!(y, color.1);
+            assert_component_eq!(z, color.2);
+        }
+
+        let Components(x, y, z) = adaptation.adapt(D65::WHITE_POINT);
+        let Components(wx, wy, wz) = Illuminant::F11.white_point();
+        assert_component_eq!(x, wx);
+        assert_component_eq!(y, wy);
+        assert_component_eq!(z, wz);
+    }
+
+    #[test]
+    fn adaptation_methods() {
+        let color = Components(0.3, 0.4, 0.5);
+        let results = [
+            AdaptationMethod::Bradford,
+            AdaptationMethod::Cat02,
+            AdaptationMethod::VonKries,
+        ]
+        .map(|method| {
+            let adaptation = Adaptation::with_method(
+                Illuminant::D65.white_point(),
+                Illuminant::A.white_point(),
+                method,
+            );
+
+            // White always maps to the target white point.
+            let Components(x, y, z) = adaptation.adapt(Illuminant::D65.white_point());
+            let Components(wx, wy, wz) = Illuminant::A.white_point();
+            assert_component_eq!(x, wx);
+            assert_component_eq!(y, wy);
+            assert_component_eq!(z, wz);
+
+            adaptation.adapt(color)
+        });
+
+        // The cone response models do not agree for other colors.
+        assert!((results[0] - results[2]).length() > 1.0e-3);
+        assert!((results[1] - results[2]).length() > 1.0e-3);
+    }
+
+    #[test]
+    fn round_trip_in_source_space() {
+        let color = Color::new(Space::Srgb, 0.8, 0.5, 0.2, 0.5);
+
+        let warm = color.under_illuminant(Illuminant::D65, Illuminant::A);
+        assert_eq!(warm.space, Space::Srgb);
+        assert_eq!(warm.alpha, 0.5);
+        assert!(warm.components.2 < color.components.2);
+
+        let back = warm.under_illuminant(Illuminant::A, Illuminant::D65);
+        assert_component_eq!(back.components.0, 0.8);
+        assert_component_eq!(back.components.1, 0.5);
+        assert_component_eq!(back.components.2, 0.2);
+    }
+}
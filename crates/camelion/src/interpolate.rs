@@ -1,5 +1,5 @@
 use crate::{
-    color::{Color, Flags, Space},
+    color::{Color, Components, Flags, Space},
     math::normalize_hue,
     Component,
 };
@@ -10,35 +10,73 @@ impl Color {
         Interpolation::new(self, other, space)
     }
 
+    /// Move this color a fraction `t` of the way towards `target`,
+    /// interpolating in the specified [`Space`](color space). This is useful
+    /// for animations that step towards a target each frame.
+    ///
+    /// NOTE: After the call, this color is in the interpolation color space.
+    ///       Repeated steps therefore do not convert back and forth between
+    ///       color spaces, which would accumulate rounding errors and drift
+    ///       the hue of polar color spaces. Hues are interpolated along the
+    ///       shorter arc, so they move steadily towards the target hue.
+    pub fn lerp_towards(&mut self, target: &Color, space: Space, t: Component) {
+        *self = Interpolation::new(self, target, space).at(t);
+    }
+
+    /// Mix this color with `other` in the given color space, like the CSS
+    /// `color-mix()` function with a single percentage. `percentage` is the
+    /// amount of `other` in [0..1], so the weights are `1 - percentage` for
+    /// this color and `percentage` for `other`. E.g. `a.mix(&b, space, 0.25)`
+    /// is `color-mix(in <space>, a, b 25%)`.
+    /// <https://drafts.csswg.org/css-color-5/#color-mix>
+    pub fn mix(&self, other: &Color, space: Space, percentage: Component) -> Color {
+        self.mix_weighted(other, space, 1.0 - percentage, percentage)
+    }
+
+    /// Mix this color with `other` in the given color space, like the CSS
+    /// `color-mix()` function with a percentage for each color, given in
+    /// [0..1]. The percentages are normalized to sum to 1 and if they sum to
+    /// less than 1, the resulting alpha is multiplied by the sum. See
+    /// [`Interpolation::with_normalized_weights`].
+    ///
+    /// NOTE: CSS rejects a `color-mix()` where the percentages sum to 0, here
+    ///       the result has NaN components.
+    /// <https://drafts.csswg.org/css-color-5/#color-mix-percent-norm>
+    pub fn mix_weighted(
+        &self,
+        other: &Color,
+        space: Space,
+        percentage1: Component,
+        percentage2: Component,
+    ) -> Color {
+        Interpolation::new(self, other, space).with_normalized_weights(percentage1, percentage2)
+    }
+
     /// Premultiply the color with it's alpha and return the result as per:
     /// <https://drafts.csswg.org/css-color-4/#interpolation-alpha>
-    fn premultiply(&self) -> Premultiplied {
+    ///
+    /// Hue components are not premultiplied. If the alpha component is
+    /// missing, the components are not premultiplied either. Useful for
+    /// caching premultiplied colors that are blended many times.
+    pub fn premultiply(&self) -> Premultiplied {
+        let mut components = self.components;
+
         // If the alpha value is none, the premultiplied value is the
         // un-premultiplied value.
-        if self.flags.contains(Flags::ALPHA_IS_NONE) {
-            return Premultiplied {
-                components: [self.c0(), self.c1(), self.c2()],
-                alpha: None,
-            };
-        }
-
-        let hue_index = self.space.hue_index();
-
-        macro_rules! c {
-            ($c:expr,$i:literal) => {{
-                $c.map(|v| {
-                    if hue_index != Some($i) {
-                        v * self.alpha
-                    } else {
-                        v
-                    }
-                })
-            }};
+        if !self.flags.contains(Flags::ALPHA_IS_NONE) {
+            let hue_index = self.space.hue_index();
+            for i in 0..3 {
+                if hue_index != Some(i) {
+                    components[i] *= self.alpha;
+                }
+            }
         }
 
         Premultiplied {
-            components: [c!(self.c0(), 0), c!(self.c1(), 1), c!(self.c2(), 2)],
+            components,
+            flags: self.flags.difference(Flags::ALPHA_IS_NONE),
             alpha: self.alpha(),
+            space: self.space,
         }
     }
 }
@@ -64,9 +102,32 @@ pub enum HueInterpolationMethod {
     /// color to the second, the angle is always decreasing.
     /// <https://drafts.csswg.org/css-color-4/#hue-decreasing>
     Decreasing,
+    /// Hue angles are normalized to [0..360] and then offset by an explicit
+    /// number of degrees for each end, e.g. an `end_offset` of 360.0 makes
+    /// the hue wind around once more. Used to reproduce the exact path of a
+    /// previously authored (serialized) interpolation.
+    Specified {
+        /// The offset in degrees added to the starting hue.
+        start_offset: Component,
+        /// The offset in degrees added to the ending hue.
+        end_offset: Component,
+    },
 }
 
 impl HueInterpolationMethod {
+    /// Return the CSS keyword for this hue interpolation method, e.g.
+    /// `"longer"`. [`HueInterpolationMethod::Specified`] has no CSS keyword
+    /// and returns None.
+    pub fn name(&self) -> Option<&'static str> {
+        match self {
+            HueInterpolationMethod::Shorter => Some("shorter"),
+            HueInterpolationMethod::Longer => Some("longer"),
+            HueInterpolationMethod::Increasing => Some("increasing"),
+            HueInterpolationMethod::Decreasing => Some("decreasing"),
+            HueInterpolationMethod::Specified { .. } => None,
+        }
+    }
+
     fn adjust_hue(&self, a: &mut Component, b: &mut Component) {
         debug_assert!(!a.is_nan());
         debug_assert!(!b.is_nan());
@@ -102,67 +163,326 @@ impl HueInterpolationMethod {
                     *a += 360.0;
                 }
             }
+            HueInterpolationMethod::Specified {
+                start_offset,
+                end_offset,
+            } => {
+                *a += start_offset;
+                *b += end_offset;
+            }
+        }
+    }
+}
+
+/// Writes the CSS `<hue-interpolation-method>`, e.g. `longer hue`.
+///
+/// NOTE: [`HueInterpolationMethod::Specified`] is written with its offsets,
+///       e.g. `specified hue(0, 360)`, which is not valid CSS.
+impl std::fmt::Display for HueInterpolationMethod {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HueInterpolationMethod::Specified {
+                start_offset,
+                end_offset,
+            } => write!(f, "specified hue({}, {})", start_offset, end_offset),
+            _ => write!(f, "{} hue", self.name().unwrap_or_default()),
+        }
+    }
+}
+
+/// Error returned when parsing an unknown hue interpolation method.
+#[derive(Clone, Debug, PartialEq)]
+pub struct UnknownHueInterpolationMethod {
+    /// The input that could not be parsed.
+    pub input: String,
+}
+
+impl std::fmt::Display for UnknownHueInterpolationMethod {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "unknown hue interpolation method \"{}\"", self.input)
+    }
+}
+
+impl std::error::Error for UnknownHueInterpolationMethod {}
+
+/// Parses a CSS `<hue-interpolation-method>`, e.g. `longer hue`. The `hue`
+/// keyword is optional and keywords are ASCII case-insensitive.
+impl std::str::FromStr for HueInterpolationMethod {
+    type Err = UnknownHueInterpolationMethod;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut words = s.split_ascii_whitespace();
+        let keyword = words.next().unwrap_or_default();
+        let hue = words.next();
+
+        let method = match keyword.to_ascii_lowercase().as_str() {
+            "shorter" => HueInterpolationMethod::Shorter,
+            "longer" => HueInterpolationMethod::Longer,
+            "increasing" => HueInterpolationMethod::Increasing,
+            "decreasing" => HueInterpolationMethod::Decreasing,
+            _ => {
+                return Err(UnknownHueInterpolationMethod {
+                    input: s.to_string(),
+                })
+            }
+        };
+
+        match (hue, words.next()) {
+            (None, None) => Ok(method),
+            (Some(hue), None) if hue.eq_ignore_ascii_case("hue") => Ok(method),
+            _ => Err(UnknownHueInterpolationMethod {
+                input: s.to_string(),
+            }),
         }
     }
 }
 
 /// A structure storing a color that was pre-multiplied with its `alpha`
-/// component.
-#[derive(Clone)]
-struct Premultiplied {
+/// component. Created with [`Color::premultiply`] and converted back with
+/// [`Premultiplied::into_color`].
+#[derive(Clone, Debug)]
+pub struct Premultiplied {
     /// Components from the source color with each multiplied by the original
-    /// alpha value.
-    components: [Option<Component>; 3],
-    /// The original alpha value.
-    alpha: Option<Component>,
+    /// alpha value. Hue components are not multiplied.
+    pub components: Components,
+    /// Flags marking which of the components are missing. The alpha
+    /// component is never marked as missing, see `alpha`.
+    pub flags: Flags,
+    /// The original alpha value, or None if it is missing.
+    pub alpha: Option<Component>,
+    /// The color space of the components.
+    pub space: Space,
 }
 
+/// The flags marking each component as missing, in component order.
+const COMPONENT_FLAGS: [Flags; 3] = [Flags::C0_IS_NONE, Flags::C1_IS_NONE, Flags::C2_IS_NONE];
+
 impl Premultiplied {
-    /// Un-premultiply the components back into a color using the specified
-    /// alpha value.
+    /// Return the component at `index`, or None if it is missing.
+    pub fn component(&self, index: usize) -> Option<Component> {
+        if self.flags.contains(COMPONENT_FLAGS[index]) {
+            None
+        } else {
+            Some(self.components[index])
+        }
+    }
+
+    /// Un-premultiply the components back into a color using the alpha
+    /// value. If the alpha value is missing or 0, the components are not
+    /// divided.
     /// <https://drafts.csswg.org/css-color-4/#interpolation-alpha>
-    fn into_color(self, space: Space, alpha: Option<Component>) -> Color {
-        let alpha = match alpha {
-            Some(alpha) if alpha != 0.0 => alpha,
-            _ => {
-                return Color::new(
-                    space,
-                    self.components[0],
-                    self.components[1],
-                    self.components[2],
-                    alpha,
-                );
+    pub fn into_color(self) -> Color {
+        let Premultiplied {
+            mut components,
+            alpha,
+            space,
+            ..
+        } = self;
+
+        if let Some(alpha) = alpha.filter(|alpha| *alpha != 0.0) {
+            let hue_index = space.hue_index();
+            for i in 0..3 {
+                if hue_index != Some(i) {
+                    components[i] /= alpha;
+                }
+            }
+        }
+
+        let c = |i: usize| {
+            if self.flags.contains(COMPONENT_FLAGS[i]) {
+                None
+            } else {
+                Some(components[i])
             }
         };
 
-        let hue_index = space.hue_index();
+        Color::new(space, c(0), c(1), c(2), alpha)
+    }
+}
 
-        macro_rules! c {
-            ($i:literal) => {{
-                if hue_index == Some($i) {
-                    self.components[$i]
-                } else {
-                    self.components[$i].map(|v| v / alpha)
-                }
-            }};
+/// Calculate the weighted mean of the values that are present, with the
+/// weights of the present values renormalized. If the weights of all the
+/// present values are zero, the plain mean is used. Returns None if all the
+/// values are missing.
+fn weighted_mean(
+    values: impl Iterator<Item = (Option<Component>, Component)>,
+) -> Option<Component> {
+    let (mut sum, mut total_weight, mut plain_sum, mut count) = (0.0, 0.0, 0.0, 0);
+    for (value, weight) in values {
+        if let Some(value) = value {
+            sum += value * weight;
+            total_weight += weight;
+            plain_sum += value;
+            count += 1;
         }
+    }
 
-        Color::new(space, c!(0), c!(1), c!(2), alpha)
+    match count {
+        0 => None,
+        _ if total_weight == 0.0 => Some(plain_sum / count as Component),
+        _ => Some(sum / total_weight),
     }
 }
 
+/// Mix any number of colors with the given weights in the specified color
+/// space. This generalizes [`Interpolation::with_normalized_weights`] to more
+/// than two colors:
+///
+/// - The weights are normalized to sum to 1. If they sum to less than 1, the
+///   resulting alpha is multiplied by the sum.
+/// - The colors are premultiplied with their alpha before mixing. A missing
+///   alpha takes the weighted mean of the alphas that are present.
+/// - Missing components are skipped and the weights of the colors that have
+///   the component are renormalized. If a component is missing on all the
+///   colors, it is missing in the result.
+/// - Hues are unwrapped along the shorter arc from the first hue that is
+///   present, then averaged. For two colors this is the same as
+///   [`HueInterpolationMethod::Shorter`].
+///
+/// Returns None if `colors` is empty or the weights do not sum to more than
+/// zero.
+/// <https://drafts.csswg.org/css-color-5/#color-mix-percent-norm>
+pub fn mix_many(colors: &[(Color, Component)], space: Space) -> Option<Color> {
+    let sum: Component = colors.iter().map(|(_, weight)| weight).sum();
+    if colors.is_empty() || sum <= 0.0 {
+        return None;
+    }
+    let alpha_multiplier = sum.min(1.0);
+
+    let mut colors = colors
+        .iter()
+        .map(|(color, weight)| (color.to_space(space), weight / sum))
+        .collect::<Vec<_>>();
+
+    // Replace missing alpha values with the mean of the present ones.
+    let alpha = weighted_mean(
+        colors
+            .iter()
+            .map(|(color, weight)| (color.alpha(), *weight)),
+    );
+    if let Some(alpha) = alpha {
+        for (color, _) in colors
+            .iter_mut()
+            .filter(|(color, _)| color.alpha().is_none())
+        {
+            color.flags.remove(Flags::ALPHA_IS_NONE);
+            color.alpha = alpha;
+        }
+    }
+
+    // Replace missing components with the mean of the present ones before
+    // premultiplying, like `Interpolation` carries them over from the other
+    // side. The mean is weighted with alpha, so the premultiplied mix of a
+    // component is the mix of the colors that have it. Hues are not
+    // premultiplied, so they are skipped when mixing instead.
+    for (i, flag) in COMPONENT_FLAGS.into_iter().enumerate() {
+        if space.hue_index() == Some(i) {
+            continue;
+        }
+
+        let present = colors.iter().map(|(color, weight)| {
+            let value = (!color.flags.contains(flag)).then_some(color.components[i]);
+            (value, weight * color.alpha().unwrap_or(1.0))
+        });
+        if let Some(value) = weighted_mean(present) {
+            for (color, _) in colors
+                .iter_mut()
+                .filter(|(color, _)| color.flags.contains(flag))
+            {
+                color.flags.remove(flag);
+                color.components[i] = value;
+            }
+        }
+    }
+
+    let premultiplied = colors
+        .into_iter()
+        .map(|(color, weight)| (color.premultiply(), weight))
+        .collect::<Vec<_>>();
+
+    let mut result = Premultiplied {
+        components: Components(0.0, 0.0, 0.0),
+        flags: Flags::empty(),
+        alpha: alpha.map(|alpha| alpha.clamp(0.0, 1.0)),
+        space,
+    };
+    for (i, flag) in COMPONENT_FLAGS.into_iter().enumerate() {
+        let values = premultiplied
+            .iter()
+            .map(|(p, weight)| (p.component(i), *weight));
+
+        let value = if space.hue_index() == Some(i) {
+            let first = premultiplied
+                .iter()
+                .find_map(|(p, _)| p.component(i))
+                .map(normalize_hue)
+                .unwrap_or_default();
+            let unwrap = |hue: Component| {
+                first + ((normalize_hue(hue) - first + 180.0).rem_euclid(360.0) - 180.0)
+            };
+            weighted_mean(values.map(|(hue, weight)| (hue.map(unwrap), weight))).map(normalize_hue)
+        } else {
+            weighted_mean(values)
+        };
+
+        match value {
+            Some(value) => result.components[i] = value,
+            None => result.flags.insert(flag),
+        }
+    }
+
+    let mut result = result.into_color();
+    result.alpha *= alpha_multiplier;
+    Some(result)
+}
+
+/// Interpolate between the three colors at the corners of a triangle with
+/// the barycentric coordinates `bary`, e.g. for Gouraud shading. The colors
+/// are mixed in the specified color space with [`mix_many`], using the
+/// barycentric coordinates as weights.
+///
+/// Returns None if the barycentric coordinates do not sum to more than zero.
+pub fn interpolate_triangle(
+    a: &Color,
+    b: &Color,
+    c: &Color,
+    bary: (Component, Component, Component),
+    space: Space,
+) -> Option<Color> {
+    mix_many(
+        &[
+            (a.clone(), bary.0),
+            (b.clone(), bary.1),
+            (c.clone(), bary.2),
+        ],
+        space,
+    )
+}
+
 /// Represents an interpolation between two colors using a specified color space.
 #[derive(Clone)]
 pub struct Interpolation {
-    /// The color that will be interpolated from.
-    left: Premultiplied,
-    /// The color that will be interpolated to.
-    right: Premultiplied,
+    /// The color that will be interpolated from, in the interpolation color
+    /// space.
+    left: Color,
+    /// The color that will be interpolated to, in the interpolation color
+    /// space.
+    right: Color,
     /// The color space/form used to interpolate between the two colors.
     pub space: Space,
     /// Which hue interpolation method to use when a hue component is present
     /// in the color space/from used for interpolation.
     pub hue_interpolation_method: HueInterpolationMethod,
+    /// The position in [0..1] where the interpolation reaches the halfway
+    /// point between the two colors. Defaults to 0.5.
+    pub midpoint: Component,
+    /// Whether the components are premultiplied with alpha before they are
+    /// interpolated, as CSS does. Defaults to true. Set with
+    /// [`Interpolation::with_premultiply`], which keeps `prepared` in sync.
+    premultiply: bool,
+    /// The left and right colors, prepared for interpolation once, so that
+    /// sampling many colors does not premultiply them on every call.
+    prepared: (Premultiplied, Premultiplied),
 }
 
 impl Interpolation {
@@ -172,8 +492,24 @@ impl Interpolation {
         // powerless component handling.
 
         // Convert both sides into the interpolation color space.
-        let mut left = left.to_space(space);
-        let mut right = right.to_space(space);
+        Self::from_converted(left.to_space(space), right.to_space(space), space)
+    }
+
+    /// Create a new interpolation with colors that are already in the
+    /// interpolation color space, which skips converting them. Useful when
+    /// the same colors are used in many interpolations.
+    ///
+    /// NOTE: Both colors must be in `space`, which is only checked in debug
+    ///       builds.
+    pub fn from_converted(mut left: Color, mut right: Color, space: Space) -> Self {
+        debug_assert_eq!(
+            left.space, space,
+            "left color is not in the interpolation space"
+        );
+        debug_assert_eq!(
+            right.space, space,
+            "right color is not in the interpolation space"
+        );
 
         // Replace alpha none values with those from the other side.
         match (left.alpha(), right.alpha()) {
@@ -193,11 +529,32 @@ impl Interpolation {
                 || left.alpha().is_some() && right.alpha().is_some()
         );
 
+        // Replace missing components with those from the other side, before
+        // they are premultiplied with alpha.
+        for (i, flag) in COMPONENT_FLAGS.into_iter().enumerate() {
+            match (left.flags.contains(flag), right.flags.contains(flag)) {
+                (true, false) => {
+                    left.flags.remove(flag);
+                    left.components[i] = right.components[i];
+                }
+                (false, true) => {
+                    right.flags.remove(flag);
+                    right.components[i] = left.components[i];
+                }
+                _ => {}
+            }
+        }
+
+        let prepared = (Self::prepare(&left, true), Self::prepare(&right, true));
+
         Self {
-            left: left.premultiply(),
-            right: right.premultiply(),
+            left,
+            right,
             space,
             hue_interpolation_method: Default::default(),
+            midpoint: 0.5,
+            premultiply: true,
+            prepared,
         }
     }
 
@@ -209,6 +566,66 @@ impl Interpolation {
         }
     }
 
+    /// Set the position where the interpolation reaches the halfway point
+    /// between the two colors, like a CSS gradient color hint.
+    /// <https://drafts.csswg.org/css-images-4/#color-interpolation-hint>
+    pub fn with_midpoint(self, position: Component) -> Self {
+        Self {
+            midpoint: position,
+            ..self
+        }
+    }
+
+    /// Enable or disable premultiplying the components with alpha before
+    /// interpolating. When disabled (straight alpha), the components are
+    /// interpolated independently of alpha.
+    /// <https://drafts.csswg.org/css-color-4/#interpolation-alpha>
+    pub fn with_premultiply(self, enabled: bool) -> Self {
+        let prepared = (
+            Self::prepare(&self.left, enabled),
+            Self::prepare(&self.right, enabled),
+        );
+
+        Self {
+            premultiply: enabled,
+            prepared,
+            ..self
+        }
+    }
+
+    /// Prepare a color for interpolation, premultiplying it with its alpha
+    /// if `premultiply` is true.
+    fn prepare(color: &Color, premultiply: bool) -> Premultiplied {
+        if premultiply {
+            return color.premultiply();
+        }
+
+        Premultiplied {
+            components: color.components,
+            flags: color.flags.difference(Flags::ALPHA_IS_NONE),
+            alpha: color.alpha(),
+            space: color.space,
+        }
+    }
+
+    /// Remap `t` according to the midpoint. Values outside of (0..1) are not
+    /// affected.
+    fn apply_midpoint(&self, t: Component) -> Component {
+        if self.midpoint == 0.5 || t <= 0.0 || t >= 1.0 {
+            return t;
+        }
+
+        if self.midpoint <= 0.0 {
+            return 1.0;
+        }
+
+        if self.midpoint >= 1.0 {
+            return 0.0;
+        }
+
+        t.powf((0.5 as Component).ln() / self.midpoint.ln())
+    }
+
     /// Calculate an interpolated color using weights for the left and right
     /// sides. The weights are normalized, before interpolation according to:
     /// <https://drafts.csswg.org/css-color-5/#color-mix-percent-norm>
@@ -239,11 +656,19 @@ impl Interpolation {
 
     /// Calculate an interpolated color using weights for the left and right
     /// sides.
+    ///
+    /// If a component is missing on only one side, the value from the other
+    /// side is used for both sides. If a component is missing on both sides,
+    /// it is missing in the result. The same applies to the alpha component,
+    /// e.g. mixing two colors with a missing alpha yields a missing alpha.
+    /// <https://drafts.csswg.org/css-color-4/#interpolation-missing>
     pub fn with_weights(&self, left_weight: Component, right_weight: Component) -> Color {
         // Interpolate the original alpha components.
         // TODO: This is essentially the same code used for each component,
         // can we somehow not duplicate it here.
-        let alpha = match (self.left.alpha, self.right.alpha) {
+        let (left, right) = &self.prepared;
+
+        let alpha = match (left.alpha, right.alpha) {
             (None, None) => None,
             (Some(left), Some(right)) => {
                 Some((left * left_weight + right * right_weight).clamp(0.0, 1.0))
@@ -257,15 +682,18 @@ impl Interpolation {
 
         // Interpolate the premultiplied components.
         let mut result = Premultiplied {
-            components: [None, None, None],
+            components: Components(0.0, 0.0, 0.0),
+            flags: Flags::empty(),
             alpha: None,
+            space: self.space,
         };
-        result.components.iter_mut().enumerate().for_each(|(i, r)| {
-            *r = match (self.left.components[i], self.right.components[i]) {
-                (None, None) => None,
-                (None, Some(right)) => Some(right),
-                (Some(left), None) => Some(left),
-                (Some(left), Some(right)) => Some(match self.space.hue_index() {
+        for (i, flag) in COMPONENT_FLAGS.into_iter().enumerate() {
+            result.components[i] = match (left.component(i), right.component(i)) {
+                (None, None) => {
+                    result.flags.insert(flag);
+                    0.0
+                }
+                (Some(left), Some(right)) => match self.space.hue_index() {
                     Some(index) if index == i => {
                         let (mut left, mut right) = (left, right);
                         self.hue_interpolation_method
@@ -273,15 +701,44 @@ impl Interpolation {
                         normalize_hue(left * left_weight + right * right_weight)
                     }
                     _ => left * left_weight + right * right_weight,
-                }),
+                },
+                _ => {
+                    // Components missing on one side were replaced with those
+                    // from the other side in `Interpolation::from_converted`.
+                    unreachable!()
+                }
             };
-        });
+        }
+
+        if self.premultiply {
+            result.alpha = alpha;
+            return result.into_color();
+        }
 
-        result.into_color(self.space, alpha)
+        let mut color = result.into_color();
+        if let Some(alpha) = alpha {
+            color.alpha = alpha;
+            color.flags.remove(Flags::ALPHA_IS_NONE);
+        }
+        color
     }
 
     /// Calculate an interpolated color using a mid point specified by `t`.
+    /// Values of `t` outside of [0..1] are clamped, see
+    /// [`Interpolation::at_extrapolated`] to extrapolate instead.
     pub fn at(&self, t: Component) -> Color {
+        self.at_extrapolated(t.clamp(0.0, 1.0))
+    }
+
+    /// Calculate a color at `t`, where `t` is allowed to fall outside of
+    /// [0..1] to extrapolate past either end of the interpolation.
+    ///
+    /// Components are extrapolated linearly. Hue components continue along
+    /// the arc chosen by the [`HueInterpolationMethod`], i.e. with the shorter
+    /// arc from 350deg to 10deg, a `t` of 1.5 yields 20deg and a `t` of -0.5
+    /// yields 340deg. The alpha component is clamped to [0..1].
+    pub fn at_extrapolated(&self, t: Component) -> Color {
+        let t = self.apply_midpoint(t);
         self.with_weights(1.0 - t, t)
     }
 }
@@ -298,12 +755,20 @@ impl Space {
             | Space::Rec2020 => true,
             Space::Hsl
             | Space::Hwb
+            | Space::Hsv
             | Space::Lab
             | Space::Lch
             | Space::Oklab
             | Space::Oklch
             | Space::XyzD50
-            | Space::XyzD65 => false,
+            | Space::XyzD65
+            | Space::Hct
+            | Space::LabD65
+            | Space::LchD65
+            | Space::Jzazbz
+            | Space::Jzczhz
+            | Space::Ipt
+            | Space::ICtCp => false,
         }
     }
 
@@ -316,6 +781,7 @@ impl Space {
             | Space::SrgbLinear
             | Space::Hsl
             | Space::Hwb
+            | Space::Hsv
             | Space::Lab
             | Space::Lch
             | Space::Oklab
@@ -323,22 +789,37 @@ impl Space {
             | Space::DisplayP3
             | Space::A98Rgb
             | Space::ProPhotoRgb
-            | Space::Rec2020 => false,
+            | Space::Rec2020
+            | Space::Hct
+            | Space::LabD65
+            | Space::LchD65
+            | Space::Jzazbz
+            | Space::Jzczhz
+            | Space::Ipt
+            | Space::ICtCp => false,
         }
     }
 
     /// Returns the index of a hue component, otherwise None if the color does
     /// not have a hue component.
-    fn hue_index(&self) -> Option<usize> {
+    pub(crate) fn hue_index(&self) -> Option<usize> {
         match self {
             Space::Hsl => Some(0),
             Space::Hwb => Some(0),
+            Space::Hsv => Some(0),
+            Space::Hct => Some(0),
             Space::Lch => Some(2),
+            Space::LchD65 => Some(2),
+            Space::Jzczhz => Some(2),
             Space::Oklch => Some(2),
             Space::Srgb
             | Space::SrgbLinear
             | Space::Lab
+            | Space::LabD65
             | Space::Oklab
+            | Space::Jzazbz
+            | Space::Ipt
+            | Space::ICtCp
             | Space::XyzD50
             | Space::XyzD65
             | Space::DisplayP3
@@ -384,14 +865,14 @@ fn _analogous_missing_components(from: Space, to: Space, flags: Flags) -> Flags
     }
 
     // Hue              H
-    if matches!(from, Space::Hsl | Space::Hwb) {
-        if matches!(to, Space::Hsl | Space::Hwb) {
+    if matches!(from, Space::Hsl | Space::Hwb | Space::Hsv) {
+        if matches!(to, Space::Hsl | Space::Hwb | Space::Hsv) {
             result.set(Flags::C0_IS_NONE, flags.contains(Flags::C0_IS_NONE));
         } else if matches!(to, Space::Lch | Space::Oklch) {
             result.set(Flags::C2_IS_NONE, flags.contains(Flags::C0_IS_NONE));
         }
     } else if matches!(from, Space::Lch | Space::Oklch) {
-        if matches!(to, Space::Hsl | Space::Hwb) {
+        if matches!(to, Space::Hsl | Space::Hwb | Space::Hsv) {
             result.set(Flags::C0_IS_NONE, flags.contains(Flags::C2_IS_NONE));
         } else if matches!(to, Space::Lch | Space::Oklch) {
             result.set(Flags::C2_IS_NONE, flags.contains(Flags::C2_IS_NONE));
@@ -539,25 +1020,304 @@ mod tests {
 
         let decreasing = interp.clone().with_hue_interpolation(H::Decreasing);
         assert_component_eq!(decreasing.at(0.5).components.0, 10.0);
+
+        // 50deg to 330deg + 360deg winds around more than once.
+        let specified = interp.clone().with_hue_interpolation(H::Specified {
+            start_offset: 0.0,
+            end_offset: 360.0,
+        });
+        assert_component_eq!(specified.at(0.5).components.0, 10.0);
+        assert_component_eq!(specified.at(0.25).components.0, 210.0);
+
+        // Without offsets the hues are interpolated as is.
+        let specified = interp.clone().with_hue_interpolation(H::Specified {
+            start_offset: 0.0,
+            end_offset: 0.0,
+        });
+        assert_component_eq!(specified.at(0.5).components.0, 190.0);
+    }
+
+    #[test]
+    fn hue_interpolation_method_names() {
+        use HueInterpolationMethod as H;
+
+        for method in [H::Shorter, H::Longer, H::Increasing, H::Decreasing] {
+            let name = method.name().unwrap();
+            assert_eq!(method.to_string(), format!("{} hue", name));
+            assert_eq!(name.parse::<H>().unwrap().name(), Some(name));
+            assert_eq!(method.to_string().parse::<H>().unwrap().name(), Some(name));
+        }
+
+        assert!(matches!("LONGER Hue".parse::<H>(), Ok(H::Longer)));
+        assert!("sideways hue".parse::<H>().is_err());
+        assert!("shorter saturation".parse::<H>().is_err());
+        assert!("shorter hue please".parse::<H>().is_err());
+        assert!("".parse::<H>().is_err());
+
+        let specified = H::Specified {
+            start_offset: 0.0,
+            end_offset: 360.0,
+        };
+        assert_eq!(specified.name(), None);
+        assert_eq!(specified.to_string(), "specified hue(0, 360)");
+    }
+
+    #[test]
+    fn extrapolate_past_both_ends() {
+        use HueInterpolationMethod as H;
+
+        let left = Color::new(Space::Srgb, 0.2, 0.4, 0.6, 1.0);
+        let right = Color::new(Space::Srgb, 0.4, 0.6, 0.8, 1.0);
+        let interp = left.interpolate(&right, Space::Srgb);
+
+        let result = interp.at_extrapolated(1.5);
+        assert_component_eq!(result.components.0, 0.5);
+        assert_component_eq!(result.components.1, 0.7);
+        assert_component_eq!(result.components.2, 0.9);
+
+        let result = interp.at_extrapolated(-0.5);
+        assert_component_eq!(result.components.0, 0.1);
+        assert_component_eq!(result.components.1, 0.3);
+        assert_component_eq!(result.components.2, 0.5);
+
+        // `at` clamps to the ends instead.
+        assert_eq!(interp.at(1.5), interp.at(1.0));
+        assert_eq!(interp.at(-0.5), interp.at(0.0));
+
+        // Hue continues along the chosen arc.
+        let left = Color::new(Space::Oklch, 0.5, 0.1, 350.0, 1.0);
+        let right = Color::new(Space::Oklch, 0.5, 0.1, 10.0, 1.0);
+        let interp = left.interpolate(&right, Space::Oklch);
+
+        let shorter = interp.clone().with_hue_interpolation(H::Shorter);
+        assert_component_eq!(shorter.at_extrapolated(1.5).components.2, 20.0);
+        assert_component_eq!(shorter.at_extrapolated(-0.5).components.2, 340.0);
+
+        let longer = interp.with_hue_interpolation(H::Longer);
+        assert_component_eq!(longer.at_extrapolated(1.5).components.2, 200.0);
+        assert_component_eq!(longer.at_extrapolated(-0.5).components.2, 160.0);
+    }
+
+    #[test]
+    fn lerp_towards_target() {
+        let target = Color::new(Space::Oklch, 0.7, 0.1, 20.0, 1.0);
+        let mut color = Color::new(Space::Srgb, 0.2, 0.4, 0.6, 1.0);
+
+        color.lerp_towards(&target, Space::Oklch, 0.5);
+        assert_eq!(color.space, Space::Oklch);
+
+        // Crossing 0deg along the shorter arc never moves the hue away from
+        // the target.
+        let mut color = Color::new(Space::Oklch, 0.5, 0.1, 320.0, 1.0);
+        let mut distance = 60.0;
+        for _ in 0..50 {
+            color.lerp_towards(&target, Space::Oklch, 0.25);
+            let hue = color.components.2;
+            let d = (target.components.2 - hue).rem_euclid(360.0);
+            assert!(d <= distance);
+            distance = d;
+        }
+        assert!(distance < 1.0e-3);
+        assert!((color.components.0 - 0.7).abs() < 1.0e-3);
+    }
+
+    #[test]
+    fn mix_many_matches_two_color_interpolation() {
+        let pairs = [
+            (
+                Color::new(Space::Srgb, 0.2, 0.4, 0.6, 0.5),
+                Color::new(Space::Srgb, 0.9, 0.1, 0.3, 1.0),
+            ),
+            (
+                Color::new(Space::Oklch, 0.5, 0.1, 350.0, None),
+                Color::new(Space::Oklch, 0.7, None, 20.0, 0.25),
+            ),
+            (
+                Color::new(Space::Hsl, 120.0, 0.3, None, 0.8),
+                Color::new(Space::Hsl, None, 0.7, None, 0.4),
+            ),
+        ];
+
+        for (left, right) in pairs {
+            let space = left.space;
+            let expected = left.interpolate(&right, space).at(0.75);
+            let mixed = mix_many(&[(left, 0.25), (right, 0.75)], space).unwrap();
+
+            assert_eq!(mixed.flags, expected.flags);
+            assert_component_eq!(mixed.components.0, expected.components.0);
+            assert_component_eq!(mixed.components.1, expected.components.1);
+            assert_component_eq!(mixed.components.2, expected.components.2);
+            assert_component_eq!(mixed.alpha, expected.alpha);
+        }
+    }
+
+    #[test]
+    fn mix_two_colors() {
+        let red = Color::new(Space::Srgb, 1.0, 0.0, 0.0, 1.0);
+        let blue = Color::new(Space::Srgb, 0.0, 0.0, 1.0, 1.0);
+
+        // color-mix(in srgb, red, blue 25%)
+        let mixed = red.mix(&blue, Space::Srgb, 0.25);
+        assert_eq!(mixed.space, Space::Srgb);
+        assert_component_eq!(mixed.components.0, 0.75);
+        assert_component_eq!(mixed.components.2, 0.25);
+        assert_component_eq!(mixed.alpha, 1.0);
+        assert_eq!(mixed, red.interpolate(&blue, Space::Srgb).at(0.25));
+
+        // color-mix(in srgb, red 30%, blue 30%)
+        let mixed = red.mix_weighted(&blue, Space::Srgb, 0.3, 0.3);
+        assert_component_eq!(mixed.components.0, 0.5);
+        assert_component_eq!(mixed.components.2, 0.5);
+        assert_component_eq!(mixed.alpha, 0.6);
+
+        // color-mix(in hsl, hsl(120deg 10% 20%) 12.5%, hsl(30deg 30% 40%) 37.5%)
+        let mixed = Color::new(Space::Hsl, 120.0, 0.1, 0.2, 1.0).mix_weighted(
+            &Color::new(Space::Hsl, 30.0, 0.3, 0.4, 1.0),
+            Space::Hsl,
+            0.125,
+            0.375,
+        );
+        assert_component_eq!(mixed.components.0, 52.5);
+        assert_component_eq!(mixed.components.1, 0.25);
+        assert_component_eq!(mixed.components.2, 0.35);
+        assert_component_eq!(mixed.alpha, 0.5);
+    }
+
+    #[test]
+    fn mix_many_colors() {
+        let colors = [
+            (Color::new(Space::Srgb, 1.0, 0.0, None, 1.0), 0.2),
+            (Color::new(Space::Srgb, 0.0, 1.0, 0.5, 1.0), 0.2),
+            (Color::new(Space::Srgb, 0.0, 0.0, 1.0, 1.0), 0.1),
+        ];
+
+        let mixed = mix_many(&colors, Space::Srgb).unwrap();
+        assert_component_eq!(mixed.components.0, 0.4);
+        assert_component_eq!(mixed.components.1, 0.4);
+        // Blue is only present on the last two colors.
+        assert_component_eq!(mixed.components.2, 2.0 / 3.0);
+        // The weights sum to 0.5.
+        assert_component_eq!(mixed.alpha, 0.5);
+
+        // Hues are averaged along the shorter arcs.
+        let hues =
+            [340.0, 0.0, 20.0].map(|hue| (Color::new(Space::Oklch, 0.5, 0.1, hue, 1.0), 1.0));
+        let mixed = mix_many(&hues, Space::Oklch).unwrap();
+        assert_component_eq!(mixed.components.2, 0.0);
+        assert_component_eq!(mixed.alpha, 1.0);
+
+        // Missing components are filled in before premultiplying, so they
+        // are not affected by the alpha of the other colors.
+        let colors = [
+            (Color::new(Space::Srgb, None, 0.0, 0.0, 0.2), 1.0),
+            (Color::new(Space::Srgb, 1.0, 0.0, 0.0, 1.0), 1.0),
+        ];
+        let mixed = mix_many(&colors, Space::Srgb).unwrap();
+        assert_component_eq!(mixed.components.0, 1.0);
+        assert_component_eq!(mixed.alpha, 0.6);
+
+        let colors = [
+            (Color::new(Space::Srgb, None, 0.0, 0.0, 0.2), 1.0),
+            (Color::new(Space::Srgb, 1.0, 0.0, 0.0, 1.0), 1.0),
+            (Color::new(Space::Srgb, 0.5, 0.0, 0.0, 0.5), 2.0),
+        ];
+        let mixed = mix_many(&colors, Space::Srgb).unwrap();
+        // (1.0 * 1.0 + 0.5 * 0.5 * 2.0) / (1.0 + 0.5 * 2.0)
+        assert_component_eq!(mixed.components.0, 0.75);
+
+        assert!(mix_many(&[], Space::Srgb).is_none());
+        assert!(mix_many(
+            &[(Color::new(Space::Srgb, 1.0, 0.0, 0.0, 1.0), 0.0)],
+            Space::Srgb
+        )
+        .is_none());
+    }
+
+    #[test]
+    fn interpolate_triangle_corners_and_center() {
+        let a = Color::new(Space::Srgb, 1.0, 0.0, 0.0, 1.0);
+        let b = Color::new(Space::Srgb, 0.0, 1.0, 0.0, 1.0);
+        let c = Color::new(Space::Srgb, 0.0, 0.0, 1.0, 0.0);
+
+        let corner = interpolate_triangle(&a, &b, &c, (0.0, 1.0, 0.0), Space::Srgb).unwrap();
+        assert_component_eq!(corner.components.0, 0.0);
+        assert_component_eq!(corner.components.1, 1.0);
+        assert_component_eq!(corner.alpha, 1.0);
+
+        // The transparent corner does not contribute to the color.
+        let third = 1.0 / 3.0;
+        let center = interpolate_triangle(&a, &b, &c, (third, third, third), Space::Srgb).unwrap();
+        assert_component_eq!(center.components.0, 0.5);
+        assert_component_eq!(center.components.1, 0.5);
+        assert_component_eq!(center.components.2, 0.0);
+        assert_component_eq!(center.alpha, 2.0 / 3.0);
+
+        assert!(interpolate_triangle(&a, &b, &c, (0.0, 0.0, 0.0), Space::Srgb).is_none());
+    }
+
+    #[test]
+    fn midpoint_hint() {
+        let left = Color::new(Space::Srgb, 0.0, 0.0, 0.0, 1.0);
+        let right = Color::new(Space::Srgb, 1.0, 1.0, 1.0, 1.0);
+        let interp = left.interpolate(&right, Space::Srgb).with_midpoint(0.25);
+
+        assert_component_eq!(interp.at(0.0).components.0, 0.0);
+        assert_component_eq!(interp.at(0.25).components.0, 0.5);
+        assert_component_eq!(interp.at(1.0).components.0, 1.0);
+
+        // Hints at the edges jump straight to the other color.
+        let interp = interp.with_midpoint(0.0);
+        assert_component_eq!(interp.at(0.1).components.0, 1.0);
+        let interp = interp.with_midpoint(1.0);
+        assert_component_eq!(interp.at(0.9).components.0, 0.0);
     }
 
     #[test]
     fn test_premultiplied() {
         // rgb(24% 12% 98% / 0.4) => [9.6% 4.8% 39.2%]
         let left = Color::new(Space::Srgb, 0.24, 0.12, 0.98, 0.4).premultiply();
-        assert_component_eq!(left.components[0].unwrap(), 0.096);
-        assert_component_eq!(left.components[1].unwrap(), 0.048);
-        assert_component_eq!(left.components[2].unwrap(), 0.392);
+        assert_component_eq!(left.component(0).unwrap(), 0.096);
+        assert_component_eq!(left.component(1).unwrap(), 0.048);
+        assert_component_eq!(left.component(2).unwrap(), 0.392);
         assert_component_eq!(left.alpha.unwrap(), 0.4);
 
         // rgb(62% 26% 64% / 0.6) => [37.2% 15.6% 38.4%]
         let right = Color::new(Space::Srgb, 0.62, 0.26, 0.64, 0.6).premultiply();
-        assert_component_eq!(right.components[0].unwrap(), 0.372);
-        assert_component_eq!(right.components[1].unwrap(), 0.156);
-        assert_component_eq!(right.components[2].unwrap(), 0.384);
+        assert_component_eq!(right.component(0).unwrap(), 0.372);
+        assert_component_eq!(right.component(1).unwrap(), 0.156);
+        assert_component_eq!(right.component(2).unwrap(), 0.384);
         assert_component_eq!(right.alpha.unwrap(), 0.6);
     }
 
+    #[test]
+    fn premultiplied_round_trip() {
+        // Hues are not premultiplied.
+        let oklch = Color::new(Space::Oklch, 0.6, 0.2, 120.0, 0.5);
+        let premultiplied = oklch.premultiply();
+        assert_eq!(premultiplied.space, Space::Oklch);
+        assert_component_eq!(premultiplied.component(0).unwrap(), 0.3);
+        assert_component_eq!(premultiplied.component(1).unwrap(), 0.1);
+        assert_component_eq!(premultiplied.component(2).unwrap(), 120.0);
+
+        let back = premultiplied.into_color();
+        assert_eq!(back.space, Space::Oklch);
+        assert_component_eq!(back.components.0, 0.6);
+        assert_component_eq!(back.components.1, 0.2);
+        assert_component_eq!(back.components.2, 120.0);
+        assert_component_eq!(back.alpha, 0.5);
+
+        // Missing alpha means the components are not premultiplied.
+        let missing = Color::new(Space::Srgb, 0.2, None, 0.6, None);
+        let premultiplied = missing.premultiply();
+        assert_eq!(premultiplied.alpha, None);
+        assert_eq!(premultiplied.component(1), None);
+        assert_component_eq!(premultiplied.component(2).unwrap(), 0.6);
+
+        let back = premultiplied.into_color();
+        assert_eq!(back.flags, Flags::C1_IS_NONE | Flags::ALPHA_IS_NONE);
+        assert_component_eq!(back.components.0, 0.2);
+    }
+
     #[test]
     fn interpolate_with_alpha() {
         let left = Color::new(Space::Srgb, 0.24, 0.12, 0.98, 0.4);
@@ -572,122 +1332,167 @@ mod tests {
     }
 
     #[test]
-    fn ad_hoc() {
-        // // color-mix(in xyz-d65, color(xyz-d65 .1 .2 .3 / none), color(xyz-d65 .5 .6 .7 / none))
-        // let left = Color::new(Space::XyzD65, 0.1, 0.2, 0.3, None);
-        // let right = Color::new(Space::XyzD65, 0.5, 0.6, 0.7, None);
-        // let middle = left.interpolate(&right, Space::XyzD65).at(0.5);
-        // // color(xyz-d65 0.3 0.4 0.5 / none)
-        // assert_component_eq!(middle.components.0, 0.3);
-        // assert_component_eq!(middle.components.1, 0.4);
-        // assert_component_eq!(middle.components.2, 0.5);
-        // assert_eq!(middle.flags, Flags::ALPHA_IS_NONE);
-
-        // // color-mix(in hsl, hsl(120deg 10% 20%) 12.5%, hsl(30deg 30% 40%) 37.5%)
-        // let left = Color::new(Space::XyzD65, 0.1, 0.2, 0.3, None);
-        // let right = Color::new(Space::XyzD65, 0.5, 0.6, 0.7, None);
-        // let middle = left.interpolate(&right, Space::XyzD65).at(0.5);
-        // // color(srgb 0.4375 0.415625 0.2625 / 0.5)
-        // assert_component_eq!(middle.components.0, 0.3);
-        // assert_component_eq!(middle.components.1, 0.4);
-        // assert_component_eq!(middle.components.2, 0.5);
-        // assert_eq!(middle.flags, Flags::ALPHA_IS_NONE);
-
-        // // color-mix(in hsl, hsl(120deg 10% 20% / .4), hsl(30deg 30% 40% / .8))
-        // let left = Color::new(Space::Hsl, 120.0, 0.1, 0.2, 0.4);
-        // let right = Color::new(Space::Hsl, 30.0, 0.3, 0.4, 0.8);
-        // let middle = left
-        //     .interpolate(&right, Space::Hsl)
-        //     .at(0.5)
-        //     .to_space(Space::Srgb);
-        // // color(srgb 0.372222 0.411111 0.255556 / 0.6)
-        // assert_component_eq!(middle.components.0, 0.372222);
-        // assert_component_eq!(middle.components.1, 0.411111);
-        // assert_component_eq!(middle.components.2, 0.255556);
-        // assert_component_eq!(middle.alpha, 0.6);
-
-        // // color-mix(in hsl longer hue, hsl(40deg 50% 50%), hsl(60deg 50% 50%))
-        // let left = Color::new(Space::Hsl, 40.0, 0.5, 0.5, 1.0);
-        // let right = Color::new(Space::Hsl, 60.0, 0.5, 0.5, 1.0);
-        // let middle = left
-        //     .interpolate(&right, Space::Hsl)
-        //     .with_hue_interpolation(HueInterpolationMethod::Longer)
-        //     .at(0.5)
-        //     .to_space(Space::Srgb);
-        // // color(srgb 0.25 0.333333 0.75)
-        // assert_component_eq!(middle.components.0, 0.25);
-        // assert_component_eq!(middle.components.1, 0.333333);
-        // assert_component_eq!(middle.components.2, 0.75);
-        // assert_component_eq!(middle.alpha, 1.0);
-
-        // // color-mix(in srgb, color(srgb .1 .2 .3 / none), color(srgb .5 .6 .7))
-        // let left = Color::new(Space::Srgb, 0.1, 0.2, 0.3, None);
-        // let right = Color::new(Space::Srgb, 0.5, 0.6, 0.7, 1.0);
-        // let interp = Interpolation::new(&left, &right, Space::Srgb);
-        // // color(${resultColorSpace} 0.3 0.4 0.5)
-        // let result = interp.at(0.5);
-        // assert_component_eq!(result.components.0, 0.3);
-        // assert_component_eq!(result.components.1, 0.4);
-        // assert_component_eq!(result.components.2, 0.5);
-        // assert_component_eq!(result.alpha, 1.0);
-
-        // // color-mix(in srgb, color(srgb .1 .2 .3 / none), color(srgb .5 .6 .7 / 0.5))
-        // let left = Color::new(Space::Srgb, 0.1, 0.2, 0.3, None);
-        // let right = Color::new(Space::Srgb, 0.5, 0.6, 0.7, 0.5);
-        // let interp = Interpolation::new(&left, &right, Space::Srgb);
-        // // color(srgb 0.3 0.4 0.5 / 0.5)
-        // let result = interp.at(0.5);
-        // assert_component_eq!(result.components.0, 0.3);
-        // assert_component_eq!(result.components.1, 0.4);
-        // assert_component_eq!(result.components.2, 0.5);
-        // assert_component_eq!(result.alpha, 0.5);
-
-        // // color-mix(in hsl, transparent, hsl(30deg 30% 40%))
-        // let left = Color::new(Space::Srgb, 0.0, 0.0, 0.0, 0.0);
-        // let right = Color::new(Space::Hsl, 30.0, 0.3, 0.4, 1.0);
-        // let interp = Interpolation::new(&left, &right, Space::Hsl);
-        // // color(srgb 0.52 0.4 0.28 / 0.5)
-        // let result = interp.at(0.5).to_space(Space::Srgb);
-        // assert_component_eq!(result.components.0, 0.52);
-        // assert_component_eq!(result.components.1, 0.4);
-        // assert_component_eq!(result.components.2, 0.28);
-        // assert_component_eq!(result.alpha, 0.5);
-
-        // // color-mix(in hsl, hsl(none none none), hsl(none none none))
-        // let left = Color::new(Space::Hsl, None, None, None, 1.0);
-        // let right = Color::new(Space::Hsl, None, None, None, 1.0);
-        // let interp = Interpolation::new(&left, &right, Space::Hsl);
-        // let result = interp.at(0.5);
-        // assert!(result.c0().is_none());
-        // assert!(result.c1().is_none());
-        // assert!(result.c2().is_none());
-
-        // let srgb = result.to_space(Space::Srgb);
-        // assert!(srgb.c0().is_none());
-        // assert!(srgb.c1().is_none());
-        // assert!(srgb.c2().is_none());
-
-        // // color-mix(in hsl, lab(100 104.3 -50.9) 100%, rgb(0, 0, 0) 0%)
-        // let left = Color::new(Space::Lab, 1.0, 104.3, -50.9, 1.0);
-        // let right = Color::new(Space::Srgb, 0.0, 0.0, 0.0, 1.0);
-        // let interp = left.interpolate(&right, Space::Hsl);
-        // // color(srgb 1.59343 0.58802 1.40564)
-        // let result = interp.at(0.0);
-        // panic!("{:?}", result.to_space(Space::Srgb));
-
-        // // color-mix(in hwb, hwb(40deg 30% 40%), hwb(60deg 30% 40%))
-        // let left = Color::new(Space::Hwb, 40.0, 0.3, 0.4, 1.0).to_space(Space::Srgb);
-        // let right = Color::new(Space::Hwb, 60.0, 0.3, 0.4, 1.0).to_space(Space::Srgb);
-        // // color-mix(in hwb, rgb(153, 128, 77), rgb(153, 153, 77))
-        // panic!(
-        //     "{} {} {}, {} {} {}",
-        //     (left.components.0 * 255.0).round() as u8,
-        //     (left.components.1 * 255.0).round() as u8,
-        //     (left.components.2 * 255.0).round() as u8,
-        //     (right.components.0 * 255.0).round() as u8,
-        //     (right.components.1 * 255.0).round() as u8,
-        //     (right.components.2 * 255.0).round() as u8,
-        // );
+    fn interpolate_from_converted() {
+        let left = Color::new(Space::Srgb, 1.0, 0.0, 0.0, 1.0);
+        let right = Color::new(Space::Srgb, 0.0, 0.0, 1.0, None);
+
+        let converted = Interpolation::from_converted(
+            left.to_space(Space::Oklch),
+            right.to_space(Space::Oklch),
+            Space::Oklch,
+        );
+        let interpolation = Interpolation::new(&left, &right, Space::Oklch);
+        assert_eq!(converted.at(0.3), interpolation.at(0.3));
+        assert_eq!(converted.at(0.3).alpha(), Some(1.0));
+    }
+
+    #[test]
+    #[should_panic]
+    #[cfg(debug_assertions)]
+    fn interpolate_from_unconverted() {
+        let left = Color::new(Space::Srgb, 1.0, 0.0, 0.0, 1.0);
+        let right = Color::new(Space::Srgb, 0.0, 0.0, 1.0, 1.0);
+        Interpolation::from_converted(left, right, Space::Oklch);
+    }
+
+    #[test]
+    fn interpolate_with_straight_alpha() {
+        let left = Color::new(Space::Srgb, 0.24, 0.12, 0.98, 0.4);
+        let right = Color::new(Space::Srgb, 0.62, 0.26, 0.64, 0.6);
+
+        let middle = left
+            .interpolate(&right, Space::Srgb)
+            .with_premultiply(false)
+            .at(0.5);
+
+        assert_component_eq!(middle.components.0, 0.43);
+        assert_component_eq!(middle.components.1, 0.19);
+        assert_component_eq!(middle.components.2, 0.81);
+        assert_component_eq!(middle.alpha, 0.5);
+
+        // Fully transparent colors keep their components.
+        let transparent = Color::new(Space::Srgb, 1.0, 0.0, 0.0, 0.0);
+        let middle = transparent
+            .interpolate(&right, Space::Srgb)
+            .with_premultiply(false)
+            .at(0.5);
+        assert_component_eq!(middle.components.0, 0.81);
+        assert_component_eq!(middle.alpha, 0.3);
+
+        let missing = Color::new(Space::Srgb, 0.2, 0.4, 0.6, None);
+        let middle = missing
+            .interpolate(&missing, Space::Srgb)
+            .with_premultiply(false)
+            .at(0.5);
+        assert_eq!(middle.alpha(), None);
+
+        // Enabling premultiplication again is the same as the default.
+        let interpolation = left.interpolate(&right, Space::Srgb);
+        let toggled = interpolation
+            .clone()
+            .with_premultiply(false)
+            .with_premultiply(true);
+        assert_eq!(toggled.at(0.5), interpolation.at(0.5));
+    }
+
+    #[test]
+    fn interpolate_xyz_with_missing_alpha() {
+        // color-mix(in xyz-d65, color(xyz-d65 .1 .2 .3 / none), color(xyz-d65 .5 .6 .7 / none))
+        let left = Color::new(Space::XyzD65, 0.1, 0.2, 0.3, None);
+        let right = Color::new(Space::XyzD65, 0.5, 0.6, 0.7, None);
+        let middle = left.interpolate(&right, Space::XyzD65).at(0.5);
+        // color(xyz-d65 0.3 0.4 0.5 / none)
+        assert_component_eq!(middle.components.0, 0.3);
+        assert_component_eq!(middle.components.1, 0.4);
+        assert_component_eq!(middle.components.2, 0.5);
+        assert_eq!(middle.flags, Flags::ALPHA_IS_NONE);
+        assert_eq!(middle.alpha(), None);
+
+        // The missing alpha is carried over to other color spaces.
+        let srgb = middle.to_space(Space::Srgb);
+        let expected = Color::new(Space::XyzD65, 0.3, 0.4, 0.5, 1.0).to_space(Space::Srgb);
+        assert_component_eq!(srgb.components.0, expected.components.0);
+        assert_component_eq!(srgb.components.1, expected.components.1);
+        assert_component_eq!(srgb.components.2, expected.components.2);
+        assert_eq!(srgb.flags, Flags::ALPHA_IS_NONE);
+        assert_eq!(srgb.alpha(), None);
+    }
+
+    #[test]
+    fn interpolate_with_component_missing_on_one_side() {
+        let spaces = (0..=u8::MAX).filter_map(Space::from_u8);
+
+        let present = [0.2, 0.3, 0.4];
+        let other = [0.6, 0.7, 0.8];
+
+        for space in spaces {
+            for missing in 0..3 {
+                let with_missing = |values: [Component; 3], alpha: Component| {
+                    let c = |i: usize| (i != missing).then_some(values[i]);
+                    Color::new(space, c(0), c(1), c(2), alpha)
+                };
+                let full = |values: [Component; 3], alpha: Component| {
+                    Color::new(space, values[0], values[1], values[2], alpha)
+                };
+
+                // Missing on the left, and then on the right, with the same
+                // and with different alphas.
+                let cases = [
+                    (with_missing(other, 1.0), full(present, 1.0), present),
+                    (full(present, 1.0), with_missing(other, 1.0), present),
+                    (with_missing(other, 0.2), full(present, 1.0), present),
+                    (full(present, 0.5), with_missing(other, 1.0), present),
+                ];
+
+                for (left, right, expected) in cases {
+                    let interp = left.interpolate(&right, space);
+                    for t in [0.0, 0.25, 0.5, 1.0] {
+                        let result = interp.at(t);
+                        let value = [result.c0(), result.c1(), result.c2()][missing];
+                        assert_component_eq!(value.unwrap(), expected[missing]);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn interpolate_all_missing_components() {
+        // color-mix(in hsl, hsl(none none none), hsl(none none none))
+        let left = Color::new(Space::Hsl, None, None, None, 1.0);
+        let right = Color::new(Space::Hsl, None, None, None, 1.0);
+        let interp = Interpolation::new(&left, &right, Space::Hsl);
+
+        for t in [0.0, 0.25, 0.5, 1.0] {
+            let result = interp.at(t);
+            assert_eq!(result.c0(), None);
+            assert_eq!(result.c1(), None);
+            assert_eq!(result.c2(), None);
+            assert_eq!(result.alpha(), Some(1.0));
+
+            // Missing components resolve to black when converted.
+            let srgb = result.to_space(Space::Srgb);
+            assert_eq!(srgb.c0(), Some(0.0));
+            assert_eq!(srgb.c1(), Some(0.0));
+            assert_eq!(srgb.c2(), Some(0.0));
+        }
+    }
+
+    #[test]
+    fn interpolate_with_zero_percent_weight() {
+        // color-mix(in hsl, lab(100 104.3 -50.9) 100%, rgb(0, 0, 0) 0%)
+        let left = Color::new(Space::Lab, 100.0, 104.3, -50.9, 1.0);
+        let right = Color::new(Space::Srgb, 0.0, 0.0, 0.0, 1.0);
+        let interp = left.interpolate(&right, Space::Hsl);
+
+        // color(srgb 1.59343 0.58802 1.40564)
+        let result = interp
+            .with_normalized_weights(1.0, 0.0)
+            .to_space(Space::Srgb);
+        crate::assert_component_eq!(result.components.0, 1.59343);
+        crate::assert_component_eq!(result.components.1, 0.58802);
+        crate::assert_component_eq!(result.components.2, 1.40564);
+        assert_eq!(result.alpha, 1.0);
     }
 
     #[test]
@@ -7,6 +7,7 @@
 //! - [`SrgbLinear`] for colors in the sRGB (linear light) color space.
 //! - [`Hsl`] for sRGB colors specified in the HSL (hue, saturation, lightness) form.
 //! - [`Hwb`] for sRGB colors specified in the HWB (hue, whiteness, blackness) form.
+//! - [`Hsv`] for sRGB colors specified in the HSV (hue, saturation, value) form.
 //! - [`Lab`] for colors specified in the CIE-Lab color space, using the rectangular orthogonal form.
 //! - [`Lch`] for colors specified in the CIE-Lab color space, using the cylindrical polar form.
 //! - [`Oklab`] for colors specified in the oklab color space, using the rectangular orthogonal form.
@@ -17,18 +18,45 @@
 //! - [`A98Rgb`] for colors in the A98 color space, specified with red, green and blue components.
 //! - [`ProPhotoRgb`] for colors in the ProPhoto RGB color space, specified with red, green and blue components.
 //! - [`Rec2020`] for colors in the rec2020 color space, specified with red, green and blue components.
+//! - [`Hct`] for colors specified in the HCT (hue, chroma, tone) color space used by Material Design.
+//! - [`LabD65`] for colors specified in the CIE-Lab color space with a D65 white reference, using the rectangular orthogonal form.
+//! - [`LchD65`] for colors specified in the CIE-Lab color space with a D65 white reference, using the cylindrical polar form.
+//! - [`Jzazbz`] for colors specified in the Jzazbz color space, using the rectangular orthogonal form.
+//! - [`Jzczhz`] for colors specified in the Jzazbz color space, using the cylindrical polar form.
+//! - [`Ipt`] for colors specified in the IPT color space.
+//! - [`ICtCp`] for colors specified in the ICtCp color space, used for HDR signals.
 
 #![deny(missing_docs)]
 
 #[cfg(test)]
 mod test;
 
+#[cfg(feature = "approx")]
+mod approx_eq;
+mod blend;
 mod color;
 mod color_space;
+mod colormap;
+mod combine;
+mod contrast;
 mod convert;
+mod correlates;
+mod css;
+mod delta_e;
+mod easing;
+mod encoding;
+mod error;
 mod gamut;
+mod illuminant;
 mod interpolate;
 mod math;
+mod named;
+mod palette;
+mod quantize;
+#[cfg(feature = "serde")]
+mod serialization;
+mod swatches;
+mod validate;
 
 #[cfg(not(feature = "f64"))]
 /// A 32-bit floating point value that all components are stored as.
@@ -41,8 +69,58 @@ pub type Component = f64;
 // All the models can be accessed through the module.
 pub mod models;
 
+// The scalar transfer functions of the RGB color spaces.
+pub mod transfer;
+
 // Most common color types.
-pub use color::{Color, ComponentDetails, Components, Flags, Space};
+pub use color::{Color, ComponentDetails, ComponentKind, Components, Flags, Space};
+
+// Error types.
+pub use error::Error;
+
+// Arithmetic types.
+pub use combine::MissingPolicy;
+
+// Conversion types.
+pub use convert::{conversion_roundtrip_error, AlphaMode, ROUNDTRIP_TOLERANCE};
+pub use math::{ab_to_ch, ch_to_ab};
+
+// Blending types.
+pub use blend::BlendMode;
+
+// Colormap types.
+pub use colormap::Colormap;
+
+// Perceptual correlate types.
+pub use correlates::LightnessRef;
+
+// Contrast functions.
+pub use contrast::apca_contrast;
+
+// Color difference types.
+pub use delta_e::{color_diff_stats, DeltaMetric, DiffStats};
+
+// Gamut mapping types.
+pub use gamut::{gamut_boundary, gamut_coverage, max_chroma, GamutError, GamutMethod, OutOfGamut};
+
+// Chromatic adaptation types.
+pub use illuminant::{Adaptation, AdaptationMethod, Illuminant};
 
 // Color interpolation types.
-pub use interpolate::{HueInterpolationMethod, Interpolation};
+pub use easing::Easing;
+pub use interpolate::{
+    interpolate_triangle, mix_many, HueInterpolationMethod, Interpolation, Premultiplied,
+    UnknownHueInterpolationMethod,
+};
+
+// Palette generation types.
+pub use palette::{color_ramp, color_ramp_equalized, sort_colors, SchemeKind, SortKey, TONES};
+
+// Quantization types.
+pub use quantize::PaletteBuilder;
+
+// Swatch extraction types.
+pub use swatches::{extract_swatches, Swatch, Swatches};
+
+// Validation types.
+pub use validate::{ValidationRule, Warning};
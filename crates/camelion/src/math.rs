@@ -20,6 +20,14 @@ pub fn normalize_hue(hue: Component) -> Component {
     normalize(hue).rem_euclid(360.0)
 }
 
+/// Move `hue` by full turns to be as close as possible to `reference`, so
+/// that the difference between them is along the shorter arc, e.g. 359
+/// degrees moves to -1 degrees if the reference is 1 degree.
+#[inline]
+pub fn closest_hue(hue: Component, reference: Component) -> Component {
+    reference - ((reference - hue + 180.0).rem_euclid(360.0) - 180.0)
+}
+
 /// Returns true if the value is very close to zero.
 #[inline]
 pub fn almost_zero(v: Component) -> bool {
@@ -28,6 +36,67 @@ pub fn almost_zero(v: Component) -> bool {
     v.abs() < Component::EPSILON
 }
 
+/// Convert the opponent coordinates `a` and `b` of a rectangular color model
+/// (e.g. Lab or Oklab) to the (chroma, hue) of its cylindrical polar form, with
+/// the hue in degrees in [0..360]. If the chroma is almost zero, the color is
+/// achromatic and the hue is powerless, which is returned as NaN.
+/// <https://drafts.csswg.org/css-color-4/#lab-to-lch>
+pub fn ab_to_ch(a: Component, b: Component) -> (Component, Component) {
+    let chroma = (a * a + b * b).sqrt();
+    let hue = if almost_zero(chroma) {
+        Component::NAN
+    } else {
+        normalize_hue(b.atan2(a).to_degrees())
+    };
+
+    (chroma, hue)
+}
+
+/// The inverse of [`ab_to_ch`]. A NaN (powerless) hue does not contribute to
+/// `a` or `b`. Negative chroma is clamped to 0.
+/// <https://drafts.csswg.org/css-color-4/#lch-to-lab>
+pub fn ch_to_ab(chroma: Component, hue: Component) -> (Component, Component) {
+    if hue.is_nan() {
+        return (0.0, 0.0);
+    }
+
+    // Chroma can not be negative, so clamp it at 0 to avoid producing a color
+    // mirrored around the neutral axis.
+    let chroma = chroma.max(0.0);
+
+    let hue = hue.to_radians();
+    (chroma * hue.cos(), chroma * hue.sin())
+}
+
+/// The m1 exponent of the SMPTE ST 2084 perceptual quantizer (PQ).
+const PQ_M1: f64 = 2610.0 / 16384.0;
+/// The m2 exponent of the SMPTE ST 2084 perceptual quantizer (PQ).
+pub const PQ_M2: Component = 2523.0 / 4096.0 * 128.0;
+const PQ_C1: f64 = 3424.0 / 4096.0;
+const PQ_C2: f64 = 2413.0 / 4096.0 * 32.0;
+const PQ_C3: f64 = 2392.0 / 4096.0 * 32.0;
+
+/// Encode a linear value, where 1.0 is 10000 cd/m², with the SMPTE ST 2084
+/// perceptual quantizer (PQ). The `m2` exponent is a parameter, because some
+/// color spaces (e.g. Jzazbz) use a modified value. Negative values are
+/// clamped to 0.
+/// <https://www.itu.int/rec/R-REC-BT.2100>
+///
+/// NOTE: The large exponents of PQ amplify rounding errors, so the curve is
+///       always calculated with 64-bit precision.
+#[allow(clippy::unnecessary_cast)] // Component can be an f64.
+pub fn pq_encode(value: Component, m2: Component) -> Component {
+    let v = (value.max(0.0) as f64).powf(PQ_M1);
+    ((PQ_C1 + PQ_C2 * v) / (1.0 + PQ_C3 * v)).powf(m2 as f64) as Component
+}
+
+/// The inverse of [`pq_encode`].
+#[allow(clippy::unnecessary_cast)] // Component can be an f64.
+pub fn pq_decode(value: Component, m2: Component) -> Component {
+    let v = (value.max(0.0) as f64).powf(1.0 / m2 as f64);
+    ((v - PQ_C1).max(0.0) / (PQ_C2 - PQ_C3 * v)).powf(1.0 / PQ_M1) as Component
+}
+
 pub type Transform = Transform3D<Component>;
 
 type Vector = Vector3D<Component>;
@@ -72,3 +141,37 @@ pub const fn transform_3x3(
         _unit: PhantomData,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn opponent_coordinates_to_chroma_hue() {
+        let (chroma, hue) = ab_to_ch(0.0, 2.0);
+        assert!((chroma - 2.0).abs() < 1.0e-6);
+        assert!((hue - 90.0).abs() < 1.0e-4);
+
+        let (chroma, hue) = ab_to_ch(-1.0, -1.0);
+        assert!((chroma - Component::sqrt(2.0)).abs() < 1.0e-6);
+        assert!((hue - 225.0).abs() < 1.0e-4);
+
+        let (chroma, hue) = ab_to_ch(0.0, 0.0);
+        assert_eq!(chroma, 0.0);
+        assert!(hue.is_nan());
+    }
+
+    #[test]
+    fn chroma_hue_to_opponent_coordinates() {
+        let (a, b) = ch_to_ab(2.0, 90.0);
+        assert!(a.abs() < 1.0e-6);
+        assert!((b - 2.0).abs() < 1.0e-6);
+
+        let (a, b) = ch_to_ab(ab_to_ch(0.3, -0.1).0, ab_to_ch(0.3, -0.1).1);
+        assert!((a - 0.3).abs() < 1.0e-6);
+        assert!((b + 0.1).abs() < 1.0e-6);
+
+        assert_eq!(ch_to_ab(1.0, Component::NAN), (0.0, 0.0));
+        assert_eq!(ch_to_ab(-1.0, 45.0), (0.0, 0.0));
+    }
+}
@@ -49,12 +49,36 @@ impl ToBase for models::Hsl {
     }
 }
 
+impl ToBase for models::Hsv {
+    fn to_base(&self) -> Base {
+        self.to_srgb().to_base()
+    }
+}
+
 impl ToBase for models::Hwb {
     fn to_base(&self) -> Base {
         self.to_srgb().to_base()
     }
 }
 
+impl ToBase for models::Hct {
+    fn to_base(&self) -> Base {
+        self.to_xyz()
+    }
+}
+
+impl ToBase for models::Ipt {
+    fn to_base(&self) -> Base {
+        self.to_xyz()
+    }
+}
+
+impl ToBase for models::ICtCp {
+    fn to_base(&self) -> Base {
+        self.to_xyz()
+    }
+}
+
 impl<S: ColorSpace> ToBase for models::Rectangular<S>
 where
     models::Rectangular<S>: ToXyz,
@@ -106,6 +130,7 @@ mod tests {
     fn test_hsl_hwb() {
         models::Hsl::new(0.0, 0.0, 0.0).to_base();
         models::Hwb::new(0.0, 0.0, 0.0).to_base();
+        models::Hsv::new(0.0, 0.0, 0.0).to_base();
     }
 
     #[test]
@@ -121,4 +146,15 @@ mod tests {
         models::XyzD50::new(0.0, 0.0, 0.0).to_base();
         models::XyzD65::new(0.0, 0.0, 0.0).to_base();
     }
+
+    #[test]
+    fn test_hct() {
+        models::Hct::new(0.0, 0.0, 0.0).to_base();
+    }
+
+    #[test]
+    fn test_ipt_ictcp() {
+        models::Ipt::new(0.0, 0.0, 0.0).to_base();
+        models::ICtCp::new(0.0, 0.0, 0.0).to_base();
+    }
 }
@@ -0,0 +1,340 @@
+//! The CAM16 color appearance model, also used as the basis for the HCT
+//! color space.
+//! <https://doi.org/10.1002/col.22131>
+
+use crate::{
+    color::Components,
+    math::{normalize_hue, transform, transform_3x3, Transform},
+    models::xyz::{WhitePoint, XyzD65, D65},
+    Component,
+};
+
+/// Converts CIE-XYZ to the CAT16 adapted RGB responses.
+#[rustfmt::skip]
+#[allow(clippy::excessive_precision)]
+const XYZ_TO_CAT16: Transform = transform_3x3(
+     0.401288, -0.250268, -0.002079,
+     0.650173,  1.204414,  0.048952,
+    -0.051461,  0.045854,  0.953127,
+);
+
+/// Converts the CAT16 adapted RGB responses back to CIE-XYZ.
+#[rustfmt::skip]
+#[allow(clippy::excessive_precision)]
+const CAT16_TO_XYZ: Transform = transform_3x3(
+     1.86206786,  0.38752654, -0.0158415,
+    -1.01125463,  0.62144744, -0.03412294,
+     0.14918677, -0.00897398,  1.04996444,
+);
+
+/// Convert a CIE-Lab lightness value to a relative luminance in the range
+/// [0..100].
+pub(crate) fn y_from_lstar(lstar: Component) -> Component {
+    const KAPPA: Component = 24389.0 / 27.0;
+
+    let ft = (lstar + 16.0) / 116.0;
+    if lstar > 8.0 {
+        ft * ft * ft * 100.0
+    } else {
+        lstar / KAPPA * 100.0
+    }
+}
+
+/// Convert a relative luminance in the range [0..100] to a CIE-Lab lightness
+/// value.
+pub(crate) fn lstar_from_y(y: Component) -> Component {
+    const KAPPA: Component = 24389.0 / 27.0;
+    const EPSILON: Component = 216.0 / 24389.0;
+
+    let y = y / 100.0;
+    let fy = if y > EPSILON {
+        y.cbrt()
+    } else {
+        (KAPPA * y + 16.0) / 116.0
+    };
+    116.0 * fy - 16.0
+}
+
+/// The luminance of the area surrounding the stimulus and its background.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Surround {
+    /// E.g. viewing a projected image in a dark room.
+    Dark,
+    /// E.g. viewing a television screen in a dimly lit room.
+    Dim,
+    /// E.g. viewing a surface color in an office.
+    #[default]
+    Average,
+}
+
+impl Surround {
+    fn value(&self) -> Component {
+        match self {
+            Surround::Dark => 0.0,
+            Surround::Dim => 1.0,
+            Surround::Average => 2.0,
+        }
+    }
+}
+
+/// The environment a color is viewed in, with all the values derived from it
+/// that the CAM16 model needs.
+#[derive(Clone, Debug)]
+pub struct ViewingConditions {
+    n: Component,
+    aw: Component,
+    nbb: Component,
+    ncb: Component,
+    c: Component,
+    nc: Component,
+    rgb_d: Components,
+    fl: Component,
+    fl_root: Component,
+    z: Component,
+}
+
+impl ViewingConditions {
+    /// Create viewing conditions for a D65 white point from the adapting
+    /// luminance in cd/m², the CIE-Lab lightness of the background and the
+    /// surround.
+    pub fn new(
+        adapting_luminance: Component,
+        background_lstar: Component,
+        surround: Surround,
+    ) -> Self {
+        Self::with_white_point(
+            D65::WHITE_POINT.map(|v| v * 100.0),
+            adapting_luminance,
+            background_lstar,
+            surround.value(),
+            false,
+        )
+    }
+
+    /// Create viewing conditions from the white point (with Y = 100), the
+    /// adapting luminance in cd/m², the CIE-Lab lightness of the background
+    /// and the surround, where 0.0 is dark, 1.0 is dim and 2.0 is average.
+    fn with_white_point(
+        white_point: Components,
+        adapting_luminance: Component,
+        background_lstar: Component,
+        surround: Component,
+        discounting_illuminant: bool,
+    ) -> Self {
+        let lerp = |a: Component, b: Component, t: Component| a + (b - a) * t;
+
+        let rgb_w = transform(&XYZ_TO_CAT16, white_point);
+
+        let f = 0.8 + surround / 10.0;
+        let c = if f >= 0.9 {
+            lerp(0.59, 0.69, (f - 0.9) * 10.0)
+        } else {
+            lerp(0.525, 0.59, (f - 0.8) * 10.0)
+        };
+
+        let d = if discounting_illuminant {
+            1.0
+        } else {
+            f * (1.0 - (1.0 / 3.6) * ((-adapting_luminance - 42.0) / 92.0).exp())
+        }
+        .clamp(0.0, 1.0);
+
+        let rgb_d = rgb_w.map(|w| d * (100.0 / w) + 1.0 - d);
+
+        let k = 1.0 / (5.0 * adapting_luminance + 1.0);
+        let k4 = k * k * k * k;
+        let k4f = 1.0 - k4;
+        let fl = k4 * adapting_luminance + 0.1 * k4f * k4f * (5.0 * adapting_luminance).cbrt();
+
+        let n = y_from_lstar(background_lstar) / white_point.1;
+        let z = 1.48 + n.sqrt();
+        let nbb = 0.725 / n.powf(0.2);
+
+        let adapt = |d: Component, w: Component| {
+            let factor = (fl * d * w / 100.0).powf(0.42);
+            400.0 * factor / (factor + 27.13)
+        };
+        let rgb_a = Components(
+            adapt(rgb_d.0, rgb_w.0),
+            adapt(rgb_d.1, rgb_w.1),
+            adapt(rgb_d.2, rgb_w.2),
+        );
+        let aw = (2.0 * rgb_a.0 + rgb_a.1 + 0.05 * rgb_a.2) * nbb;
+
+        Self {
+            n,
+            aw,
+            nbb,
+            ncb: nbb,
+            c,
+            nc: f,
+            rgb_d,
+            fl,
+            fl_root: fl.powf(0.25),
+            z,
+        }
+    }
+}
+
+impl Default for ViewingConditions {
+    /// The viewing conditions also used by HCT: a D65 white point, an
+    /// adapting luminance of 200/π times the luminance of mid-gray, a mid-gray
+    /// background and an average surround.
+    fn default() -> Self {
+        const PI: Component = std::f64::consts::PI as Component;
+
+        Self::new(
+            200.0 / PI * y_from_lstar(50.0) / 100.0,
+            50.0,
+            Surround::Average,
+        )
+    }
+}
+
+/// The appearance correlates of a color in the CAM16 color appearance model.
+#[derive(Clone, Debug)]
+pub struct Cam16 {
+    /// The lightness (J) correlate.
+    pub j: Component,
+    /// The chroma (C) correlate.
+    pub chroma: Component,
+    /// The hue angle (h) in degrees.
+    pub hue: Component,
+    /// The colorfulness (M) correlate.
+    pub colorfulness: Component,
+    /// The saturation (s) correlate.
+    pub saturation: Component,
+}
+
+impl XyzD65 {
+    /// Calculate the CAM16 appearance correlates of this color under the
+    /// given viewing conditions.
+    pub fn cam16(&self, conditions: ViewingConditions) -> Cam16 {
+        Cam16::from_xyz(
+            Components(self.x, self.y, self.z).map(|v| v * 100.0),
+            &conditions,
+        )
+    }
+}
+
+impl Cam16 {
+    /// Calculate the correlates of a color specified in CIE-XYZ (with Y in
+    /// the range [0..100]) under the given viewing conditions.
+    pub(crate) fn from_xyz(xyz: Components, vc: &ViewingConditions) -> Self {
+        let rgb_t = transform(&XYZ_TO_CAT16, xyz);
+
+        let adapt = |d: Component, t: Component| {
+            let rgb_d = d * t;
+            let factor = (vc.fl * rgb_d.abs() / 100.0).powf(0.42);
+            rgb_d.signum() * 400.0 * factor / (factor + 27.13)
+        };
+        let r_a = adapt(vc.rgb_d.0, rgb_t.0);
+        let g_a = adapt(vc.rgb_d.1, rgb_t.1);
+        let b_a = adapt(vc.rgb_d.2, rgb_t.2);
+
+        // Opponent color dimensions.
+        let a = (11.0 * r_a - 12.0 * g_a + b_a) / 11.0;
+        let b = (r_a + g_a - 2.0 * b_a) / 9.0;
+
+        let u = (20.0 * r_a + 20.0 * g_a + 21.0 * b_a) / 20.0;
+        let p2 = (40.0 * r_a + 20.0 * g_a + b_a) / 20.0;
+
+        let hue = normalize_hue(b.atan2(a).to_degrees());
+
+        let ac = p2 * vc.nbb;
+        let j = 100.0 * (ac / vc.aw).powf(vc.c * vc.z);
+
+        let e_hue = 0.25 * ((hue.to_radians() + 2.0).cos() + 3.8);
+        let p1 = 50000.0 / 13.0 * e_hue * vc.nc * vc.ncb;
+        let t = p1 * a.hypot(b) / (u + 0.305);
+        let alpha = t.powf(0.9) * (1.64 - (0.29 as Component).powf(vc.n)).powf(0.73);
+        let chroma = alpha * (j / 100.0).sqrt();
+        let colorfulness = chroma * vc.fl_root;
+        let saturation = 50.0 * (alpha * vc.c / (vc.aw + 4.0)).sqrt();
+
+        Self {
+            j,
+            chroma,
+            hue,
+            colorfulness,
+            saturation,
+        }
+    }
+
+    /// Convert the lightness (J), chroma (C) and hue (h) correlates back to
+    /// CIE-XYZ (with Y in the range [0..100]) under the given viewing
+    /// conditions.
+    pub(crate) fn jch_to_xyz(
+        j: Component,
+        chroma: Component,
+        hue: Component,
+        vc: &ViewingConditions,
+    ) -> Components {
+        let alpha = if chroma == 0.0 || j == 0.0 {
+            0.0
+        } else {
+            chroma / (j / 100.0).sqrt()
+        };
+
+        let t = (alpha / (1.64 - (0.29 as Component).powf(vc.n)).powf(0.73)).powf(1.0 / 0.9);
+        let h_rad = hue.to_radians();
+
+        let e_hue = 0.25 * ((h_rad + 2.0).cos() + 3.8);
+        let ac = vc.aw * (j / 100.0).powf(1.0 / vc.c / vc.z);
+        let p1 = e_hue * (50000.0 / 13.0) * vc.nc * vc.ncb;
+        let p2 = ac / vc.nbb;
+
+        let (h_sin, h_cos) = h_rad.sin_cos();
+        let gamma = 23.0 * (p2 + 0.305) * t / (23.0 * p1 + 11.0 * t * h_cos + 108.0 * t * h_sin);
+        let a = gamma * h_cos;
+        let b = gamma * h_sin;
+
+        let r_a = (460.0 * p2 + 451.0 * a + 288.0 * b) / 1403.0;
+        let g_a = (460.0 * p2 - 891.0 * a - 261.0 * b) / 1403.0;
+        let b_a = (460.0 * p2 - 220.0 * a - 6300.0 * b) / 1403.0;
+
+        let unadapt = |v: Component, d: Component| {
+            let base = (27.13 * v.abs() / (400.0 - v.abs())).max(0.0);
+            v.signum() * (100.0 / vc.fl) * base.powf(1.0 / 0.42) / d
+        };
+
+        transform(
+            &CAT16_TO_XYZ,
+            Components(
+                unadapt(r_a, vc.rgb_d.0),
+                unadapt(g_a, vc.rgb_d.1),
+                unadapt(b_a, vc.rgb_d.2),
+            ),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Srgb, ToBase};
+
+    #[test]
+    fn cam16_of_srgb_red() {
+        // Reference values from the Material color utilities.
+        let cam16 = Srgb::new(1.0, 0.0, 0.0)
+            .to_base()
+            .cam16(ViewingConditions::default());
+
+        assert!((cam16.j - 46.445).abs() < 0.1);
+        assert!((cam16.chroma - 113.357).abs() < 0.1);
+        assert!((cam16.hue - 27.408).abs() < 0.1);
+        assert!((cam16.colorfulness - 89.494).abs() < 0.1);
+        assert!((cam16.saturation - 91.889).abs() < 0.1);
+    }
+
+    #[test]
+    fn surround_affects_lightness() {
+        let xyz = Srgb::new(0.5, 0.5, 0.5).to_base();
+
+        let dark = xyz.cam16(ViewingConditions::new(64.0, 20.0, Surround::Dark));
+        let average = xyz.cam16(ViewingConditions::new(64.0, 20.0, Surround::Average));
+
+        assert!(dark.j > average.j);
+    }
+}
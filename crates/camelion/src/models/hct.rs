@@ -0,0 +1,137 @@
+//! Model a color in the HCT (hue, chroma, tone) color space used by Material
+//! Design. The hue and chroma are taken from the CAM16 color appearance model
+//! and the tone is the CIE-Lab lightness.
+//! <https://material.io/blog/science-of-color-design>
+
+use crate::{
+    color::{Components, CssColorSpaceId, Space},
+    math::{almost_zero, normalize},
+    models::{
+        cam16::{lstar_from_y, y_from_lstar, Cam16, ViewingConditions},
+        xyz::{ToXyz, Xyz, XyzD65, D65},
+    },
+    Component,
+};
+
+camelion_macros::gen_model! {
+    /// A color specified in the HCT color space.
+    pub struct Hct {
+        /// The CAM16 hue component of the color.
+        pub hue: Component,
+        /// The CAM16 chroma component of the color.
+        pub chroma: Component,
+        /// The tone (CIE-Lab lightness) component of the color.
+        pub tone: Component,
+    }
+}
+
+impl CssColorSpaceId for Hct {
+    const ID: Space = Space::Hct;
+}
+
+impl From<XyzD65> for Hct {
+    fn from(value: XyzD65) -> Self {
+        let vc = ViewingConditions::default();
+        let cam16 = Cam16::from_xyz(
+            Components(value.x, value.y, value.z).map(|v| v * 100.0),
+            &vc,
+        );
+
+        let hue = if almost_zero(cam16.chroma) {
+            Component::NAN
+        } else {
+            cam16.hue
+        };
+
+        Hct::new(hue, cam16.chroma, lstar_from_y(value.y * 100.0))
+    }
+}
+
+impl ToXyz for Hct {
+    type WhitePoint = D65;
+
+    fn to_xyz(&self) -> Xyz<Self::WhitePoint> {
+        let tone = normalize(self.tone);
+        if tone <= 0.0 {
+            return Xyz::new(0.0, 0.0, 0.0);
+        }
+
+        // A missing (powerless) hue does not contribute any chroma.
+        let (hue, chroma) = if self.hue.is_nan() {
+            (0.0, 0.0)
+        } else {
+            (self.hue, normalize(self.chroma).max(0.0))
+        };
+
+        let vc = ViewingConditions::default();
+        let target_y = y_from_lstar(tone);
+        let xyz_at = |j: Component| Cam16::jch_to_xyz(j, chroma, hue, &vc);
+
+        // The tone only fixes the luminance, so search for the CAM16
+        // lightness (J) that produces the target luminance with the given hue
+        // and chroma.
+        let mut low: Component = 0.0;
+        let mut high: Component = 100.0;
+        while xyz_at(high).1 < target_y && high < 1000.0 {
+            low = high;
+            high *= 2.0;
+        }
+
+        for _ in 0..64 {
+            let mid = (low + high) / 2.0;
+            if xyz_at(mid).1 < target_y {
+                low = mid;
+            } else {
+                high = mid;
+            }
+        }
+
+        let Components(x, y, z) = xyz_at((low + high) / 2.0).map(|v| v / 100.0);
+        Xyz::new(x, y, z)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        assert_component_eq,
+        models::{Srgb, ToBase},
+    };
+
+    #[test]
+    fn srgb_primaries_to_hct() {
+        // Reference values from the Material color utilities.
+        let red = Hct::from(Srgb::new(1.0, 0.0, 0.0).to_base());
+        assert_component_eq!(red.hue, 27.408, epsilon = 0.1);
+        assert_component_eq!(red.chroma, 113.357, epsilon = 0.1);
+        assert_component_eq!(red.tone, 53.233, epsilon = 0.01);
+
+        let blue = Hct::from(Srgb::new(0.0, 0.0, 1.0).to_base());
+        assert_component_eq!(blue.hue, 282.788, epsilon = 0.1);
+        assert_component_eq!(blue.chroma, 87.230, epsilon = 0.1);
+        assert_component_eq!(blue.tone, 32.302, epsilon = 0.01);
+    }
+
+    #[test]
+    fn hct_round_trip() {
+        let xyz = Srgb::new(0.2, 0.6, 0.4).to_base();
+        let back = Hct::from(xyz.clone()).to_xyz();
+
+        assert_component_eq!(back.x, xyz.x, epsilon = 1.0e-3);
+        assert_component_eq!(back.y, xyz.y, epsilon = 1.0e-3);
+        assert_component_eq!(back.z, xyz.z, epsilon = 1.0e-3);
+    }
+
+    #[test]
+    fn hct_missing_hue_is_gray() {
+        let xyz = Hct::new(Component::NAN, 40.0, 50.0).to_xyz();
+        let gray = Hct::from(xyz);
+
+        assert_component_eq!(gray.chroma, 0.0, epsilon = 0.5);
+        assert_component_eq!(gray.tone, 50.0, epsilon = 0.01);
+
+        let black = Hct::new(120.0, 40.0, 0.0).to_xyz();
+        assert_eq!((black.x, black.y, black.z), (0.0, 0.0, 0.0));
+    }
+}
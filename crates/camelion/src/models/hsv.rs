@@ -0,0 +1,23 @@
+//! Model a color with the HSV (also known as HSB) notation in the sRGB color
+//! space.
+
+use crate::{
+    color::{CssColorSpaceId, Space},
+    Component,
+};
+
+camelion_macros::gen_model! {
+    /// A color specified with the HSV (hue, saturation, value) notation in the sRGB color space.
+    pub struct Hsv {
+        /// The hue component of the color.
+        pub hue: Component,
+        /// The saturation component of the color.
+        pub saturation: Component,
+        /// The value (brightness) component of the color.
+        pub value: Component,
+    }
+}
+
+impl CssColorSpaceId for Hsv {
+    const ID: Space = Space::Hsv;
+}
@@ -0,0 +1,116 @@
+//! Model a color in the ICtCp color space, designed for HDR and wide color
+//! gamut signals.
+//! <https://www.itu.int/rec/R-REC-BT.2100>
+
+use crate::{
+    color::{Components, CssColorSpaceId, Space},
+    math::{pq_decode, pq_encode, transform, transform_3x3, Transform, PQ_M2},
+    models::xyz::{ToXyz, Xyz, XyzD65, D65, WHITE_LUMINANCE},
+    Component,
+};
+
+/// The BT.2100 Rec.2020 to LMS matrix, combined with the CIE-XYZ to Rec.2020
+/// matrix.
+#[rustfmt::skip]
+#[allow(clippy::excessive_precision)]
+const XYZ_TO_LMS: Transform = transform_3x3(
+     0.35928325901212155, -0.1920808463704994,  0.007079784460747814,
+     0.69760511477795,     1.100476797037432,   0.07483966621863647,
+    -0.035891593232028875, 0.07537486585191189, 0.8433265453898765,
+);
+
+#[rustfmt::skip]
+#[allow(clippy::excessive_precision)]
+const LMS_TO_XYZ: Transform = transform_3x3(
+     2.0701522183894228,   0.3647385209748075,  -0.04974720753581226,
+    -1.3263473389671563,   0.6805660249472273,  -0.0492609666966135,
+     0.20665104762940514, -0.04530454592203471,  1.1880659249923042,
+);
+
+#[rustfmt::skip]
+#[allow(clippy::excessive_precision)]
+const LMS_TO_ICTCP: Transform = transform_3x3(
+    2048.0 / 4096.0,   6610.0 / 4096.0,  17933.0 / 4096.0,
+    2048.0 / 4096.0, -13613.0 / 4096.0, -17390.0 / 4096.0,
+       0.0 / 4096.0,   7003.0 / 4096.0,   -543.0 / 4096.0,
+);
+
+#[rustfmt::skip]
+#[allow(clippy::excessive_precision)]
+const ICTCP_TO_LMS: Transform = transform_3x3(
+    1.0,                  1.0,                  1.0,
+    0.008609037037932756, -0.008609037037932756, 0.5600313357106791,
+    0.11102962500302596,  -0.11102962500302596, -0.32062717498731885,
+);
+
+camelion_macros::gen_model! {
+    /// A color specified in the ICtCp color space.
+    pub struct ICtCp {
+        /// The intensity component of the color.
+        pub intensity: Component,
+        /// The blue-yellow (tritan) component of the color.
+        pub ct: Component,
+        /// The red-green (protan) component of the color.
+        pub cp: Component,
+    }
+}
+
+impl CssColorSpaceId for ICtCp {
+    const ID: Space = Space::ICtCp;
+}
+
+impl From<XyzD65> for ICtCp {
+    fn from(value: XyzD65) -> Self {
+        // ICtCp is defined on absolute luminance, with 1.0 being 10000 cd/m².
+        let xyz = Components(value.x, value.y, value.z).map(|v| v * WHITE_LUMINANCE / 10000.0);
+
+        let lms = transform(&XYZ_TO_LMS, xyz);
+        let lms = lms.map(|v| pq_encode(v, PQ_M2));
+        transform(&LMS_TO_ICTCP, lms).into()
+    }
+}
+
+impl ToXyz for ICtCp {
+    type WhitePoint = D65;
+
+    fn to_xyz(&self) -> Xyz<Self::WhitePoint> {
+        let ictcp = Components(self.intensity, self.ct, self.cp);
+        let lms = transform(&ICTCP_TO_LMS, ictcp);
+        let lms = lms.map(|v| pq_decode(v, PQ_M2));
+        let xyz = transform(&LMS_TO_XYZ, lms);
+        xyz.map(|v| v * 10000.0 / WHITE_LUMINANCE).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        assert_component_eq,
+        models::{Srgb, ToBase, WhitePoint},
+    };
+
+    #[test]
+    fn srgb_to_ictcp() {
+        let Components(x, y, z) = D65::WHITE_POINT;
+        let white = ICtCp::from(XyzD65::new(x, y, z));
+        assert_component_eq!(white.intensity, 0.580689, epsilon = 1.0e-4);
+        assert_component_eq!(white.ct, 0.0, epsilon = 1.0e-4);
+        assert_component_eq!(white.cp, 0.0, epsilon = 1.0e-4);
+
+        let red = ICtCp::from(Srgb::new(1.0, 0.0, 0.0).to_base());
+        assert_component_eq!(red.intensity, 0.427880, epsilon = 1.0e-4);
+        assert_component_eq!(red.ct, -0.115704, epsilon = 1.0e-4);
+        assert_component_eq!(red.cp, 0.278729, epsilon = 1.0e-4);
+    }
+
+    #[test]
+    fn ictcp_round_trip() {
+        let xyz = Srgb::new(0.2, 0.6, 0.4).to_base();
+        let back = ICtCp::from(xyz.clone()).to_xyz();
+
+        assert_component_eq!(back.x, xyz.x, epsilon = 1.0e-4);
+        assert_component_eq!(back.y, xyz.y, epsilon = 1.0e-4);
+        assert_component_eq!(back.z, xyz.z, epsilon = 1.0e-4);
+    }
+}
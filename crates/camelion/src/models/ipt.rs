@@ -0,0 +1,112 @@
+//! Model a color in the IPT color space, designed for uniform hue.
+//! <https://doi.org/10.2352/CIC.1998.6.1.art00003>
+
+use crate::{
+    color::{Components, CssColorSpaceId, Space},
+    math::{transform, transform_3x3, Transform},
+    models::xyz::{ToXyz, Xyz, XyzD65, D65},
+    Component,
+};
+
+#[rustfmt::skip]
+#[allow(clippy::excessive_precision)]
+const XYZ_TO_LMS: Transform = transform_3x3(
+     0.4002, -0.2280, 0.0,
+     0.7075,  1.1500, 0.0,
+    -0.0807,  0.0612, 0.9184,
+);
+
+#[rustfmt::skip]
+#[allow(clippy::excessive_precision)]
+const LMS_TO_XYZ: Transform = transform_3x3(
+     1.8502429449432056,  0.3668307751713486,   0.0,
+    -1.138301637867233,   0.6438845448402355,   0.0,
+     0.2384349585087014, -0.010673443584379992, 1.088850174216028,
+);
+
+#[rustfmt::skip]
+#[allow(clippy::excessive_precision)]
+const LMS_TO_IPT: Transform = transform_3x3(
+    0.4,  4.455,  0.8056,
+    0.4, -4.851,  0.3572,
+    0.2,  0.396, -1.1628,
+);
+
+#[rustfmt::skip]
+#[allow(clippy::excessive_precision)]
+const IPT_TO_LMS: Transform = transform_3x3(
+    1.0,                  1.0,                  1.0,
+    0.09756893051461392, -0.11387648547314712,  0.03261510991706641,
+    0.2052264331645916,   0.13321715836999806, -0.6768871830691793,
+);
+
+/// The exponent of the IPT nonlinearity.
+const EXPONENT: Component = 0.43;
+
+camelion_macros::gen_model! {
+    /// A color specified in the IPT color space.
+    pub struct Ipt {
+        /// The intensity component of the color.
+        pub intensity: Component,
+        /// The protan (red-green) component of the color.
+        pub protan: Component,
+        /// The tritan (yellow-blue) component of the color.
+        pub tritan: Component,
+    }
+}
+
+impl CssColorSpaceId for Ipt {
+    const ID: Space = Space::Ipt;
+}
+
+impl From<XyzD65> for Ipt {
+    fn from(value: XyzD65) -> Self {
+        let lms = transform(&XYZ_TO_LMS, Components(value.x, value.y, value.z));
+        let lms = lms.map(|v| v.abs().powf(EXPONENT).copysign(v));
+        transform(&LMS_TO_IPT, lms).into()
+    }
+}
+
+impl ToXyz for Ipt {
+    type WhitePoint = D65;
+
+    fn to_xyz(&self) -> Xyz<Self::WhitePoint> {
+        let ipt = Components(self.intensity, self.protan, self.tritan);
+        let lms = transform(&IPT_TO_LMS, ipt);
+        let lms = lms.map(|v| v.abs().powf(1.0 / EXPONENT).copysign(v));
+        transform(&LMS_TO_XYZ, lms).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        assert_component_eq,
+        models::{Srgb, ToBase, WhitePoint},
+    };
+
+    #[test]
+    fn srgb_to_ipt() {
+        let Components(x, y, z) = D65::WHITE_POINT;
+        let white = Ipt::from(XyzD65::new(x, y, z));
+        assert_component_eq!(white.intensity, 1.0, epsilon = 1.0e-3);
+        assert_component_eq!(white.protan, 0.0, epsilon = 1.0e-3);
+        assert_component_eq!(white.tritan, 0.0, epsilon = 1.0e-3);
+
+        let red = Ipt::from(Srgb::new(1.0, 0.0, 0.0).to_base());
+        assert_component_eq!(red.intensity, 0.456161, epsilon = 1.0e-4);
+        assert_component_eq!(red.protan, 0.620930, epsilon = 1.0e-4);
+        assert_component_eq!(red.tritan, 0.442809, epsilon = 1.0e-4);
+    }
+
+    #[test]
+    fn ipt_round_trip() {
+        let xyz = Srgb::new(0.2, 0.6, 0.4).to_base();
+        let back = Ipt::from(xyz.clone()).to_xyz();
+
+        assert_component_eq!(back.x, xyz.x, epsilon = 1.0e-4);
+        assert_component_eq!(back.y, xyz.y, epsilon = 1.0e-4);
+        assert_component_eq!(back.z, xyz.z, epsilon = 1.0e-4);
+    }
+}
@@ -0,0 +1,152 @@
+//! Models for the Jzazbz color space and its polar form, JzCzhz. Jzazbz is
+//! perceptually uniform over a wide range of luminance, which makes it
+//! suitable for HDR colors.
+//! <https://doi.org/10.1364/OE.25.015131>
+
+use crate::{
+    color::{Components, CssColorSpaceId, Space},
+    color_space,
+    math::{pq_decode, pq_encode, transform, transform_3x3, Transform, PQ_M2},
+    models::{
+        lab::{Polar, Rectangular},
+        xyz::{ToXyz, Xyz, XyzD65, D65, WHITE_LUMINANCE},
+    },
+    Component,
+};
+
+const B: Component = 1.15;
+const G: Component = 0.66;
+const D: Component = -0.56;
+#[allow(clippy::excessive_precision)]
+const D0: Component = 1.6295499532821566e-11;
+
+/// Jzazbz uses a modified m2 exponent for the PQ transfer function.
+const M2: Component = 1.7 * PQ_M2;
+
+#[rustfmt::skip]
+#[allow(clippy::excessive_precision)]
+const XYZ_TO_LMS: Transform = transform_3x3(
+     0.41478972, -0.2015100, -0.0166008,
+     0.579999,    1.120649,   0.264800,
+     0.0146480,   0.0531008,  0.6684799,
+);
+
+#[rustfmt::skip]
+#[allow(clippy::excessive_precision)]
+const LMS_TO_XYZ: Transform = transform_3x3(
+     1.9242264357876067,  0.35031676209499907, -0.09098281098284758,
+    -1.0047923125953655,  0.7264811939316552,  -0.312728290523074,
+     0.03765140403061801, -0.06538442294808502,  1.5227665613052606,
+);
+
+#[rustfmt::skip]
+#[allow(clippy::excessive_precision)]
+const LMS_TO_IAB: Transform = transform_3x3(
+    0.5,  3.524000,  0.199076,
+    0.5, -4.066708,  1.096799,
+    0.0,  0.542708, -1.295875,
+);
+
+#[rustfmt::skip]
+#[allow(clippy::excessive_precision)]
+const IAB_TO_LMS: Transform = transform_3x3(
+    1.0,                   1.0,                   1.0,
+    0.1386050432715393,   -0.1386050432715393,   -0.09601924202631895,
+    0.058047316156118876, -0.058047316156118876, -0.811891896056039,
+);
+
+/// The model for a color specified in the Jzazbz color space with the
+/// rectangular orthogonal form.
+pub type Jzazbz = Rectangular<color_space::Jzazbz>;
+
+impl CssColorSpaceId for Jzazbz {
+    const ID: Space = Space::Jzazbz;
+}
+
+/// The model for a color specified in the Jzazbz color space with the
+/// cylindrical polar form (JzCzhz).
+pub type Jzczhz = Polar<color_space::Jzazbz>;
+
+impl CssColorSpaceId for Jzczhz {
+    const ID: Space = Space::Jzczhz;
+}
+
+impl From<XyzD65> for Jzazbz {
+    fn from(value: XyzD65) -> Self {
+        // Jzazbz is defined on absolute luminance.
+        let Components(x, y, z) =
+            Components(value.x, value.y, value.z).map(|v| v * WHITE_LUMINANCE);
+
+        let xm = B * x - (B - 1.0) * z;
+        let ym = G * y - (G - 1.0) * x;
+
+        let lms = transform(&XYZ_TO_LMS, Components(xm, ym, z));
+        let lms = lms.map(|v| pq_encode(v / 10000.0, M2));
+        let Components(iz, az, bz) = transform(&LMS_TO_IAB, lms);
+
+        let jz = ((1.0 + D) * iz) / (1.0 + D * iz) - D0;
+
+        Jzazbz::new(jz, az, bz)
+    }
+}
+
+impl ToXyz for Jzazbz {
+    type WhitePoint = D65;
+
+    fn to_xyz(&self) -> Xyz<Self::WhitePoint> {
+        let jz = self.lightness + D0;
+        let iz = jz / (1.0 + D - D * jz);
+
+        let lms = transform(&IAB_TO_LMS, Components(iz, self.a, self.b));
+        let lms = lms.map(|v| pq_decode(v, M2) * 10000.0);
+        let Components(xm, ym, z) = transform(&LMS_TO_XYZ, lms);
+
+        let x = (xm + (B - 1.0) * z) / B;
+        let y = (ym + (G - 1.0) * x) / G;
+
+        let Components(x, y, z) = Components(x, y, z).map(|v| v / WHITE_LUMINANCE);
+        Xyz::new(x, y, z)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        assert_component_eq,
+        models::{Srgb, ToBase, WhitePoint},
+    };
+
+    #[test]
+    fn white_is_achromatic() {
+        let Components(x, y, z) = D65::WHITE_POINT;
+        let white = Jzazbz::from(XyzD65::new(x, y, z));
+
+        assert_component_eq!(white.lightness, 0.222065, epsilon = 1.0e-4);
+        assert_component_eq!(white.a, 0.0, epsilon = 1.0e-3);
+        assert_component_eq!(white.b, 0.0, epsilon = 1.0e-3);
+    }
+
+    #[test]
+    fn srgb_red_to_jzazbz() {
+        let red = Jzazbz::from(Srgb::new(1.0, 0.0, 0.0).to_base());
+
+        assert_component_eq!(red.lightness, 0.134385, epsilon = 1.0e-4);
+        assert_component_eq!(red.a, 0.117885, epsilon = 1.0e-4);
+        assert_component_eq!(red.b, 0.111878, epsilon = 1.0e-4);
+    }
+
+    #[test]
+    fn jzazbz_round_trip() {
+        let xyz = Srgb::new(0.2, 0.6, 0.4).to_base();
+        let back = Jzazbz::from(xyz.clone()).to_xyz();
+
+        assert_component_eq!(back.x, xyz.x, epsilon = 1.0e-4);
+        assert_component_eq!(back.y, xyz.y, epsilon = 1.0e-4);
+        assert_component_eq!(back.z, xyz.z, epsilon = 1.0e-4);
+
+        let jzczhz = Jzazbz::from(xyz.clone()).to_polar();
+        let back = jzczhz.to_rectangular().to_xyz();
+        assert_component_eq!(back.y, xyz.y, epsilon = 1.0e-4);
+    }
+}
@@ -4,7 +4,7 @@
 use crate::{
     color::{Components, CssColorSpaceId, Space},
     color_space,
-    math::{almost_zero, normalize_hue, transform, transform_3x3, Transform},
+    math::{ch_to_ab, normalize_hue, transform, transform_3x3, Transform},
     models::xyz::{ToXyz, WhitePoint, Xyz, XyzD50, XyzD65, D50, D65},
     Component,
 };
@@ -24,9 +24,22 @@ camelion_macros::gen_model! {
 impl<S: color_space::ColorSpace> Rectangular<S> {
     /// Convert this orthogonal rectangular model into its cylindrical polar
     /// form.
+    ///
+    /// The hue is missing (NaN) if the chroma is at or below the achromatic
+    /// threshold of the color space, e.g. 0.0015 for Lab and 0.000004 for
+    /// Oklab.
     pub fn to_polar(&self) -> Polar<S> {
+        self.to_polar_with_threshold(S::ACHROMATIC_THRESHOLD)
+    }
+
+    /// Same as [`Rectangular::to_polar`], but the hue is missing (NaN) if the
+    /// chroma is at or below the given `threshold`.
+    pub fn to_polar_with_threshold(&self, threshold: Component) -> Polar<S> {
+        // Not using `ab_to_ch`, because it already makes the hue powerless
+        // for a chroma that is almost zero, which would ignore a smaller
+        // `threshold`.
         let chroma = (self.a * self.a + self.b * self.b).sqrt();
-        let hue = if almost_zero(chroma) {
+        let hue = if chroma <= threshold {
             Component::NAN
         } else {
             normalize_hue(self.b.atan2(self.a).to_degrees())
@@ -52,10 +65,7 @@ impl<S: color_space::ColorSpace> Polar<S> {
     /// Convert this cylindrical polar model into its orthogonal rectangular
     /// form.
     pub fn to_rectangular(&self) -> Rectangular<S> {
-        let hue = self.hue.to_radians();
-        let a = self.chroma * hue.cos();
-        let b = self.chroma * hue.sin();
-
+        let (a, b) = ch_to_ab(self.chroma, self.hue);
         Rectangular::new(self.lightness, a, b)
     }
 }
@@ -67,82 +77,151 @@ impl CssColorSpaceId for Lab {
     const ID: Space = Space::Lab;
 }
 
+/// Convert CIE-Lab components to CIE-XYZ coordinates relative to the given
+/// white point.
+fn lab_to_xyz(lab: Components, white_point: Components) -> Components {
+    const KAPPA: Component = 24389.0 / 27.0;
+    const EPSILON: Component = 216.0 / 24389.0;
+
+    let Components(lightness, a, b) = lab;
+
+    let f1 = (lightness + 16.0) / 116.0;
+    let f0 = f1 + a / 500.0;
+    let f2 = f1 - b / 200.0;
+
+    let f0_cubed = f0 * f0 * f0;
+    let x = if f0_cubed > EPSILON {
+        f0_cubed
+    } else {
+        (116.0 * f0 - 16.0) / KAPPA
+    };
+
+    let y = if lightness > KAPPA * EPSILON {
+        let v = (lightness + 16.0) / 116.0;
+        v * v * v
+    } else {
+        lightness / KAPPA
+    };
+
+    let f2_cubed = f2 * f2 * f2;
+    let z = if f2_cubed > EPSILON {
+        f2_cubed
+    } else {
+        (116.0 * f2 - 16.0) / KAPPA
+    };
+
+    Components(x * white_point.0, y * white_point.1, z * white_point.2)
+}
+
+/// Convert CIE-XYZ coordinates relative to the given white point to CIE-Lab
+/// components.
+fn xyz_to_lab(xyz: Components, white_point: Components) -> Components {
+    const KAPPA: Component = 24389.0 / 27.0;
+    const EPSILON: Component = 216.0 / 24389.0;
+
+    let adapted = Components(
+        xyz.0 / white_point.0,
+        xyz.1 / white_point.1,
+        xyz.2 / white_point.2,
+    );
+
+    let Components(f0, f1, f2) = adapted.map(|v| {
+        if v > EPSILON {
+            v.cbrt()
+        } else {
+            (KAPPA * v + 16.0) / 116.0
+        }
+    });
+
+    let lightness = 116.0 * f1 - 16.0;
+    let a = 500.0 * (f0 - f1);
+    let b = 200.0 * (f1 - f2);
+
+    Components(lightness, a, b)
+}
+
 impl ToXyz for Lab {
     type WhitePoint = D50;
 
     fn to_xyz(&self) -> Xyz<Self::WhitePoint> {
-        const KAPPA: Component = 24389.0 / 27.0;
-        const EPSILON: Component = 216.0 / 24389.0;
-
-        // To avoid accessing the values through self all the time.
-        let (lightness, a, b) = (self.lightness, self.a, self.b);
+        let lab = Components(self.lightness, self.a, self.b);
+        lab_to_xyz(lab, D50::WHITE_POINT).into()
+    }
+}
 
-        let f1 = (lightness + 16.0) / 116.0;
-        let f0 = f1 + a / 500.0;
-        let f2 = f1 - b / 200.0;
+impl From<XyzD50> for Lab {
+    fn from(value: XyzD50) -> Self {
+        let xyz = Components(value.x, value.y, value.z);
+        xyz_to_lab(xyz, D50::WHITE_POINT).into()
+    }
+}
 
-        let f0_cubed = f0 * f0 * f0;
-        let x = if f0_cubed > EPSILON {
-            f0_cubed
-        } else {
-            (116.0 * f0 - 16.0) / KAPPA
-        };
+/// The model for a color specified in the CIE-Lab color space with the cylindrical polar form.
+pub type Lch = Polar<color_space::Lab>;
 
-        let y = if lightness > KAPPA * EPSILON {
-            let v = (lightness + 16.0) / 116.0;
-            v * v * v
-        } else {
-            lightness / KAPPA
-        };
+impl CssColorSpaceId for Lch {
+    const ID: Space = Space::Lch;
+}
 
-        let f2_cubed = f2 * f2 * f2;
-        let z = if f2_cubed > EPSILON {
-            f2_cubed
-        } else {
-            (116.0 * f2 - 16.0) / KAPPA
-        };
+impl Lch {
+    /// The lightness and chroma values that represent 100%.
+    /// <https://drafts.csswg.org/css-color-4/#specifying-lab-lch>
+    const PERCENT_REFERENCE: (Component, Component) = (100.0, 150.0);
+
+    /// Create a new model with the lightness and chroma specified as
+    /// percentages, where 1.0 is 100%. 100% lightness is 100 and 100% chroma
+    /// is 150.
+    pub fn from_percentages(lightness: Component, chroma: Component, hue: Component) -> Self {
+        let (l, c) = Self::PERCENT_REFERENCE;
+        Self::new(lightness * l, chroma * c, hue)
+    }
 
-        Xyz::new(
-            x * D50::WHITE_POINT.0,
-            y * D50::WHITE_POINT.1,
-            z * D50::WHITE_POINT.2,
-        )
+    /// Return the lightness, chroma and hue of this model, with the lightness
+    /// and chroma as percentages, where 1.0 is 100%. See
+    /// [`Lch::from_percentages`].
+    pub fn to_percentages(&self) -> Components {
+        let (l, c) = Self::PERCENT_REFERENCE;
+        Components(self.lightness / l, self.chroma / c, self.hue)
     }
 }
 
-impl From<XyzD50> for Lab {
-    fn from(value: XyzD50) -> Self {
-        const KAPPA: Component = 24389.0 / 27.0;
-        const EPSILON: Component = 216.0 / 24389.0;
+/// The model for a color specified in the CIE-Lab color space with a D65
+/// white point, using the rectangular orthogonal form.
+///
+/// NOTE: This is not the CSS `lab()` color space, which uses a D50 white point
+///       (see [`Lab`]). The components are calculated directly from CIE-XYZ
+///       with a D65 white point, without chromatic adaptation.
+pub type LabD65 = Rectangular<color_space::LabD65>;
 
-        let adapted = Components(
-            value.x / D50::WHITE_POINT.0,
-            value.y / D50::WHITE_POINT.1,
-            value.z / D50::WHITE_POINT.2,
-        );
+impl CssColorSpaceId for LabD65 {
+    const ID: Space = Space::LabD65;
+}
 
-        // 4. Convert D50-adapted XYZ to Lab.
-        let Components(f0, f1, f2) = adapted.map(|v| {
-            if v > EPSILON {
-                v.cbrt()
-            } else {
-                (KAPPA * v + 16.0) / 116.0
-            }
-        });
+impl ToXyz for LabD65 {
+    type WhitePoint = D65;
 
-        let lightness = 116.0 * f1 - 16.0;
-        let a = 500.0 * (f0 - f1);
-        let b = 200.0 * (f1 - f2);
+    fn to_xyz(&self) -> Xyz<Self::WhitePoint> {
+        let lab = Components(self.lightness, self.a, self.b);
+        lab_to_xyz(lab, D65::WHITE_POINT).into()
+    }
+}
 
-        Lab::new(lightness, a, b)
+impl From<XyzD65> for LabD65 {
+    fn from(value: XyzD65) -> Self {
+        let xyz = Components(value.x, value.y, value.z);
+        xyz_to_lab(xyz, D65::WHITE_POINT).into()
     }
 }
 
-/// The model for a color specified in the CIE-Lab color space with the cylindrical polar form.
-pub type Lch = Polar<color_space::Lab>;
+/// The model for a color specified in the CIE-Lab color space with a D65
+/// white point, using the cylindrical polar form.
+///
+/// NOTE: This is not the CSS `lch()` color space, which uses a D50 white point
+///       (see [`Lch`]).
+pub type LchD65 = Polar<color_space::LabD65>;
 
-impl CssColorSpaceId for Lch {
-    const ID: Space = Space::Lch;
+impl CssColorSpaceId for LchD65 {
+    const ID: Space = Space::LchD65;
 }
 
 /// The model for a color specified in the oklab color space with the rectangular orthogonal form.
@@ -208,9 +287,33 @@ impl CssColorSpaceId for Oklch {
     const ID: Space = Space::Oklch;
 }
 
+impl Oklch {
+    /// The lightness and chroma values that represent 100%.
+    /// <https://drafts.csswg.org/css-color-4/#specifying-oklab-oklch>
+    const PERCENT_REFERENCE: (Component, Component) = (1.0, 0.4);
+
+    /// Create a new model with the lightness and chroma specified as
+    /// percentages, where 1.0 is 100%. 100% lightness is 1.0 and 100% chroma
+    /// is 0.4, e.g. `oklch(40% 50% 30)` is `Oklch::from_percentages(0.4, 0.5,
+    /// 30.0)`.
+    pub fn from_percentages(lightness: Component, chroma: Component, hue: Component) -> Self {
+        let (l, c) = Self::PERCENT_REFERENCE;
+        Self::new(lightness * l, chroma * c, hue)
+    }
+
+    /// Return the lightness, chroma and hue of this model, with the lightness
+    /// and chroma as percentages, where 1.0 is 100%. See
+    /// [`Oklch::from_percentages`].
+    pub fn to_percentages(&self) -> Components {
+        let (l, c) = Self::PERCENT_REFERENCE;
+        Components(self.lightness / l, self.chroma / c, self.hue)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::models::Jzazbz;
 
     #[test]
     fn converting_to_polar_should_set_hue_to_missing_for_small_a_b_values() {
@@ -219,4 +322,107 @@ mod tests {
 
         assert!(polar.hue.is_nan());
     }
+
+    #[test]
+    fn achromatic_threshold_per_color_space() {
+        // Lab chroma goes up to ~150.
+        assert!(Lab::new(50.0, 0.001, 0.0).to_polar().hue.is_nan());
+        assert!(!Lab::new(50.0, 0.01, 0.0).to_polar().hue.is_nan());
+        assert!(LabD65::new(50.0, 0.0, -0.001).to_polar().hue.is_nan());
+        assert!(!LabD65::new(50.0, 0.0, -0.01).to_polar().hue.is_nan());
+
+        // Oklab chroma goes up to ~0.4.
+        assert!(Oklab::new(0.5, 0.000003, 0.0).to_polar().hue.is_nan());
+        assert!(!Oklab::new(0.5, 0.00001, 0.0).to_polar().hue.is_nan());
+
+        // Jzazbz uses the default threshold.
+        assert!(Jzazbz::new(0.1, 0.0, 0.0).to_polar().hue.is_nan());
+        assert!(!Jzazbz::new(0.1, 0.000001, 0.0).to_polar().hue.is_nan());
+    }
+
+    #[test]
+    fn converting_to_polar_with_custom_threshold() {
+        let lab = Lab::new(50.0, 0.3, 0.4);
+        assert!(lab.to_polar_with_threshold(0.5).hue.is_nan());
+
+        let polar = lab.to_polar_with_threshold(0.4);
+        assert_eq!(polar.chroma, 0.5);
+        assert!(!polar.hue.is_nan());
+
+        // Thresholds below the precision of `almost_zero` are respected.
+        let lab = Lab::new(50.0, 0.0, 1.0e-9);
+        assert!(lab.to_polar().hue.is_nan());
+        assert_eq!(lab.to_polar_with_threshold(0.0).hue, 90.0);
+    }
+
+    #[test]
+    fn converting_to_rectangular_with_missing_hue_should_zero_a_b() {
+        let oklch = Oklch::new(0.5, 0.1, Component::NAN);
+        let oklab = oklch.to_rectangular();
+
+        assert_eq!(oklab.lightness, 0.5);
+        assert_eq!(oklab.a, 0.0);
+        assert_eq!(oklab.b, 0.0);
+    }
+
+    #[test]
+    fn negative_chroma_is_clamped_to_zero() {
+        // lch(50 -10 30)
+        let lab = Lch::new(50.0, -10.0, 30.0).to_rectangular();
+
+        assert_eq!(lab.lightness, 50.0);
+        assert_eq!(lab.a, 0.0);
+        assert_eq!(lab.b, 0.0);
+    }
+
+    #[test]
+    fn lab_d65_uses_the_d65_white_point() {
+        let Components(x, y, z) = D65::WHITE_POINT;
+        let white = LabD65::from(XyzD65::new(x, y, z));
+        assert!((white.lightness - 100.0).abs() < 1.0e-3);
+        assert!(white.a.abs() < 1.0e-3);
+        assert!(white.b.abs() < 1.0e-3);
+
+        let xyz = XyzD65::new(0.2, 0.3, 0.4);
+        let back = LabD65::from(xyz.clone()).to_xyz();
+        assert!((back.x - xyz.x).abs() < 1.0e-5);
+        assert!((back.y - xyz.y).abs() < 1.0e-5);
+        assert!((back.z - xyz.z).abs() < 1.0e-5);
+    }
+
+    #[test]
+    fn chroma_as_percentage() {
+        // oklch(40% 50% 30)
+        let oklch = Oklch::from_percentages(0.4, 0.5, 30.0);
+        assert_eq!(oklch.lightness, 0.4);
+        assert_eq!(oklch.chroma, 0.2);
+        assert_eq!(oklch.hue, 30.0);
+        assert_eq!(oklch.to_percentages(), Components(0.4, 0.5, 30.0));
+
+        // lch(40% 50% 30)
+        let lch = Lch::from_percentages(0.4, 0.5, 30.0);
+        assert_eq!(lch.lightness, 40.0);
+        assert_eq!(lch.chroma, 75.0);
+        assert_eq!(lch.hue, 30.0);
+        assert_eq!(lch.to_percentages(), Components(0.4, 0.5, 30.0));
+    }
+
+    #[test]
+    fn achromatic_oklch_round_trips_through_oklab() {
+        use crate::{Color, Space};
+
+        let oklch = Color::new(Space::Oklch, 0.5, 0.0, None, 1.0);
+
+        let oklab = oklch.to_space(Space::Oklab);
+        assert_eq!(oklab.space, Space::Oklab);
+        assert_eq!(oklab.c0(), Some(0.5));
+        assert_eq!(oklab.c1(), Some(0.0));
+        assert_eq!(oklab.c2(), Some(0.0));
+
+        let oklch = oklab.to_space(Space::Oklch);
+        assert_eq!(oklch.space, Space::Oklch);
+        assert_eq!(oklch.c0(), Some(0.5));
+        assert_eq!(oklch.c1(), Some(0.0));
+        assert_eq!(oklch.c2(), None);
+    }
 }
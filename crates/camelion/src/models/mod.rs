@@ -5,14 +5,26 @@
 use crate::{color::Color, Component};
 
 mod base;
+mod cam16;
+mod hct;
 mod hsl;
+mod hsv;
 mod hwb;
+mod ictcp;
+mod ipt;
+mod jzazbz;
 mod lab;
 mod rgb;
 mod xyz;
 
+pub use cam16::{Cam16, Surround, ViewingConditions};
+pub use hct::*;
 pub use hsl::*;
+pub use hsv::*;
 pub use hwb::*;
+pub use ictcp::*;
+pub use ipt::*;
+pub use jzazbz::*;
 pub use lab::*;
 pub use rgb::*;
 pub use xyz::*;
@@ -0,0 +1,123 @@
+//! Hex notation for sRGB colors.
+//! <https://drafts.csswg.org/css-color-4/#hex-notation>
+
+use super::Srgb;
+use crate::{math::normalize, Component};
+
+impl Srgb {
+    /// Parse a color in hex notation: `#rgb`, `#rgba`, `#rrggbb` or
+    /// `#rrggbbaa`, with or without the leading `#`. The alpha digits are
+    /// ignored, see [`Srgb::from_hex_with_alpha`]. Returns None if the
+    /// number of digits is not valid or a digit is not hexadecimal.
+    pub fn from_hex(hex: &str) -> Option<Srgb> {
+        Self::from_hex_with_alpha(hex).map(|(srgb, _)| srgb)
+    }
+
+    /// Same as [`Srgb::from_hex`], but also returns the alpha, or None if
+    /// the hex notation has no alpha digits.
+    pub fn from_hex_with_alpha(hex: &str) -> Option<(Srgb, Option<Component>)> {
+        let digits = hex.strip_prefix('#').unwrap_or(hex);
+        // `from_str_radix` would accept a leading '+', so check every digit.
+        if !digits.chars().all(|digit| digit.is_ascii_hexdigit()) {
+            return None;
+        }
+
+        let values = match digits.len() {
+            // Each digit is duplicated, e.g. "f" is "ff".
+            3 | 4 => digits
+                .chars()
+                .map(|digit| digit.to_digit(16).map(|v| v * 17))
+                .collect::<Option<Vec<_>>>()?,
+            6 | 8 => (0..digits.len())
+                .step_by(2)
+                .map(|i| u32::from_str_radix(&digits[i..i + 2], 16).ok())
+                .collect::<Option<Vec<_>>>()?,
+            _ => return None,
+        };
+
+        let component = |value: u32| value as Component / 255.0;
+        let srgb = Srgb::new(
+            component(values[0]),
+            component(values[1]),
+            component(values[2]),
+        );
+        Some((srgb, values.get(3).copied().map(component)))
+    }
+
+    /// Format this color in lowercase hex notation, `#rrggbb`. Components
+    /// are clamped to [0..1] and missing (NaN) components are formatted as
+    /// 0.
+    pub fn to_hex(&self) -> String {
+        self.to_hex_with_alpha(None)
+    }
+
+    /// Same as [`Srgb::to_hex`], but formats the color as `#rrggbbaa` if
+    /// `alpha` is present and not 1.
+    pub fn to_hex_with_alpha(&self, alpha: Option<Component>) -> String {
+        let byte = |v: Component| (normalize(v).clamp(0.0, 1.0) * 255.0).round() as u8;
+
+        let mut hex = format!(
+            "#{:02x}{:02x}{:02x}",
+            byte(self.red),
+            byte(self.green),
+            byte(self.blue)
+        );
+        if let Some(alpha) = alpha.filter(|alpha| *alpha != 1.0) {
+            hex.push_str(&format!("{:02x}", byte(alpha)));
+        }
+
+        hex
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_component_eq;
+
+    #[test]
+    fn parse_hex() {
+        let srgb = Srgb::from_hex("#ff8000").unwrap();
+        assert_component_eq!(srgb.red, 1.0);
+        assert_component_eq!(srgb.green, 128.0 / 255.0);
+        assert_component_eq!(srgb.blue, 0.0);
+
+        let (short, alpha) = Srgb::from_hex_with_alpha("f80c").unwrap();
+        assert_component_eq!(short.green, 136.0 / 255.0);
+        assert_component_eq!(alpha.unwrap(), 204.0 / 255.0);
+
+        let (long, alpha) = Srgb::from_hex_with_alpha("#FF800080").unwrap();
+        assert_eq!(long, srgb);
+        assert_component_eq!(alpha.unwrap(), 128.0 / 255.0);
+        assert_eq!(Srgb::from_hex_with_alpha("#fff").unwrap().1, None);
+
+        assert_eq!(
+            Srgb::from_hex("#ff80"),
+            Some(Srgb::new(1.0, 1.0, 136.0 / 255.0))
+        );
+        assert_eq!(Srgb::from_hex("#ff800"), None);
+        assert_eq!(Srgb::from_hex("#gg8000"), None);
+        assert_eq!(Srgb::from_hex("#ffé0"), None);
+        assert_eq!(Srgb::from_hex("#+f+f+f"), None);
+        assert_eq!(Srgb::from_hex("+fff"), None);
+        assert_eq!(Srgb::from_hex(""), None);
+    }
+
+    #[test]
+    fn format_hex() {
+        assert_eq!(Srgb::new(1.0, 0.5, 0.0).to_hex(), "#ff8000");
+        assert_eq!(Srgb::new(1.5, -0.5, Component::NAN).to_hex(), "#ff0000");
+        assert_eq!(
+            Srgb::new(0.0, 0.0, 1.0).to_hex_with_alpha(Some(1.0)),
+            "#0000ff"
+        );
+        assert_eq!(
+            Srgb::new(0.0, 0.0, 1.0).to_hex_with_alpha(Some(0.5)),
+            "#0000ff80"
+        );
+
+        let hex = "#12abef7f";
+        let (srgb, alpha) = Srgb::from_hex_with_alpha(hex).unwrap();
+        assert_eq!(srgb.to_hex_with_alpha(alpha), hex);
+    }
+}
@@ -2,15 +2,16 @@
 
 mod gamma;
 mod gamut;
+mod hex;
 
 pub use gamma::HasGammaEncoding;
 
 use crate::{
-    color::{Components, CssColorSpaceId, Space},
+    color::{Color, Components, CssColorSpaceId, Space},
     color_space::{self, ColorSpace},
-    math::{transform, transform_3x3, Transform},
+    math::{normalize, transform, transform_3x3, Transform},
     models::xyz::{ToXyz, Xyz, XyzD50, XyzD65, D50, D65},
-    Component,
+    Component, OutOfGamut,
 };
 
 /// Tags for RGB models that are either gamma encoded or linear light.
@@ -43,6 +44,31 @@ camelion_macros::gen_model! {
     }
 }
 
+impl<S: ColorSpace, E: encoding::GammaEncoding> Rgb<S, E> {
+    /// Quantize the components of this model to integers with the given bit
+    /// depth, e.g. 10 bits produces values in [0..1023]. Components are
+    /// clamped to [0..1] and rounded to the nearest integer. Missing (NaN)
+    /// components are quantized as 0.
+    ///
+    /// Panics if `bits` is not in [1..16].
+    pub fn to_integer(&self, bits: u8) -> [u16; 3] {
+        assert!(
+            (1..=16).contains(&bits),
+            "bit depth must be in [1..16], got {}",
+            bits
+        );
+
+        let max = ((1_u32 << bits) - 1) as Component;
+        let quantize = |v: Component| (normalize(v).clamp(0.0, 1.0) * max).round() as u16;
+
+        [
+            quantize(self.red),
+            quantize(self.green),
+            quantize(self.blue),
+        ]
+    }
+}
+
 impl<S: ColorSpace + HasGammaEncoding> Rgb<S, encoding::GammaEncoded> {
     /// Convert this model from gamma encoded to linear light.
     pub fn to_linear_light(&self) -> Rgb<S, encoding::LinearLight> {
@@ -59,6 +85,26 @@ impl<S: ColorSpace + HasGammaEncoding> Rgb<S, encoding::LinearLight> {
     }
 }
 
+impl<S: ColorSpace, E: encoding::GammaEncoding> Rgb<S, E>
+where
+    Self: CssColorSpaceId,
+{
+    /// Convert the given color into this model. Unlike [`Color::to_space`],
+    /// the conversion fails if the result is outside the gamut limits of the
+    /// color space, instead of silently producing out of range components.
+    pub fn try_from_in_gamut(color: &Color) -> Result<Self, OutOfGamut> {
+        let color = color.to_space(Self::ID);
+        if color.in_gamut() {
+            Ok(color.as_model())
+        } else {
+            Err(OutOfGamut {
+                space: Self::ID,
+                components: color.components,
+            })
+        }
+    }
+}
+
 /// Model for a color in the sRGB color space with gamma encoding.
 pub type Srgb = Rgb<color_space::Srgb, encoding::GammaEncoded>;
 
@@ -267,7 +313,7 @@ impl From<XyzD65> for Rec2020Linear {
 mod tests {
     use super::*;
     use crate::models::Model;
-    use crate::Flags;
+    use crate::{assert_component_eq, Flags};
 
     #[test]
     fn nan_is_missing_component() {
@@ -277,4 +323,50 @@ mod tests {
         assert_eq!(c.components.2, 1.0);
         assert_eq!(c.flags, Flags::C0_IS_NONE);
     }
+
+    #[test]
+    fn quantize_to_bit_depths() {
+        for (bits, max) in [(8, 255), (10, 1023), (12, 4095), (16, 65535)] {
+            let white = Rec2020::new(1.0, 1.0, 1.0);
+            assert_eq!(white.to_integer(bits), [max; 3]);
+
+            let black = DisplayP3::new(0.0, 0.0, 0.0);
+            assert_eq!(black.to_integer(bits), [0; 3]);
+
+            // Values outside [0..1] are clamped.
+            let out_of_range = Srgb::new(-0.5, 1.5, Component::NAN);
+            assert_eq!(out_of_range.to_integer(bits), [0, max, 0]);
+        }
+
+        let gray = Rec2020::new(0.5, 0.25, 1.0 / 1023.0);
+        assert_eq!(gray.to_integer(8), [128, 64, 0]);
+        assert_eq!(gray.to_integer(10), [512, 256, 1]);
+        assert_eq!(gray.to_integer(12), [2048, 1024, 4]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn quantize_to_invalid_bit_depth() {
+        Srgb::new(0.0, 0.0, 0.0).to_integer(17);
+    }
+
+    #[test]
+    fn try_from_in_gamut() {
+        let white = Color::new(Space::DisplayP3, 1.0, 1.0, 1.0, 1.0);
+        let srgb = Srgb::try_from_in_gamut(&white).unwrap();
+        assert_component_eq!(srgb.red, 1.0);
+        assert_component_eq!(srgb.green, 1.0);
+        assert_component_eq!(srgb.blue, 1.0);
+
+        // color(display-p3 1 0 0) is outside the sRGB gamut.
+        let red = Color::new(Space::DisplayP3, 1.0, 0.0, 0.0, 1.0);
+        let err = Srgb::try_from_in_gamut(&red).unwrap_err();
+        assert_eq!(err.space, Space::Srgb);
+        assert!(err.components.0 > 1.0);
+        assert!(err.components.1 < 0.0);
+
+        // The wider rec2020 gamut contains all sRGB colors.
+        let red = Color::new(Space::Srgb, 1.0, 0.0, 0.0, 1.0);
+        assert!(Rec2020::try_from_in_gamut(&red).is_ok());
+    }
 }
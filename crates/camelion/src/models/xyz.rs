@@ -2,10 +2,17 @@
 
 use crate::{
     color::{Components, CssColorSpaceId, Space},
+    illuminant::{Adaptation, AdaptationMethod},
     math::{transform, transform_3x3, Transform},
     Component,
 };
 
+/// The luminance in cd/m² of a diffuse white (a Y of 1.0), used by color
+/// spaces that are defined on absolute luminance, like [`ICtCp`](super::ICtCp)
+/// and [`Jzazbz`](super::Jzazbz).
+/// <https://www.itu.int/pub/R-REP-BT.2408>
+pub const WHITE_LUMINANCE: Component = 203.0;
+
 /// This trait is used for types that represent a CIE-XYZ white point
 /// reference.
 pub trait WhitePoint {
@@ -31,57 +38,79 @@ impl WhitePoint for D65 {
     const WHITE_POINT: Components = Components(0.9504559270516716, 1.0, 1.0890577507598784);
 }
 
-/// This trait is implemented on [`WhitePoint`]'s to transfer colors between
-/// them.
-pub trait TransferWhitePoint<T: WhitePoint>
-where
-    Self: WhitePoint + Sized,
-{
-    /// Transfer the white point reference to another.
-    fn transfer(from: &Xyz<Self>) -> Xyz<T>;
+/// CIE-XYZ color with a D55 white point reference.
+#[derive(Clone, Debug)]
+pub struct D55;
+
+impl WhitePoint for D55 {
+    const WHITE_POINT: Components = Components(0.95682, 1.0, 0.92149);
 }
 
-impl TransferWhitePoint<D65> for D50 {
-    /// Convert this model from CIE-XYZ with a D50 white point to a D65 white
-    /// point.
-    fn transfer(from: &Xyz<Self>) -> Xyz<D65> {
-        #[rustfmt::skip]
-        #[allow(clippy::excessive_precision)]
-        const MAT: Transform = transform_3x3(
-             0.9554734527042182,   -0.028369706963208136,  0.012314001688319899,
-            -0.023098536874261423,  1.0099954580058226,   -0.020507696433477912,
-             0.0632593086610217,    0.021041398966943008,  1.3303659366080753,
-        );
+/// CIE-XYZ color with a D75 white point reference.
+#[derive(Clone, Debug)]
+pub struct D75;
 
-        transform(&MAT, Components(from.x, from.y, from.z)).into()
-    }
+impl WhitePoint for D75 {
+    const WHITE_POINT: Components = Components(0.94972, 1.0, 1.22638);
 }
 
-impl TransferWhitePoint<D50> for D65 {
-    /// Convert this model from CIE-XYZ with a D65 white point to a D50 white
-    /// point.
-    fn transfer(from: &Xyz<Self>) -> Xyz<D50> {
-        #[rustfmt::skip]
-        #[allow(clippy::excessive_precision)]
-        const MAT: Transform = transform_3x3(
-             1.0479298208405488,    0.029627815688159344, -0.009243058152591178,
-             0.022946793341019088,  0.990434484573249,     0.015055144896577895,
-            -0.05019222954313557,  -0.01707382502938514,   0.7518742899580008,
-        );
+/// CIE-XYZ color with the equal-energy (E) white point reference.
+#[derive(Clone, Debug)]
+pub struct E;
 
-        transform(&MAT, Components(from.x, from.y, from.z)).into()
-    }
+impl WhitePoint for E {
+    const WHITE_POINT: Components = Components(1.0, 1.0, 1.0);
 }
 
-impl<W: WhitePoint> TransferWhitePoint<W> for W
+/// The Bradford adaptation from the D50 to the D65 white point.
+#[rustfmt::skip]
+#[allow(clippy::excessive_precision)]
+const D50_TO_D65: Transform = transform_3x3(
+     0.9554734527042182,   -0.028369706963208136,  0.012314001688319899,
+    -0.023098536874261423,  1.0099954580058226,   -0.020507696433477912,
+     0.0632593086610217,    0.021041398966943008,  1.3303659366080753,
+);
+
+/// The Bradford adaptation from the D65 to the D50 white point.
+#[rustfmt::skip]
+#[allow(clippy::excessive_precision)]
+const D65_TO_D50: Transform = transform_3x3(
+     1.0479298208405488,    0.029627815688159344, -0.009243058152591178,
+     0.022946793341019088,  0.990434484573249,     0.015055144896577895,
+    -0.05019222954313557,  -0.01707382502938514,   0.7518742899580008,
+);
+
+/// This trait is implemented on [`WhitePoint`]'s to transfer colors between
+/// them. It is implemented for every pair of white points, including custom
+/// ones, by chromatically adapting with the Bradford transform.
+pub trait TransferWhitePoint<T: WhitePoint>
 where
-    W: Clone,
+    Self: WhitePoint + Sized,
 {
+    /// Transfer the white point reference to another.
+    fn transfer(from: &Xyz<Self>) -> Xyz<T>;
+}
+
+impl<F: WhitePoint, T: WhitePoint> TransferWhitePoint<T> for F {
+    /// Convert this model from CIE-XYZ with the `F` white point to the `T`
+    /// white point. Transferring between the same white points is a noop and
+    /// the matrices between D50 and D65 are precomputed, any other pair is
+    /// adapted with [`Xyz::adapt_to`].
     #[inline]
-    fn transfer(from: &Xyz<Self>) -> Xyz<W> {
-        // Transferring between the same white point references should be a
-        // noop.
-        from.clone()
+    fn transfer(from: &Xyz<Self>) -> Xyz<T> {
+        let components = Components(from.x, from.y, from.z);
+
+        // The white points are constants, so these branches are resolved at
+        // compile time.
+        if F::WHITE_POINT == T::WHITE_POINT {
+            components.into()
+        } else if F::WHITE_POINT == D50::WHITE_POINT && T::WHITE_POINT == D65::WHITE_POINT {
+            transform(&D50_TO_D65, components).into()
+        } else if F::WHITE_POINT == D65::WHITE_POINT && T::WHITE_POINT == D50::WHITE_POINT {
+            transform(&D65_TO_D50, components).into()
+        } else {
+            from.adapt_to(AdaptationMethod::Bradford)
+        }
     }
 }
 
@@ -114,6 +143,46 @@ impl<W: WhitePoint> Xyz<W> {
     {
         W::transfer(self)
     }
+
+    /// Chromatically adapt this color to the white point reference `T`. The
+    /// adaptation matrix is calculated from the [`WhitePoint::WHITE_POINT`]s
+    /// of both white points with the given method, so any pair of white
+    /// points is supported. See [`Adaptation::with_method`].
+    ///
+    /// NOTE: [`Xyz::transfer`] always uses the Bradford transform and a
+    ///       precomputed matrix between the D50 and D65 white points, which
+    ///       is faster when converting many colors.
+    pub fn adapt_to<T: WhitePoint>(&self, method: AdaptationMethod) -> Xyz<T> {
+        Adaptation::with_method(W::WHITE_POINT, T::WHITE_POINT, method)
+            .adapt(Components(self.x, self.y, self.z))
+            .into()
+    }
+
+    /// Create a color from absolute components in cd/m², where
+    /// `peak_luminance` is the luminance that maps to a Y of 1.0.
+    pub fn from_absolute(
+        x: Component,
+        y: Component,
+        z: Component,
+        peak_luminance: Component,
+    ) -> Self {
+        Self::new(x / peak_luminance, y / peak_luminance, z / peak_luminance)
+    }
+
+    /// Return the components of this color scaled to absolute luminance in
+    /// cd/m², where a Y of 1.0 maps to `peak_luminance`.
+    ///
+    /// NOTE: The [`pq_oetf`](crate::transfer::pq_oetf) transfer function
+    ///       expects 1.0 to be 10000 cd/m², so the absolute components have to
+    ///       be divided by 10000 first. Use [`WHITE_LUMINANCE`] as the peak to
+    ///       match the ICtCp and Jzazbz color spaces.
+    pub fn to_absolute(&self, peak_luminance: Component) -> Self {
+        Self::new(
+            self.x * peak_luminance,
+            self.y * peak_luminance,
+            self.z * peak_luminance,
+        )
+    }
 }
 
 /// Model for a color in the CIE-XYZ color space with a D50 white point.
@@ -129,3 +198,153 @@ pub type XyzD65 = Xyz<D65>;
 impl CssColorSpaceId for XyzD65 {
     const ID: Space = Space::XyzD65;
 }
+
+impl XyzD65 {
+    /// The range of temperatures in kelvin supported by
+    /// [`XyzD65::from_blackbody`].
+    pub const BLACKBODY_RANGE: (Component, Component) = (1667.0, 25000.0);
+
+    /// Create the color of a blackbody radiator (on the Planckian locus) at
+    /// the given temperature in kelvin, with a luminance (Y) of 1. The
+    /// chromaticity is calculated with the cubic spline approximation by
+    /// Kim et al. The temperature is clamped to
+    /// [`XyzD65::BLACKBODY_RANGE`].
+    /// <https://en.wikipedia.org/wiki/Planckian_locus#Approximation>
+    ///
+    /// NOTE: The components are not chromatically adapted, e.g. 6500K is
+    ///       close to, but not exactly, the D65 white point.
+    #[allow(clippy::excessive_precision)]
+    pub fn from_blackbody(kelvin: Component) -> XyzD65 {
+        let t = kelvin.clamp(Self::BLACKBODY_RANGE.0, Self::BLACKBODY_RANGE.1);
+        let (t2, t3) = (t * t, t * t * t);
+
+        let x = if t <= 4000.0 {
+            -0.2661239e9 / t3 - 0.2343589e6 / t2 + 0.8776956e3 / t + 0.179910
+        } else {
+            -3.0258469e9 / t3 + 2.1070379e6 / t2 + 0.2226347e3 / t + 0.240390
+        };
+
+        let (x2, x3) = (x * x, x * x * x);
+        let y = if t <= 2222.0 {
+            -1.1063814 * x3 - 1.34811020 * x2 + 2.18555832 * x - 0.20219683
+        } else if t <= 4000.0 {
+            -0.9549476 * x3 - 1.37418593 * x2 + 2.09137015 * x - 0.16748867
+        } else {
+            3.0817580 * x3 - 5.87338670 * x2 + 3.75112997 * x - 0.37001483
+        };
+
+        // xyY to XYZ, with Y = 1.
+        XyzD65::new(x / y, 1.0, (1.0 - x - y) / y)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_component_eq;
+
+    #[test]
+    fn absolute_luminance() {
+        let xyz = XyzD65::from_absolute(190.0, 200.0, 218.0, 200.0);
+        assert_component_eq!(xyz.x, 0.95);
+        assert_component_eq!(xyz.y, 1.0);
+        assert_component_eq!(xyz.z, 1.09);
+
+        let absolute = xyz.to_absolute(WHITE_LUMINANCE);
+        assert_component_eq!(absolute.y, WHITE_LUMINANCE);
+
+        let back = XyzD65::from_absolute(absolute.x, absolute.y, absolute.z, WHITE_LUMINANCE);
+        assert_component_eq!(back.x, xyz.x);
+        assert_component_eq!(back.z, xyz.z);
+    }
+
+    #[test]
+    fn adapt_between_white_points() {
+        let color = XyzD65::new(0.3, 0.4, 0.5);
+
+        let adapted = color.adapt_to::<D50>(AdaptationMethod::Bradford);
+        let transferred = color.transfer::<D50>();
+        assert_component_eq!(adapted.x, transferred.x);
+        assert_component_eq!(adapted.y, transferred.y);
+        assert_component_eq!(adapted.z, transferred.z);
+
+        for method in [AdaptationMethod::Cat02, AdaptationMethod::VonKries] {
+            let Components(x, y, z) = D65::WHITE_POINT;
+            let white = XyzD65::new(x, y, z).adapt_to::<D50>(method);
+            assert_component_eq!(white.x, D50::WHITE_POINT.0);
+            assert_component_eq!(white.y, D50::WHITE_POINT.1);
+            assert_component_eq!(white.z, D50::WHITE_POINT.2);
+
+            let back = color.adapt_to::<D50>(method).adapt_to::<D65>(method);
+            assert_component_eq!(back.x, color.x);
+            assert_component_eq!(back.y, color.y);
+            assert_component_eq!(back.z, color.z);
+        }
+    }
+
+    #[test]
+    fn transfer_between_any_white_points() {
+        /// A custom D93 white point, as used for display calibration.
+        #[derive(Clone, Debug)]
+        struct D93;
+
+        impl WhitePoint for D93 {
+            const WHITE_POINT: Components = Components(0.95288, 1.0, 1.41299);
+        }
+
+        let Components(x, y, z) = D65::WHITE_POINT;
+        let white = XyzD65::new(x, y, z);
+        for Components(wx, wy, wz) in [
+            white.transfer::<D93>().to_components(),
+            white.transfer::<D55>().transfer::<D93>().to_components(),
+        ] {
+            assert_component_eq!(wx, D93::WHITE_POINT.0);
+            assert_component_eq!(wy, D93::WHITE_POINT.1);
+            assert_component_eq!(wz, D93::WHITE_POINT.2);
+        }
+
+        let color = XyzD65::new(0.3, 0.4, 0.5);
+        assert_eq!(color.transfer::<D65>(), color);
+        for back in [
+            color.transfer::<D55>().transfer::<D65>(),
+            color.transfer::<D75>().transfer::<D65>(),
+            color.transfer::<E>().transfer::<D65>(),
+            color.transfer::<D93>().transfer::<D65>(),
+        ] {
+            assert_component_eq!(back.x, color.x);
+            assert_component_eq!(back.y, color.y);
+            assert_component_eq!(back.z, color.z);
+        }
+
+        // The precomputed matrices match the calculated adaptation.
+        let precomputed = color.transfer::<D50>();
+        let calculated = color.adapt_to::<D50>(AdaptationMethod::Bradford);
+        assert_component_eq!(precomputed.x, calculated.x);
+        assert_component_eq!(precomputed.z, calculated.z);
+    }
+
+    #[test]
+    fn blackbody() {
+        let chromaticity = |xyz: XyzD65| {
+            let sum = xyz.x + xyz.y + xyz.z;
+            (xyz.x / sum, xyz.y / sum)
+        };
+
+        // 6500K is close to the D65 white point.
+        let (x, y) = chromaticity(XyzD65::from_blackbody(6500.0));
+        let Components(wx, wy, wz) = D65::WHITE_POINT;
+        assert!((x - wx / (wx + wy + wz)).abs() < 0.01);
+        assert!((y - wy / (wx + wy + wz)).abs() < 0.01);
+
+        // Known points on the Planckian locus.
+        let (x, y) = chromaticity(XyzD65::from_blackbody(2856.0));
+        assert!((x - 0.4476).abs() < 1.0e-3);
+        assert!((y - 0.4074).abs() < 1.0e-3);
+
+        assert_eq!(
+            XyzD65::from_blackbody(1000.0),
+            XyzD65::from_blackbody(1667.0)
+        );
+        assert_eq!(XyzD65::from_blackbody(40000.0).y, 1.0);
+    }
+}
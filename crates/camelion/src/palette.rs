@@ -0,0 +1,530 @@
+//! Functions for generating and arranging palettes of related colors.
+
+use std::cmp::Ordering;
+
+use crate::{
+    color_space::{self, ColorSpace},
+    delta_e::delta_eok,
+    math::normalize_hue,
+    Color, Component, Flags, Interpolation, Space,
+};
+
+/// The kinds of color schemes that can be generated from a base color.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SchemeKind {
+    /// The base color and its complement on the opposite side of the hue
+    /// wheel.
+    Complementary,
+    /// The base color and its neighbours 30 degrees to either side.
+    Analogous,
+    /// Three colors evenly spaced around the hue wheel.
+    Triadic,
+    /// Four colors evenly spaced around the hue wheel.
+    Tetradic,
+    /// The base color and the two colors adjacent to its complement.
+    SplitComplementary,
+}
+
+impl SchemeKind {
+    /// The hue rotations in degrees applied to the base color, starting with
+    /// the base color itself.
+    fn rotations(&self) -> &'static [Component] {
+        match self {
+            SchemeKind::Complementary => &[0.0, 180.0],
+            SchemeKind::Analogous => &[0.0, -30.0, 30.0],
+            SchemeKind::Triadic => &[0.0, 120.0, 240.0],
+            SchemeKind::Tetradic => &[0.0, 90.0, 180.0, 270.0],
+            SchemeKind::SplitComplementary => &[0.0, 150.0, 210.0],
+        }
+    }
+}
+
+impl Color {
+    /// Generate a color scheme of the given kind from this color. The hues
+    /// are rotated in the Oklch color space and the resulting colors are
+    /// returned in the color space of this color, with this color first.
+    ///
+    /// Achromatic colors have no hue to rotate, so all the colors in the
+    /// scheme will be the same.
+    pub fn scheme(&self, kind: SchemeKind) -> Vec<Color> {
+        let oklch = self.to_space(Space::Oklch);
+
+        kind.rotations()
+            .iter()
+            .map(|&rotation| {
+                if rotation == 0.0 {
+                    return self.clone();
+                }
+
+                let mut rotated = oklch.clone();
+                rotated.components.2 = normalize_hue(rotated.components.2 + rotation);
+                rotated.to_space(self.space)
+            })
+            .collect()
+    }
+
+    /// Generate `count` colors with the hue and chroma of this color and
+    /// evenly spaced Oklch lightness values, from near black to near white
+    /// (pure black and white are excluded). The colors are mapped into the
+    /// gamut limits of the color space of this color and returned in it.
+    pub fn monochromatic(&self, count: usize) -> Vec<Color> {
+        let oklch = self.to_space(Space::Oklch);
+
+        (1..=count)
+            .map(|i| {
+                let mut color = oklch.clone();
+                color.components.0 = i as Component / (count + 1) as Component;
+                color.flags.remove(Flags::C0_IS_NONE);
+                color.to_space(self.space).map_into_gamut_limits()
+            })
+            .collect()
+    }
+
+    /// Generate a Material Design style tonal palette from this color. The
+    /// hue and chroma of this color are held in the Oklch color space while
+    /// the lightness is set to each of the [`TONES`], after which every tone
+    /// is mapped into the sRGB gamut.
+    pub fn tonal_palette(&self) -> [Color; 13] {
+        let oklch = self.to_space(Space::Oklch);
+
+        TONES.map(|tone| {
+            let mut color = oklch.clone();
+            color.components.0 = tone / 100.0;
+            color.flags.remove(Flags::C0_IS_NONE);
+            // Black and white have no chroma, holding it would push the
+            // lightness of the converted color off the end stops.
+            if tone <= 0.0 || tone >= 100.0 {
+                color.components.1 = 0.0;
+            }
+            color.to_space(Space::Srgb).map_into_gamut_limits()
+        })
+    }
+}
+
+/// The standard tone stops used by [`Color::tonal_palette`], from black (0)
+/// to white (100).
+pub const TONES: [Component; 13] = [
+    0.0, 10.0, 20.0, 30.0, 40.0, 50.0, 60.0, 70.0, 80.0, 90.0, 95.0, 99.0, 100.0,
+];
+
+/// A continuous ramp through a list of anchor colors, placed at equal
+/// parameter positions and interpolated in a color space.
+pub(crate) struct Ramp {
+    segments: Vec<Interpolation>,
+}
+
+impl Ramp {
+    /// Create a ramp through at least two anchors.
+    pub(crate) fn new(anchors: &[Color], space: Space) -> Self {
+        Self {
+            segments: anchors
+                .windows(2)
+                .map(|pair| pair[0].interpolate(&pair[1], space))
+                .collect(),
+        }
+    }
+
+    /// Sample the ramp at `u` in [0..1].
+    pub(crate) fn at(&self, u: Component) -> Color {
+        let scaled = u.clamp(0.0, 1.0) * self.segments.len() as Component;
+        let index = (scaled as usize).min(self.segments.len() - 1);
+        self.segments[index].at(scaled - index as Component)
+    }
+}
+
+/// Sample `steps` colors from a ramp through the `anchors`, which are placed
+/// at equal parameter positions and interpolated in the given color space.
+/// The first and last colors are the first and last anchors.
+pub fn color_ramp(anchors: &[Color], steps: usize, space: Space) -> Vec<Color> {
+    if anchors.len() < 2 || steps < 2 {
+        return anchors
+            .first()
+            .map(|anchor| vec![anchor.to_space(space); steps])
+            .unwrap_or_default();
+    }
+
+    let ramp = Ramp::new(anchors, space);
+    (0..steps)
+        .map(|i| ramp.at(i as Component / (steps - 1) as Component))
+        .collect()
+}
+
+/// Same as [`color_ramp`], but the samples are spaced so that the deltaE OK
+/// between each pair of consecutive colors is (approximately) equal, making
+/// the steps visually uniform even if the anchors are not.
+pub fn color_ramp_equalized(anchors: &[Color], steps: usize, space: Space) -> Vec<Color> {
+    // The number of samples used to measure the length of the ramp.
+    const SAMPLES_PER_SEGMENT: usize = 64;
+
+    if anchors.len() < 2 || steps < 2 {
+        return color_ramp(anchors, steps, space);
+    }
+
+    let ramp = Ramp::new(anchors, space);
+
+    // Measure the cumulative perceptual length along the ramp.
+    let samples = SAMPLES_PER_SEGMENT * ramp.segments.len();
+    let u_at = |i: usize| i as Component / samples as Component;
+    let mut lengths = Vec::with_capacity(samples + 1);
+    let mut previous = ramp.at(0.0);
+    let mut total = 0.0;
+    lengths.push(0.0);
+    for i in 1..=samples {
+        let current = ramp.at(u_at(i));
+        total += delta_eok(&previous, &current);
+        lengths.push(total);
+        previous = current;
+    }
+
+    if total == 0.0 {
+        return color_ramp(anchors, steps, space);
+    }
+
+    // Find the parameter at each target length by inverting the cumulative
+    // length table.
+    (0..steps)
+        .map(|i| {
+            let target = total * i as Component / (steps - 1) as Component;
+            let index = lengths
+                .partition_point(|length| *length < target)
+                .clamp(1, samples);
+            let (start, end) = (lengths[index - 1], lengths[index]);
+            let t = if end > start {
+                (target - start) / (end - start)
+            } else {
+                0.0
+            };
+            ramp.at(u_at(index - 1) + t / samples as Component)
+        })
+        .collect()
+}
+
+/// The key used to sort colors with [`sort_colors`]. All keys are computed
+/// in the Oklch color space.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SortKey {
+    /// Sort by hue, then lightness. Achromatic colors (with a powerless hue)
+    /// are grouped after all the chromatic colors.
+    Hue,
+    /// Sort by lightness, then chroma, then hue.
+    Lightness,
+    /// Sort by chroma, then lightness, then hue.
+    Chroma,
+}
+
+/// Sort a slice of colors perceptually according to the given key, from
+/// lowest to highest. Colors that compare equal on all the keys keep their
+/// original order.
+pub fn sort_colors(colors: &mut [Color], by: SortKey) {
+    let keys = colors
+        .iter()
+        .map(|color| {
+            let (lightness, chroma, hue) = oklch_key(color);
+
+            match by {
+                SortKey::Hue => [hue, lightness, chroma],
+                SortKey::Lightness => [lightness, chroma, hue],
+                SortKey::Chroma => [chroma, lightness, hue],
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let compare = |a: &[Component; 3], b: &[Component; 3]| {
+        a.iter()
+            .zip(b)
+            .map(|(a, b)| a.total_cmp(b))
+            .find(|ordering| *ordering != Ordering::Equal)
+            .unwrap_or(Ordering::Equal)
+    };
+
+    // The sort is stable, so ties keep their original order.
+    let mut order = (0..colors.len()).collect::<Vec<_>>();
+    order.sort_by(|a, b| compare(&keys[*a], &keys[*b]));
+
+    let sorted = order.iter().map(|i| colors[*i].clone()).collect::<Vec<_>>();
+    colors.clone_from_slice(&sorted);
+}
+
+/// Return the Oklch lightness, chroma and hue of a color for sorting.
+/// Missing components are treated as 0 and achromatic colors (with a
+/// powerless hue) get a hue of 360, which sorts them after all the hues.
+fn oklch_key(color: &Color) -> (Component, Component, Component) {
+    // Achromatic colors sort after all hues.
+    const NO_HUE: Component = 360.0;
+
+    let oklch = color.to_space(Space::Oklch);
+    let lightness = oklch.c0().unwrap_or(0.0);
+    let chroma = oklch.c1().unwrap_or(0.0);
+    let hue = if chroma <= color_space::Oklab::ACHROMATIC_THRESHOLD {
+        NO_HUE
+    } else {
+        oklch.c2().unwrap_or(NO_HUE)
+    };
+
+    (lightness, chroma, hue)
+}
+
+impl Color {
+    /// Return the key that defines the order of colors used by
+    /// [`Color::total_cmp`]: the Oklch lightness, then hue, then chroma.
+    /// Achromatic colors (with a powerless or missing hue) get a hue of 360,
+    /// so they sort after all the chromatic colors of the same lightness.
+    pub fn sort_key(&self) -> (Component, Component, Component) {
+        let (lightness, chroma, hue) = oklch_key(self);
+        (lightness, hue, chroma)
+    }
+
+    /// Compare this color to `other` by their [`Color::sort_key`]s. This is a
+    /// total order, so it can be used to sort colors deterministically, e.g.
+    /// `colors.sort_by(Color::total_cmp)`.
+    ///
+    /// NOTE: Colors that are not equal (e.g. the same color in different
+    ///       color spaces) can compare as [`Ordering::Equal`].
+    pub fn total_cmp(&self, other: &Color) -> Ordering {
+        let (a, b) = (self.sort_key(), other.sort_key());
+        a.0.total_cmp(&b.0)
+            .then(a.1.total_cmp(&b.1))
+            .then(a.2.total_cmp(&b.2))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_component_eq;
+
+    #[test]
+    fn schemes_rotate_hue() {
+        let base = Color::new(Space::Oklch, 0.7, 0.1, 30.0, 0.5);
+
+        let triadic = base.scheme(SchemeKind::Triadic);
+        assert_eq!(triadic.len(), 3);
+        assert_component_eq!(triadic[0].components.2, 30.0);
+        assert_component_eq!(triadic[1].components.2, 150.0);
+        assert_component_eq!(triadic[2].components.2, 270.0);
+
+        let analogous = base.scheme(SchemeKind::Analogous);
+        assert_component_eq!(analogous[1].components.2, 0.0);
+        assert_component_eq!(analogous[2].components.2, 60.0);
+
+        assert_eq!(base.scheme(SchemeKind::Complementary).len(), 2);
+        assert_eq!(base.scheme(SchemeKind::Tetradic).len(), 4);
+        assert_eq!(base.scheme(SchemeKind::SplitComplementary).len(), 3);
+
+        for c in base.scheme(SchemeKind::Tetradic) {
+            assert_component_eq!(c.components.0, 0.7);
+            assert_component_eq!(c.components.1, 0.1);
+            assert_eq!(c.alpha, 0.5);
+        }
+    }
+
+    #[test]
+    fn monochromatic_lightness_ramp() {
+        let base = Color::new(Space::Oklch, 0.5, 0.05, 200.0, 1.0);
+
+        let ramp = base.monochromatic(4);
+        assert_eq!(ramp.len(), 4);
+        for (i, c) in ramp.iter().enumerate() {
+            assert_component_eq!(c.components.0, (i + 1) as Component / 5.0);
+            assert_component_eq!(c.components.1, 0.05);
+            assert_component_eq!(c.components.2, 200.0);
+        }
+
+        assert!(base.monochromatic(0).is_empty());
+
+        let srgb = Color::new(Space::Srgb, 0.2, 0.4, 0.8, 1.0);
+        let ramp = srgb.monochromatic(5);
+        assert!(ramp.iter().all(|c| c.space == Space::Srgb && c.in_gamut()));
+
+        let lightness = ramp
+            .iter()
+            .map(|c| c.to_space(Space::Oklch).components.0)
+            .collect::<Vec<_>>();
+        assert!(lightness.windows(2).all(|w| w[0] < w[1]));
+    }
+
+    #[test]
+    fn tonal_palette_stops() {
+        let base = Color::new(Space::Srgb, 0.4, 0.3, 0.9, 1.0);
+        let palette = base.tonal_palette();
+
+        assert!(palette
+            .iter()
+            .all(|c| c.space == Space::Srgb && c.in_gamut()));
+
+        let black = &palette[0];
+        assert_component_eq!(black.components.0, 0.0);
+        assert_component_eq!(black.components.1, 0.0);
+        assert_component_eq!(black.components.2, 0.0);
+
+        let white = &palette[12];
+        assert_component_eq!(white.components.0, 1.0);
+        assert_component_eq!(white.components.1, 1.0);
+        assert_component_eq!(white.components.2, 1.0);
+
+        let lightness = palette
+            .iter()
+            .map(|c| c.to_space(Space::Oklch).components.0)
+            .collect::<Vec<_>>();
+        assert!(lightness.windows(2).all(|w| w[0] < w[1]));
+
+        // The hue is approximately preserved by the gamut mapping.
+        let hue = base.to_space(Space::Oklch).components.2;
+        for c in &palette[2..10] {
+            assert!((c.to_space(Space::Oklch).components.2 - hue).abs() < 3.0);
+        }
+    }
+
+    #[test]
+    fn sort_by_hue_groups_achromatic_colors() {
+        let red = Color::new(Space::Oklch, 0.6, 0.2, 30.0, 1.0);
+        let green = Color::new(Space::Oklch, 0.7, 0.2, 140.0, 1.0);
+        let blue = Color::new(Space::Oklch, 0.5, 0.2, 260.0, 1.0);
+        let white = Color::new(Space::Srgb, 1.0, 1.0, 1.0, 1.0);
+        let black = Color::new(Space::Srgb, 0.0, 0.0, 0.0, 1.0);
+
+        let mut colors = [
+            white.clone(),
+            blue.clone(),
+            black.clone(),
+            green.clone(),
+            red.clone(),
+        ];
+        sort_colors(&mut colors, SortKey::Hue);
+
+        let hues = colors.iter().map(|c| c.hue()).collect::<Vec<_>>();
+        assert!(hues[0].unwrap() < hues[1].unwrap());
+        assert!(hues[1].unwrap() < hues[2].unwrap());
+        assert_eq!(hues[3], None);
+        assert_eq!(hues[4], None);
+
+        // The achromatic colors are ordered by lightness.
+        assert_eq!(colors[3].components, black.components);
+        assert_eq!(colors[4].components, white.components);
+    }
+
+    #[test]
+    fn sort_with_total_order() {
+        let gray = Color::new(Space::Oklch, 0.5, 0.0, None, 1.0);
+        let red = Color::new(Space::Oklch, 0.5, 0.1, 30.0, 1.0);
+        let blue = Color::new(Space::Oklch, 0.5, 0.2, 260.0, 1.0);
+        let dark = Color::new(Space::Srgb, 0.1, 0.1, 0.1, 1.0);
+
+        let mut colors = vec![gray.clone(), blue.clone(), dark.clone(), red.clone()];
+        colors.sort_by(Color::total_cmp);
+        assert_eq!(colors, [dark, red, blue, gray.clone()]);
+
+        assert_eq!(gray.sort_key().1, 360.0);
+        assert_eq!(
+            gray.total_cmp(&gray.to_space(Space::Oklab)),
+            Ordering::Equal
+        );
+    }
+
+    #[test]
+    fn sort_by_lightness_and_chroma() {
+        let colors = [
+            Color::new(Space::Oklch, 0.8, 0.05, 100.0, 1.0),
+            Color::new(Space::Oklch, 0.2, 0.15, 200.0, 1.0),
+            Color::new(Space::Oklch, 0.5, 0.1, 300.0, 1.0),
+        ];
+
+        let mut by_lightness = colors.clone();
+        sort_colors(&mut by_lightness, SortKey::Lightness);
+        let lightness = by_lightness
+            .iter()
+            .map(|c| c.components.0)
+            .collect::<Vec<_>>();
+        assert_eq!(lightness, [0.2, 0.5, 0.8]);
+
+        let mut by_chroma = colors.clone();
+        sort_colors(&mut by_chroma, SortKey::Chroma);
+        let chroma = by_chroma.iter().map(|c| c.components.1).collect::<Vec<_>>();
+        assert_eq!(chroma, [0.05, 0.1, 0.15]);
+    }
+
+    #[test]
+    fn sort_ties_keep_original_order() {
+        let mut colors = [
+            Color::new(Space::Oklch, 0.5, 0.1, 120.0, 0.25),
+            Color::new(Space::Oklch, 0.5, 0.1, 120.0, 0.5),
+            Color::new(Space::Oklch, 0.5, 0.1, 120.0, 0.75),
+        ];
+        sort_colors(&mut colors, SortKey::Hue);
+
+        let alphas = colors.iter().map(|c| c.alpha).collect::<Vec<_>>();
+        assert_eq!(alphas, [0.25, 0.5, 0.75]);
+    }
+
+    #[test]
+    fn ramp_through_anchors() {
+        let anchors = [
+            Color::new(Space::Srgb, 0.0, 0.0, 0.0, 1.0),
+            Color::new(Space::Srgb, 1.0, 0.0, 0.0, 1.0),
+            Color::new(Space::Srgb, 1.0, 1.0, 1.0, 1.0),
+        ];
+
+        let ramp = color_ramp(&anchors, 5, Space::Srgb);
+        assert_eq!(ramp.len(), 5);
+        for (color, expected) in ramp.iter().zip([
+            [0.0, 0.0, 0.0],
+            [0.5, 0.0, 0.0],
+            [1.0, 0.0, 0.0],
+            [1.0, 0.5, 0.5],
+            [1.0, 1.0, 1.0],
+        ]) {
+            assert_component_eq!(color.components.0, expected[0]);
+            assert_component_eq!(color.components.1, expected[1]);
+            assert_component_eq!(color.components.2, expected[2]);
+        }
+
+        assert!(color_ramp(&[], 5, Space::Srgb).is_empty());
+        assert_eq!(color_ramp(&anchors[..1], 3, Space::Srgb).len(), 3);
+    }
+
+    #[test]
+    fn equalized_ramp_has_uniform_steps() {
+        // The first segment is much shorter perceptually than the second.
+        let anchors = [
+            Color::new(Space::Oklab, 0.2, 0.0, 0.0, 1.0),
+            Color::new(Space::Oklab, 0.25, 0.0, 0.0, 1.0),
+            Color::new(Space::Oklab, 0.9, 0.0, 0.0, 1.0),
+        ];
+
+        let deltas = |colors: &[Color]| {
+            colors
+                .windows(2)
+                .map(|pair| delta_eok(&pair[0], &pair[1]))
+                .collect::<Vec<_>>()
+        };
+
+        let plain = deltas(&color_ramp(&anchors, 7, Space::Oklab));
+        let equalized_ramp = color_ramp_equalized(&anchors, 7, Space::Oklab);
+        let equalized = deltas(&equalized_ramp);
+
+        let spread = |d: &[Component]| {
+            d.iter().cloned().fold(Component::MIN, Component::max)
+                - d.iter().cloned().fold(Component::MAX, Component::min)
+        };
+        assert!(spread(&plain) > 0.05);
+        assert!(spread(&equalized) < 1.0e-3);
+
+        assert_component_eq!(equalized_ramp[0].components.0, 0.2);
+        assert_component_eq!(equalized_ramp[6].components.0, 0.9);
+        assert_component_eq!(equalized_ramp[3].components.0, 0.55);
+    }
+
+    #[test]
+    fn schemes_are_returned_in_source_space() {
+        let red = Color::new(Space::Srgb, 1.0, 0.0, 0.0, 1.0);
+        let complementary = red.scheme(SchemeKind::Complementary);
+
+        assert_eq!(complementary[0].space, Space::Srgb);
+        assert_eq!(complementary[1].space, Space::Srgb);
+        assert_eq!(complementary[0].components, red.components);
+
+        let hue = complementary[1].to_space(Space::Oklch).components.2;
+        let red_hue = red.to_space(Space::Oklch).components.2;
+        assert_component_eq!(normalize_hue(hue - red_hue), 180.0);
+    }
+}
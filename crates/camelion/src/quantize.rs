@@ -0,0 +1,211 @@
+//! Reduce the pixels of an image to a small palette of representative colors.
+
+use std::collections::HashMap;
+
+use crate::{
+    models::{Oklab, Srgb, SrgbLinear, ToBase, ToXyz},
+    Component,
+};
+
+/// The size of the cells in Oklab that pixels are grouped in, for the
+/// lightness and the a and b components.
+const CELL_SIZE: (Component, Component) = (0.05, 0.04);
+
+/// The sums of the Oklab components of the pixels in a cell.
+#[derive(Clone, Debug, Default)]
+struct Cell {
+    lightness: Component,
+    a: Component,
+    b: Component,
+    population: usize,
+}
+
+/// A coarse histogram of pixels in the Oklab color space.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct Histogram {
+    cells: HashMap<(i32, i32, i32), Cell>,
+}
+
+impl Histogram {
+    /// Add a single pixel to the histogram.
+    pub fn add(&mut self, pixel: &Srgb) {
+        let (lightness_size, ab_size) = CELL_SIZE;
+
+        let oklab = Oklab::from(pixel.to_base());
+        let key = (
+            (oklab.lightness / lightness_size).round() as i32,
+            (oklab.a / ab_size).round() as i32,
+            (oklab.b / ab_size).round() as i32,
+        );
+
+        let cell = self.cells.entry(key).or_default();
+        cell.lightness += oklab.lightness;
+        cell.a += oklab.a;
+        cell.b += oklab.b;
+        cell.population += 1;
+    }
+
+    /// Return the average color and the population of each of the cells that
+    /// hold at least one pixel.
+    pub fn into_cells(self) -> Vec<(Oklab, usize)> {
+        // Sort the cells to make the result independent of the order of the
+        // hash map.
+        let mut cells: Vec<_> = self.cells.into_iter().collect();
+        cells.sort_by_key(|(key, _)| *key);
+
+        cells
+            .into_iter()
+            .map(|(_, cell)| {
+                let count = cell.population as Component;
+                let oklab = Oklab::new(cell.lightness / count, cell.a / count, cell.b / count);
+                (oklab, cell.population)
+            })
+            .collect()
+    }
+}
+
+/// Build a palette from a stream of pixels, without holding all the pixels in
+/// memory. Pixels are accumulated in a coarse histogram in the Oklab color
+/// space, which is clustered when the palette is extracted.
+/// ```rust
+/// use camelion::{models::Srgb, PaletteBuilder};
+/// let mut builder = PaletteBuilder::default();
+/// builder.push(&Srgb::new(1.0, 0.0, 0.0));
+/// builder.push(&Srgb::new(0.0, 0.0, 1.0));
+/// let palette = builder.finish(2);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct PaletteBuilder {
+    histogram: Histogram,
+}
+
+impl PaletteBuilder {
+    /// Add a single pixel.
+    pub fn push(&mut self, pixel: &Srgb) {
+        self.histogram.add(pixel);
+    }
+
+    /// Extract a palette of at most `k` colors from all the pixels that were
+    /// pushed, ordered from the most to the least common color. The cells of
+    /// the histogram are clustered with k-means in the Oklab color space,
+    /// weighted by the number of pixels in each cell.
+    pub fn finish(self, k: usize) -> Vec<Srgb> {
+        const ITERATIONS: usize = 16;
+
+        let cells: Vec<_> = self
+            .histogram
+            .into_cells()
+            .into_iter()
+            .map(|(oklab, population)| ([oklab.lightness, oklab.a, oklab.b], population))
+            .collect();
+
+        if k == 0 || cells.is_empty() {
+            return vec![];
+        }
+
+        let distance = |left: &[Component; 3], right: &[Component; 3]| {
+            left.iter()
+                .zip(right)
+                .map(|(l, r)| (l - r) * (l - r))
+                .sum::<Component>()
+        };
+        let nearest = |centroids: &[[Component; 3]], point: &[Component; 3]| {
+            (0..centroids.len())
+                .min_by(|&l, &r| {
+                    distance(&centroids[l], point).total_cmp(&distance(&centroids[r], point))
+                })
+                .unwrap()
+        };
+
+        // Start with the most common cell, then repeatedly add the cell that
+        // is furthest away from the existing centroids, weighted by its
+        // population. This is deterministic, unlike k-means++.
+        let mut centroids: Vec<[Component; 3]> = vec![];
+        while centroids.len() < k.min(cells.len()) {
+            let score = |(point, population): &([Component; 3], usize)| {
+                let distance = centroids
+                    .iter()
+                    .map(|centroid| distance(centroid, point))
+                    .reduce(Component::min)
+                    .unwrap_or(1.0);
+                distance * *population as Component
+            };
+            let next = cells
+                .iter()
+                .max_by(|left, right| score(left).total_cmp(&score(right)))
+                .unwrap();
+            centroids.push(next.0);
+        }
+
+        let mut populations = vec![0; centroids.len()];
+        for _ in 0..ITERATIONS {
+            let mut sums = vec![([0.0; 3], 0); centroids.len()];
+            for (point, population) in &cells {
+                let (sum, total) = &mut sums[nearest(&centroids, point)];
+                for (s, p) in sum.iter_mut().zip(point) {
+                    *s += p * *population as Component;
+                }
+                *total += population;
+            }
+
+            for (centroid, (sum, total)) in centroids.iter_mut().zip(&sums) {
+                if *total > 0 {
+                    *centroid = sum.map(|s| s / *total as Component);
+                }
+            }
+            populations = sums.iter().map(|(_, total)| *total).collect();
+        }
+
+        let mut palette: Vec<_> = centroids
+            .into_iter()
+            .zip(populations)
+            .filter(|(_, population)| *population > 0)
+            .collect();
+        palette.sort_by(|(_, left), (_, right)| right.cmp(left));
+
+        palette
+            .into_iter()
+            .map(|([lightness, a, b], _)| {
+                SrgbLinear::from(Oklab::new(lightness, a, b).to_xyz()).to_gamma_encoded()
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn build_palette_from_stream() {
+        let mut builder = PaletteBuilder::default();
+        for i in 0..100 {
+            let noise = (i % 5) as Component * 0.005;
+            builder.push(&Srgb::new(0.9 + noise, 0.1, 0.1));
+            if i % 2 == 0 {
+                builder.push(&Srgb::new(0.1, 0.2 + noise, 0.8));
+            }
+            if i % 4 == 0 {
+                builder.push(&Srgb::new(0.95, 0.95, 0.9 + noise));
+            }
+        }
+
+        let palette = builder.clone().finish(3);
+        assert_eq!(palette.len(), 3);
+
+        let expected = [(0.91, 0.1, 0.1), (0.1, 0.21, 0.8), (0.95, 0.95, 0.91)];
+        for (color, (red, green, blue)) in palette.iter().zip(expected) {
+            assert!((color.red - red).abs() < 0.02, "{:?}", color);
+            assert!((color.green - green).abs() < 0.02, "{:?}", color);
+            assert!((color.blue - blue).abs() < 0.02, "{:?}", color);
+        }
+
+        assert_eq!(builder.clone().finish(1).len(), 1);
+        assert!(builder.finish(0).is_empty());
+    }
+
+    #[test]
+    fn finish_without_pixels() {
+        assert!(PaletteBuilder::default().finish(4).is_empty());
+    }
+}
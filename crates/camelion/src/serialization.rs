@@ -0,0 +1,225 @@
+//! Implementations of the `serde` traits for colors and color spaces.
+//!
+//! A [`Color`] is serialized as a struct with its color space, its 3
+//! components and its alpha, e.g. in JSON:
+//!
+//! ```json
+//! { "space": "oklch", "components": [0.7, 0.1, null], "alpha": 1.0 }
+//! ```
+//!
+//! Missing components (and a missing alpha) are serialized as `null`. A
+//! [`Space`] is serialized as its identifier, see [`Space::name`].
+
+use std::fmt;
+
+use serde::{
+    de::{self, MapAccess, SeqAccess, Unexpected, Visitor},
+    ser::SerializeStruct,
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+use crate::{Color, Component, Space};
+
+impl Serialize for Space {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(self.name())
+    }
+}
+
+struct SpaceVisitor;
+
+impl<'de> Visitor<'de> for SpaceVisitor {
+    type Value = Space;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a color space identifier, e.g. \"srgb\"")
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Space::from_name(v).ok_or_else(|| E::invalid_value(Unexpected::Str(v), &self))
+    }
+}
+
+impl<'de> Deserialize<'de> for Space {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_str(SpaceVisitor)
+    }
+}
+
+const FIELDS: &[&str] = &["space", "components", "alpha"];
+
+impl Serialize for Color {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut color = serializer.serialize_struct("Color", FIELDS.len())?;
+        color.serialize_field("space", &self.space)?;
+        color.serialize_field("components", &[self.c0(), self.c1(), self.c2()])?;
+        color.serialize_field("alpha", &self.alpha())?;
+        color.end()
+    }
+}
+
+struct ColorVisitor;
+
+impl<'de> Visitor<'de> for ColorVisitor {
+    type Value = Color;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a color with a space, components and alpha")
+    }
+
+    /// Formats that do not store field names (e.g. bincode) serialize the
+    /// fields as a sequence, in the order of [`FIELDS`].
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let space: Space = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+        let [c0, c1, c2]: [Option<Component>; 3] = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+        // Like in `visit_map`, colors without an alpha are opaque.
+        let alpha = seq.next_element()?.unwrap_or(Some(1.0));
+
+        Ok(Color::new(space, c0, c1, c2, alpha))
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut space: Option<Space> = None;
+        let mut components: Option<[Option<Component>; 3]> = None;
+        let mut alpha: Option<Option<Component>> = None;
+
+        while let Some(key) = map.next_key::<String>()? {
+            match key.as_str() {
+                "space" if space.is_some() => return Err(de::Error::duplicate_field("space")),
+                "space" => space = Some(map.next_value()?),
+                "components" if components.is_some() => {
+                    return Err(de::Error::duplicate_field("components"))
+                }
+                "components" => components = Some(map.next_value()?),
+                "alpha" if alpha.is_some() => return Err(de::Error::duplicate_field("alpha")),
+                "alpha" => alpha = Some(map.next_value()?),
+                _ => return Err(de::Error::unknown_field(&key, FIELDS)),
+            }
+        }
+
+        let space = space.ok_or_else(|| de::Error::missing_field("space"))?;
+        let [c0, c1, c2] = components.ok_or_else(|| de::Error::missing_field("components"))?;
+        // Colors without an alpha field are opaque. `Color::new` clamps the
+        // alpha and sets the flags for the missing components.
+        let alpha = alpha.unwrap_or(Some(1.0));
+
+        Ok(Color::new(space, c0, c1, c2, alpha))
+    }
+}
+
+impl<'de> Deserialize<'de> for Color {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_struct("Color", FIELDS, ColorVisitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Flags;
+
+    #[test]
+    fn serialize_color() {
+        let color = Color::new(Space::Oklch, 0.5, 0.25, None, 0.75);
+        assert_eq!(
+            serde_json::to_string(&color).unwrap(),
+            r#"{"space":"oklch","components":[0.5,0.25,null],"alpha":0.75}"#
+        );
+
+        let color = Color::new(Space::XyzD65, 0.5, 0.25, 0.125, None);
+        assert_eq!(
+            serde_json::to_string(&color).unwrap(),
+            r#"{"space":"xyz-d65","components":[0.5,0.25,0.125],"alpha":null}"#
+        );
+    }
+
+    #[test]
+    fn deserialize_color() {
+        let color: Color = serde_json::from_str(
+            r#"{ "space": "srgb-linear", "components": [null, 0.5, 1.0], "alpha": 2.0 }"#,
+        )
+        .unwrap();
+        assert_eq!(color.space, Space::SrgbLinear);
+        assert_eq!(color.flags, Flags::C0_IS_NONE);
+        assert_eq!(color.c1(), Some(0.5));
+        assert_eq!(color.alpha(), Some(1.0));
+
+        // A missing alpha field is opaque, but null is a missing alpha.
+        let color: Color =
+            serde_json::from_str(r#"{ "space": "lab", "components": [50, 10, -10] }"#).unwrap();
+        assert_eq!(color, Color::new(Space::Lab, 50.0, 10.0, -10.0, 1.0));
+        let color: Color = serde_json::from_str(
+            r#"{ "space": "lab", "components": [50, 10, -10], "alpha": null }"#,
+        )
+        .unwrap();
+        assert_eq!(color.flags, Flags::ALPHA_IS_NONE);
+
+        for invalid in [
+            r#"{ "space": "rgb", "components": [1, 0, 0] }"#,
+            r#"{ "space": "srgb", "components": [1, 0] }"#,
+            r#"{ "space": "srgb" }"#,
+            r#"{ "space": "srgb", "components": [1, 0, 0], "hue": 0 }"#,
+        ] {
+            assert!(
+                serde_json::from_str::<Color>(invalid).is_err(),
+                "{}",
+                invalid
+            );
+        }
+    }
+
+    #[test]
+    fn deserialize_color_from_sequence() {
+        let color: Color = serde_json::from_str(r#"["oklch", [0.5, 0.25, null], 0.75]"#).unwrap();
+        assert_eq!(color, Color::new(Space::Oklch, 0.5, 0.25, None, 0.75));
+
+        let color: Color = serde_json::from_str(r#"["lab", [50, 10, -10]]"#).unwrap();
+        assert_eq!(color, Color::new(Space::Lab, 50.0, 10.0, -10.0, 1.0));
+
+        for invalid in [r#"[]"#, r#"["srgb"]"#, r#"[[1, 0, 0], "srgb"]"#] {
+            assert!(
+                serde_json::from_str::<Color>(invalid).is_err(),
+                "{}",
+                invalid
+            );
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        for space in (0..=u8::MAX).filter_map(Space::from_u8) {
+            let json = serde_json::to_string(&space).unwrap();
+            assert_eq!(json, format!("\"{}\"", space.name()));
+            assert_eq!(serde_json::from_str::<Space>(&json).unwrap(), space);
+
+            let color = Color::new(space, 0.25, None, 0.75, 0.5);
+            let json = serde_json::to_string(&color).unwrap();
+            assert_eq!(serde_json::from_str::<Color>(&json).unwrap(), color);
+        }
+    }
+}
@@ -0,0 +1,205 @@
+//! Extract named swatches from the pixels of an image, like Vibrant.js does,
+//! but scored in the Oklab color space.
+//! <https://github.com/Vibrant-Colors/node-vibrant>
+
+use crate::{
+    models::{Oklab, Srgb, SrgbLinear, ToXyz},
+    quantize::Histogram,
+    Component,
+};
+
+/// A color that is representative for a group of pixels.
+#[derive(Clone, Debug)]
+pub struct Swatch {
+    /// The average color of the pixels in the group.
+    pub color: Srgb,
+    /// The number of pixels in the group.
+    pub population: usize,
+}
+
+/// The named swatches extracted by [`extract_swatches`]. A swatch is None if
+/// no group of pixels matches its target.
+#[derive(Clone, Debug, Default)]
+pub struct Swatches {
+    /// A saturated color with medium lightness.
+    pub vibrant: Option<Swatch>,
+    /// A saturated dark color.
+    pub dark_vibrant: Option<Swatch>,
+    /// A saturated light color.
+    pub light_vibrant: Option<Swatch>,
+    /// A desaturated color with medium lightness.
+    pub muted: Option<Swatch>,
+    /// A desaturated dark color.
+    pub dark_muted: Option<Swatch>,
+    /// A desaturated light color.
+    pub light_muted: Option<Swatch>,
+}
+
+/// The (minimum, target, maximum) values of a property of a swatch.
+type Range = (Component, Component, Component);
+
+const DARK_LIGHTNESS: Range = (0.0, 0.26, 0.45);
+const NORMAL_LIGHTNESS: Range = (0.3, 0.5, 0.7);
+const LIGHT_LIGHTNESS: Range = (0.55, 0.74, 1.0);
+const MUTED_SATURATION: Range = (0.0, 0.3, 0.4);
+const VIBRANT_SATURATION: Range = (0.35, 1.0, 1.0);
+
+const WEIGHT_SATURATION: Component = 3.0;
+const WEIGHT_LIGHTNESS: Component = 6.5;
+const WEIGHT_POPULATION: Component = 0.5;
+
+/// The Oklch chroma that is considered fully saturated.
+const FULL_CHROMA: Component = 0.4;
+
+/// A group of similar pixels.
+struct Candidate {
+    lightness: Component,
+    saturation: Component,
+    oklab: Oklab,
+    population: usize,
+}
+
+impl Candidate {
+    /// Score how well this candidate matches the target lightness and
+    /// saturation, or None if it is outside the target ranges.
+    fn score(
+        &self,
+        lightness: Range,
+        saturation: Range,
+        max_population: usize,
+    ) -> Option<Component> {
+        let within = |value: Component, (min, _, max): Range| value >= min && value <= max;
+        if !within(self.lightness, lightness) || !within(self.saturation, saturation) {
+            return None;
+        }
+
+        let similarity = |value: Component, (_, target, _): Range| 1.0 - (value - target).abs();
+        let score = similarity(self.saturation, saturation) * WEIGHT_SATURATION
+            + similarity(self.lightness, lightness) * WEIGHT_LIGHTNESS
+            + self.population as Component / max_population as Component * WEIGHT_POPULATION;
+
+        Some(score / (WEIGHT_SATURATION + WEIGHT_LIGHTNESS + WEIGHT_POPULATION))
+    }
+
+    fn to_swatch(&self) -> Swatch {
+        Swatch {
+            color: SrgbLinear::from(self.oklab.to_xyz()).to_gamma_encoded(),
+            population: self.population,
+        }
+    }
+}
+
+/// Group the pixels into cells in the Oklab color space, returning the
+/// average color of each cell.
+fn quantize(pixels: &[Srgb]) -> Vec<Candidate> {
+    let mut histogram = Histogram::default();
+    for pixel in pixels {
+        histogram.add(pixel);
+    }
+
+    histogram
+        .into_cells()
+        .into_iter()
+        .map(|(oklab, population)| {
+            let chroma = (oklab.a * oklab.a + oklab.b * oklab.b).sqrt();
+            Candidate {
+                lightness: oklab.lightness,
+                saturation: (chroma / FULL_CHROMA).min(1.0),
+                oklab,
+                population,
+            }
+        })
+        .collect()
+}
+
+/// Extract the named swatches (vibrant, muted, etc.) from the pixels of an
+/// image. Similar pixels are grouped together and each swatch picks the
+/// group that best matches its target lightness and saturation, weighted by
+/// the population of the group. A group is only used for a single swatch.
+///
+/// NOTE: Unlike Vibrant.js, lightness is the Oklab lightness and saturation
+///       is the Oklch chroma relative to 0.4, which makes the result more
+///       perceptually uniform across hues.
+pub fn extract_swatches(pixels: &[Srgb]) -> Swatches {
+    let candidates = quantize(pixels);
+    let max_population = candidates.iter().map(|c| c.population).max().unwrap_or(0);
+
+    let mut used = vec![false; candidates.len()];
+    let mut find = |lightness: Range, saturation: Range| {
+        let (index, _) = candidates
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| !used[*index])
+            .filter_map(|(index, candidate)| {
+                candidate
+                    .score(lightness, saturation, max_population)
+                    .map(|score| (index, score))
+            })
+            .max_by(|(_, left), (_, right)| left.total_cmp(right))?;
+
+        used[index] = true;
+        Some(candidates[index].to_swatch())
+    };
+
+    // The same order as Vibrant.js, which matters because every group is only
+    // used once.
+    let vibrant = find(NORMAL_LIGHTNESS, VIBRANT_SATURATION);
+    let light_vibrant = find(LIGHT_LIGHTNESS, VIBRANT_SATURATION);
+    let dark_vibrant = find(DARK_LIGHTNESS, VIBRANT_SATURATION);
+    let muted = find(NORMAL_LIGHTNESS, MUTED_SATURATION);
+    let light_muted = find(LIGHT_LIGHTNESS, MUTED_SATURATION);
+    let dark_muted = find(DARK_LIGHTNESS, MUTED_SATURATION);
+
+    Swatches {
+        vibrant,
+        dark_vibrant,
+        light_vibrant,
+        muted,
+        dark_muted,
+        light_muted,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_color(swatch: &Option<Swatch>, red: Component, green: Component, blue: Component) {
+        let color = &swatch.as_ref().expect("swatch was not found").color;
+        assert!(
+            (color.red - red).abs() < 1.0e-3
+                && (color.green - green).abs() < 1.0e-3
+                && (color.blue - blue).abs() < 1.0e-3,
+            "{:?} != ({}, {}, {})",
+            color,
+            red,
+            green,
+            blue
+        );
+    }
+
+    #[test]
+    fn extract_named_swatches() {
+        let mut pixels = vec![];
+        pixels.extend(std::iter::repeat_n(Srgb::new(1.0, 0.0, 0.0), 50));
+        pixels.extend(std::iter::repeat_n(Srgb::new(1.0, 1.0, 0.0), 20));
+        pixels.extend(std::iter::repeat_n(Srgb::new(0.0, 0.0, 0.5), 20));
+        pixels.extend(std::iter::repeat_n(Srgb::new(0.5, 0.4, 0.4), 10));
+
+        let swatches = extract_swatches(&pixels);
+        assert_color(&swatches.vibrant, 1.0, 0.0, 0.0);
+        assert_color(&swatches.light_vibrant, 1.0, 1.0, 0.0);
+        assert_color(&swatches.dark_vibrant, 0.0, 0.0, 0.5);
+        assert_color(&swatches.muted, 0.5, 0.4, 0.4);
+        assert_eq!(swatches.vibrant.unwrap().population, 50);
+        assert!(swatches.light_muted.is_none());
+        assert!(swatches.dark_muted.is_none());
+    }
+
+    #[test]
+    fn extract_swatches_without_pixels() {
+        let swatches = extract_swatches(&[]);
+        assert!(swatches.vibrant.is_none());
+        assert!(swatches.muted.is_none());
+    }
+}
@@ -1,8 +1,15 @@
 /// Check for equality between two components allowing for 8-bit rounding
-/// errors.
+/// errors, or for the given `epsilon`. Missing (NaN) components are only
+/// equal to other missing components.
 #[macro_export]
 macro_rules! assert_component_eq {
     ($actual:expr,$expected:expr) => {{
-        approx::assert_abs_diff_eq!($actual, $expected, epsilon = 1.0 / i8::MAX as Component);
+        $crate::assert_component_eq!($actual, $expected, epsilon = 1.0 / i8::MAX as Component);
+    }};
+    ($actual:expr,$expected:expr,epsilon = $epsilon:expr) => {{
+        let (actual, expected): ($crate::Component, $crate::Component) = ($actual, $expected);
+        if !(actual.is_nan() && expected.is_nan()) {
+            approx::assert_abs_diff_eq!(actual, expected, epsilon = $epsilon);
+        }
     }};
 }
@@ -0,0 +1,150 @@
+//! The transfer functions of the RGB color spaces, operating on a single
+//! component.
+//!
+//! The `oetf` functions convert a linear light value to a gamma encoded value
+//! and the `eotf` functions convert a gamma encoded value back to linear
+//! light. Negative values are handled by mirroring the curve around 0, as
+//! required by CSS for out of gamut colors.
+//! <https://drafts.csswg.org/css-color-4/#color-conversion-code>
+
+use crate::{
+    math::{pq_decode, pq_encode, PQ_M2},
+    Component,
+};
+
+/// Convert a linear light sRGB value to gamma encoded. Also used by
+/// Display-P3.
+pub fn srgb_oetf(value: Component) -> Component {
+    let abs = value.abs();
+
+    if abs > 0.0031308 {
+        value.signum() * (1.055 * abs.powf(1.0 / 2.4) - 0.055)
+    } else {
+        12.92 * value
+    }
+}
+
+/// Convert a gamma encoded sRGB value to linear light. Also used by
+/// Display-P3.
+pub fn srgb_eotf(value: Component) -> Component {
+    let abs = value.abs();
+
+    if abs < 0.04045 {
+        value / 12.92
+    } else {
+        value.signum() * ((abs + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+/// Convert a linear light A98 RGB value to gamma encoded.
+pub fn a98_rgb_oetf(value: Component) -> Component {
+    value.signum() * value.abs().powf(256.0 / 563.0)
+}
+
+/// Convert a gamma encoded A98 RGB value to linear light.
+pub fn a98_rgb_eotf(value: Component) -> Component {
+    value.signum() * value.abs().powf(563.0 / 256.0)
+}
+
+/// Convert a linear light ProPhoto RGB value to gamma encoded.
+pub fn prophoto_rgb_oetf(value: Component) -> Component {
+    const E: Component = 1.0 / 512.0;
+
+    let abs = value.abs();
+
+    if abs >= E {
+        value.signum() * abs.powf(1.0 / 1.8)
+    } else {
+        16.0 * value
+    }
+}
+
+/// Convert a gamma encoded ProPhoto RGB value to linear light.
+pub fn prophoto_rgb_eotf(value: Component) -> Component {
+    const E: Component = 16.0 / 512.0;
+
+    let abs = value.abs();
+
+    if abs <= E {
+        value / 16.0
+    } else {
+        value.signum() * abs.powf(1.8)
+    }
+}
+
+#[allow(clippy::excessive_precision)]
+const REC2020_ALPHA: Component = 1.09929682680944;
+#[allow(clippy::excessive_precision)]
+const REC2020_BETA: Component = 0.018053968510807;
+
+/// Convert a linear light Rec. 2020 value to gamma encoded.
+pub fn rec2020_oetf(value: Component) -> Component {
+    let abs = value.abs();
+
+    if abs > REC2020_BETA {
+        value.signum() * (REC2020_ALPHA * abs.powf(0.45) - (REC2020_ALPHA - 1.0))
+    } else {
+        4.5 * value
+    }
+}
+
+/// Convert a gamma encoded Rec. 2020 value to linear light.
+pub fn rec2020_eotf(value: Component) -> Component {
+    let abs = value.abs();
+
+    if abs < REC2020_BETA * 4.5 {
+        value / 4.5
+    } else {
+        value.signum() * ((abs + REC2020_ALPHA - 1.0) / REC2020_ALPHA).powf(1.0 / 0.45)
+    }
+}
+
+/// Convert a linear light value, where 1.0 is 10000 cd/m², to a signal
+/// encoded with the SMPTE ST 2084 perceptual quantizer (PQ). Negative values
+/// are clamped to 0. See [`Xyz::to_absolute`](crate::models::Xyz::to_absolute)
+/// to get absolute luminance from a color.
+/// <https://www.itu.int/rec/R-REC-BT.2100>
+pub fn pq_oetf(value: Component) -> Component {
+    pq_encode(value, PQ_M2)
+}
+
+/// Convert a signal encoded with the SMPTE ST 2084 perceptual quantizer (PQ)
+/// to a linear light value, where 1.0 is 10000 cd/m².
+/// <https://www.itu.int/rec/R-REC-BT.2100>
+pub fn pq_eotf(value: Component) -> Component {
+    pq_decode(value, PQ_M2)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_component_eq;
+
+    #[test]
+    fn round_trip() {
+        type Curve = fn(Component) -> Component;
+
+        let curves: [(Curve, Curve); 5] = [
+            (srgb_oetf, srgb_eotf),
+            (a98_rgb_oetf, a98_rgb_eotf),
+            (prophoto_rgb_oetf, prophoto_rgb_eotf),
+            (rec2020_oetf, rec2020_eotf),
+            (pq_oetf, pq_eotf),
+        ];
+
+        for (oetf, eotf) in curves {
+            for value in [0.0, 0.001, 0.01, 0.18, 0.5, 1.0] {
+                assert_component_eq!(eotf(oetf(value)), value);
+            }
+        }
+    }
+
+    #[test]
+    fn known_values() {
+        assert_component_eq!(srgb_oetf(0.18), 0.46135613);
+        assert_component_eq!(srgb_eotf(0.5), 0.21404114);
+        assert_component_eq!(srgb_oetf(-0.18), -0.46135613);
+        assert_component_eq!(rec2020_oetf(0.18), 0.40900773);
+        assert_component_eq!(pq_oetf(0.01), 0.5080784);
+    }
+}
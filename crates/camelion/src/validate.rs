@@ -0,0 +1,166 @@
+//! Diagnostics for color components that CSS would not accept as is.
+
+use crate::{Color, Component, Flags, Space};
+
+/// The rule that a component of a color violates. See [`Color::validate`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ValidationRule {
+    /// The value is NaN or infinite.
+    NotFinite,
+    /// The value is below the minimum and is clamped to it at parse time.
+    BelowMinimum(Component),
+    /// The value is above the maximum and is clamped to it at parse time.
+    AboveMaximum(Component),
+}
+
+impl std::fmt::Display for ValidationRule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ValidationRule::NotFinite => write!(f, "is not a finite number"),
+            ValidationRule::BelowMinimum(min) => write!(f, "is below the minimum of {}", min),
+            ValidationRule::AboveMaximum(max) => write!(f, "is above the maximum of {}", max),
+        }
+    }
+}
+
+/// A component of a color that violates one of the rules for its color space.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Warning {
+    /// The short name of the component, as returned by
+    /// [`Space::component_names`], or "alpha" for the alpha component.
+    pub component: &'static str,
+    /// The value of the component.
+    pub value: Component,
+    /// The rule that the value violates.
+    pub rule: ValidationRule,
+}
+
+impl std::fmt::Display for Warning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "component {} ({}) {}",
+            self.component, self.value, self.rule
+        )
+    }
+}
+
+impl Space {
+    /// The range that CSS clamps each of the components of this color space to
+    /// at parse time. Components without a range are never clamped, e.g. RGB
+    /// components outside [0..1] are valid, but out of gamut.
+    /// <https://drafts.csswg.org/css-color-4/#specifying-lab-lch>
+    pub(crate) fn clamped_ranges(&self) -> [Option<(Component, Component)>; 3] {
+        const UNIT: Option<(Component, Component)> = Some((0.0, 1.0));
+        const PERCENT: Option<(Component, Component)> = Some((0.0, 100.0));
+        const POSITIVE: Option<(Component, Component)> = Some((0.0, Component::INFINITY));
+
+        match self {
+            Space::Hsl | Space::Hwb | Space::Hsv => [None, UNIT, UNIT],
+            Space::Lab | Space::LabD65 => [PERCENT, None, None],
+            Space::Lch | Space::LchD65 => [PERCENT, POSITIVE, None],
+            Space::Oklab => [UNIT, None, None],
+            Space::Oklch | Space::Jzczhz => [UNIT, POSITIVE, None],
+            Space::Hct => [None, POSITIVE, PERCENT],
+            Space::Srgb
+            | Space::SrgbLinear
+            | Space::DisplayP3
+            | Space::A98Rgb
+            | Space::ProPhotoRgb
+            | Space::Rec2020
+            | Space::XyzD50
+            | Space::XyzD65
+            | Space::Jzazbz
+            | Space::Ipt
+            | Space::ICtCp => [None, None, None],
+        }
+    }
+}
+
+impl Color {
+    /// Return a warning for each of the components of this color that CSS
+    /// would not accept as is, e.g. an HSL saturation above 100%, which is
+    /// clamped at parse time. Missing components are never reported.
+    pub fn validate(&self) -> Vec<Warning> {
+        let names = self.space.component_names();
+        let ranges = self.space.clamped_ranges();
+
+        let channels = [
+            (names[0], self.components.0, ranges[0], Flags::C0_IS_NONE),
+            (names[1], self.components.1, ranges[1], Flags::C1_IS_NONE),
+            (names[2], self.components.2, ranges[2], Flags::C2_IS_NONE),
+            ("alpha", self.alpha, Some((0.0, 1.0)), Flags::ALPHA_IS_NONE),
+        ];
+
+        channels
+            .into_iter()
+            .filter(|(_, _, _, flag)| !self.flags.contains(*flag))
+            .filter_map(|(component, value, range, _)| {
+                let rule = if !value.is_finite() {
+                    ValidationRule::NotFinite
+                } else {
+                    match range {
+                        Some((min, _)) if value < min => ValidationRule::BelowMinimum(min),
+                        Some((_, max)) if value > max => ValidationRule::AboveMaximum(max),
+                        _ => return None,
+                    }
+                };
+
+                Some(Warning {
+                    component,
+                    value,
+                    rule,
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn valid_colors_have_no_warnings() {
+        assert!(Color::new(Space::Srgb, 1.5, -0.5, 0.5, 1.0)
+            .validate()
+            .is_empty());
+        assert!(Color::new(Space::Hsl, 720.0, 0.5, None, None)
+            .validate()
+            .is_empty());
+        assert!(Color::new(Space::Lch, 50.0, 300.0, -30.0, 1.0)
+            .validate()
+            .is_empty());
+    }
+
+    #[test]
+    fn out_of_range_components_are_reported() {
+        let warnings = Color::new(Space::Hsl, 120.0, 1.5, -0.25, 1.0).validate();
+        assert_eq!(
+            warnings,
+            vec![
+                Warning {
+                    component: "S",
+                    value: 1.5,
+                    rule: ValidationRule::AboveMaximum(1.0),
+                },
+                Warning {
+                    component: "L",
+                    value: -0.25,
+                    rule: ValidationRule::BelowMinimum(0.0),
+                },
+            ]
+        );
+        assert_eq!(
+            warnings[0].to_string(),
+            "component S (1.5) is above the maximum of 1"
+        );
+
+        let warnings = Color::new(Space::Oklch, 0.5, -0.1, Component::NAN, 1.0).validate();
+        assert_eq!(warnings.len(), 2);
+        assert_eq!(warnings[0].component, "C");
+        assert_eq!(warnings[0].rule, ValidationRule::BelowMinimum(0.0));
+        assert_eq!(warnings[1].component, "H");
+        assert_eq!(warnings[1].rule, ValidationRule::NotFinite);
+    }
+}
@@ -94,12 +94,34 @@ pub fn gen_model(input: TokenStream) -> TokenStream {
             }
         }
 
+        // Implemented by hand, because deriving it would require the generic
+        // color space tags to implement `PartialEq` as well.
+        impl #impl_gen PartialEq for #struct_name #type_gen {
+            fn eq(&self, other: &Self) -> bool {
+                self.#field1 == other.#field1
+                    && self.#field2 == other.#field2
+                    && self.#field3 == other.#field3
+            }
+        }
+
         impl #impl_gen From<crate::color::Components> for #struct_name #type_gen {
             fn from(value: crate::color::Components) -> Self {
                 Self::new(value.0, value.1, value.2)
             }
         }
 
+        impl #impl_gen From<[crate::Component; 3]> for #struct_name #type_gen {
+            fn from(value: [crate::Component; 3]) -> Self {
+                Self::new(value[0], value[1], value[2])
+            }
+        }
+
+        impl #impl_gen From<#struct_name #type_gen> for [crate::Component; 3] {
+            fn from(value: #struct_name #type_gen) -> Self {
+                [value.#field1, value.#field2, value.#field3]
+            }
+        }
+
         impl #impl_gen crate::models::Model for #struct_name #type_gen
         where
             Self: crate::color::CssColorSpaceId